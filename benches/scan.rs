@@ -0,0 +1,45 @@
+//! Serial vs parallel `PathScanner::scan` over a 100-entry PATH.
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use spath_cli::scanner::PathScanner;
+use std::hint::black_box;
+
+const ENTRY_COUNT: usize = 100;
+
+/// A PATH mixing existing, missing, spaced and relative entries.
+fn path_value() -> String {
+    let temp = std::env::temp_dir();
+    let temp = temp.display();
+    (0..ENTRY_COUNT)
+        .map(|i| match i % 4 {
+            0 => "C:\\Windows\\System32".to_string(),
+            1 => format!("{}\\spath_bench_missing_{}", temp, i),
+            2 => format!("C:\\Program Files\\spath bench {}", i),
+            _ => format!("tools\\bin{}", i),
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn scan_benchmark(c: &mut Criterion) {
+    let path_value = path_value();
+    let mut group = c.benchmark_group("scan_100_entries");
+    for parallel in [false, true] {
+        let name = if parallel { "parallel" } else { "serial" };
+        group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            &parallel,
+            |b, &parallel| {
+                b.iter(|| {
+                    PathScanner::from_string(black_box(path_value.as_str()))
+                        .with_parallel(parallel)
+                        .scan()
+                        .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, scan_benchmark);
+criterion_main!(benches);
@@ -1,17 +1,94 @@
 //! System PATH analyzer.
-use crate::models::{PathEntry, PathLocation};
+use crate::constants::{
+    EXECUTABLE_EXTENSIONS, LANGUAGE_RUNTIMES, PROGRAM_FILES, PROGRAM_FILES_X86,
+};
+use crate::models::{PathCategory, PathEntry, PathLocation};
 use crate::registry::RegistryHelper;
+use crate::utils::{expand_env_vars, normalize_for_comparison};
 use anyhow::Result;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+use tracing::debug;
 
+#[derive(Debug, Clone, Serialize)]
 pub struct AnalysisResults {
     pub entries: Vec<PathEntry>,
 }
 
-pub struct SystemAnalyzer;
+/// The entries of an analysis grouped the way `analyze` reports them.
+#[derive(Debug, Serialize)]
+pub struct AnalysisGroups<'a> {
+    /// User-specific entries found in SYSTEM PATH.
+    pub misplaced: Vec<&'a PathEntry>,
+    pub unquoted_system: Vec<&'a PathEntry>,
+    pub unquoted_user: Vec<&'a PathEntry>,
+    /// Every occurrence after the first of an entry.
+    pub duplicates: Vec<&'a PathEntry>,
+}
+
+impl AnalysisResults {
+    /// Groups the entries into misplaced, unquoted and duplicate ones.
+    pub fn groups(&self) -> AnalysisGroups<'_> {
+        let misplaced = self
+            .entries
+            .iter()
+            .filter(|e| e.should_be_in_user_path())
+            .collect();
+        let unquoted_system = self
+            .entries
+            .iter()
+            .filter(|e| {
+                e.location == PathLocation::System
+                    && e.category == PathCategory::SystemProgram
+                    && e.needs_quotes()
+            })
+            .collect();
+        let unquoted_user = self
+            .entries
+            .iter()
+            .filter(|e| e.location == PathLocation::User && e.needs_quotes())
+            .collect();
+        let mut seen = HashSet::new();
+        let duplicates = self
+            .entries
+            .iter()
+            .filter(|e| !seen.insert(normalize_for_comparison(&e.path)))
+            .collect();
+        AnalysisGroups {
+            misplaced,
+            unquoted_system,
+            unquoted_user,
+            duplicates,
+        }
+    }
+}
+
+pub struct SystemAnalyzer {
+    offline: bool,
+    timeout: Option<Duration>,
+}
 
 impl SystemAnalyzer {
     pub fn new() -> Result<Self> {
-        Ok(Self)
+        Ok(Self {
+            offline: false,
+            timeout: None,
+        })
+    }
+    /// Builds entries without touching the filesystem, leaving their
+    /// existence unchecked (see [`PathEntry::offline`]).
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+    /// Bounds each existence check; entries that take longer are left
+    /// unchecked instead of hanging the analysis.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
     }
     pub fn analyze(&self) -> Result<AnalysisResults> {
         let system_paths = RegistryHelper::read_system_path()?;
@@ -21,26 +98,265 @@ impl SystemAnalyzer {
             .chain(user_paths.iter())
             .cloned()
             .collect();
-        let mut entries = Vec::new();
-        let mut index = 0;
-        for path in &system_paths {
-            entries.push(PathEntry::new(
-                path.clone(),
-                index,
-                PathLocation::System,
-                &all_paths,
-            ));
-            index += 1;
-        }
-        for path in &user_paths {
-            entries.push(PathEntry::new(
-                path.clone(),
-                index,
-                PathLocation::User,
-                &all_paths,
-            ));
-            index += 1;
-        }
+        let counts = PathEntry::path_counts(&all_paths);
+        let locations = std::iter::repeat_n(PathLocation::System, system_paths.len())
+            .chain(std::iter::repeat(PathLocation::User));
+        let entries = all_paths
+            .into_iter()
+            .zip(locations)
+            .enumerate()
+            .map(|(index, (path, location))| {
+                if self.offline {
+                    PathEntry::offline(path, index, location, &counts)
+                } else {
+                    PathEntry::with_path_counts_within(path, index, location, &counts, self.timeout)
+                }
+            })
+            .collect();
         Ok(AnalysisResults { entries })
     }
 }
+
+/// A USER entry that SYSTEM PATH already provides.
+///
+/// The effective PATH is SYSTEM followed by USER, so the USER copy never wins.
+#[derive(Debug, Clone)]
+pub struct RedundantUserEntry {
+    pub user_path: String,
+    pub system_path: String,
+}
+
+/// Lists USER entries that duplicate a SYSTEM entry and can be removed from USER.
+pub fn detect_redundant_user_entries(results: &AnalysisResults) -> Vec<RedundantUserEntry> {
+    let mut system_paths: HashMap<String, &str> = HashMap::new();
+    for entry in &results.entries {
+        if entry.location == PathLocation::System {
+            system_paths
+                .entry(normalize_for_comparison(&entry.path))
+                .or_insert(&entry.path);
+        }
+    }
+    results
+        .entries
+        .iter()
+        .filter(|e| e.location == PathLocation::User)
+        .filter_map(|e| {
+            system_paths
+                .get(&normalize_for_comparison(&e.path))
+                .map(|system_path| RedundantUserEntry {
+                    user_path: e.path.clone(),
+                    system_path: system_path.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// A PATH directory that provides a language runtime executable.
+#[derive(Debug, Clone)]
+pub struct RuntimeProvider {
+    pub directory: String,
+    /// Output of `<exe> --version`, only filled in when versions are resolved.
+    pub version: Option<String>,
+}
+
+/// A runtime executable found in more than one PATH directory.
+#[derive(Debug, Clone)]
+pub struct RuntimeShadowing {
+    pub executable: String,
+    /// Providers in PATH order; the first one wins.
+    pub providers: Vec<RuntimeProvider>,
+}
+
+impl RuntimeShadowing {
+    pub fn winner(&self) -> &RuntimeProvider {
+        &self.providers[0]
+    }
+}
+
+/// Finds language runtimes provided by more than one directory in `paths`.
+///
+/// By default this only checks for the executables on disk. With
+/// `resolve_versions` each copy is run with `--version` to label it.
+pub fn detect_runtime_shadowing(paths: &[String], resolve_versions: bool) -> Vec<RuntimeShadowing> {
+    let mut reports = Vec::new();
+    for executable in LANGUAGE_RUNTIMES {
+        let providers: Vec<RuntimeProvider> = paths
+            .iter()
+            .filter_map(|path| {
+                let directory = expand_env_vars(path.trim().trim_matches('"'));
+                let candidate = Path::new(&directory).join(executable);
+                if !candidate.is_file() {
+                    return None;
+                }
+                let version = if resolve_versions {
+                    read_runtime_version(&candidate)
+                } else {
+                    None
+                };
+                Some(RuntimeProvider {
+                    directory: path.clone(),
+                    version,
+                })
+            })
+            .collect();
+        if providers.len() > 1 {
+            debug!(
+                "{} is provided by {} directories",
+                executable,
+                providers.len()
+            );
+            reports.push(RuntimeShadowing {
+                executable: executable.to_string(),
+                providers,
+            });
+        }
+    }
+    reports
+}
+
+/// An executable whose winning PATH directory differs between two PATHs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionChange {
+    pub executable: String,
+    /// Directory that provides the executable before, if any.
+    pub before: Option<String>,
+    /// Directory that provides the executable after, if any.
+    pub after: Option<String>,
+}
+
+/// Lists executables whose first providing directory would change when
+/// `before` is replaced by `after`, sorted by name.
+pub fn detect_resolution_changes(before: &[String], after: &[String]) -> Vec<ResolutionChange> {
+    let winners_before = resolution_winners(before);
+    let winners_after = resolution_winners(after);
+    let mut executables: Vec<&String> = winners_before.keys().chain(winners_after.keys()).collect();
+    executables.sort();
+    executables.dedup();
+    executables
+        .into_iter()
+        .filter_map(|key| {
+            let old = winners_before.get(key);
+            let new = winners_after.get(key);
+            let same = match (old, new) {
+                (Some((_, a)), Some((_, b))) => {
+                    normalize_for_comparison(a) == normalize_for_comparison(b)
+                }
+                (None, None) => true,
+                _ => false,
+            };
+            if same {
+                return None;
+            }
+            Some(ResolutionChange {
+                executable: old.or(new).map(|(name, _)| name.clone())?,
+                before: old.map(|(_, dir)| dir.clone()),
+                after: new.map(|(_, dir)| dir.clone()),
+            })
+        })
+        .collect()
+}
+
+/// Maps each lower-cased executable name to its file name and the first
+/// PATH entry that provides it.
+fn resolution_winners(paths: &[String]) -> HashMap<String, (String, String)> {
+    let mut winners = HashMap::new();
+    for path in paths {
+        let directory = expand_env_vars(path.trim().trim_matches('"'));
+        let Ok(entries) = std::fs::read_dir(&directory) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let is_executable = Path::new(&file_name)
+                .extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase())
+                .is_some_and(|ext| EXECUTABLE_EXTENSIONS.contains(&ext.as_str()));
+            if is_executable && entry.path().is_file() {
+                winners
+                    .entry(file_name.to_lowercase())
+                    .or_insert_with(|| (file_name, path.clone()));
+            }
+        }
+    }
+    winners
+}
+
+fn read_runtime_version(executable: &Path) -> Option<String> {
+    let output = Command::new(executable).arg("--version").output().ok()?;
+    let text = if output.stdout.is_empty() {
+        output.stderr
+    } else {
+        output.stdout
+    };
+    String::from_utf8_lossy(&text)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Architecture {
+    X64,
+    X86,
+}
+
+impl std::fmt::Display for Architecture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Architecture::X64 => write!(f, "x64"),
+            Architecture::X86 => write!(f, "x86"),
+        }
+    }
+}
+
+/// The same tool directory present under both Program Files roots.
+#[derive(Debug, Clone)]
+pub struct ArchitectureConflict {
+    /// Path below the Program Files root, e.g. `\tool\bin`.
+    pub suffix: String,
+    pub x64_path: String,
+    pub x86_path: String,
+    /// Architecture whose entry comes first in PATH.
+    pub winner: Architecture,
+}
+
+/// Pairs entries that look like x86/x64 variants of the same tool
+/// (same suffix under `Program Files` and `Program Files (x86)`).
+pub fn detect_architecture_conflicts(paths: &[String]) -> Vec<ArchitectureConflict> {
+    let mut x64: Vec<(usize, String)> = Vec::new();
+    let mut x86: Vec<(usize, String)> = Vec::new();
+    for (index, path) in paths.iter().enumerate() {
+        let normalized = normalize_for_comparison(&expand_env_vars(path.trim().trim_matches('"')));
+        if let Some(suffix) = normalized.strip_prefix(PROGRAM_FILES_X86) {
+            x86.push((index, suffix.to_string()));
+        } else if let Some(suffix) = normalized.strip_prefix(PROGRAM_FILES) {
+            if suffix.starts_with('\\') {
+                x64.push((index, suffix.to_string()));
+            }
+        }
+    }
+    let mut conflicts = Vec::new();
+    for (x64_index, suffix) in &x64 {
+        if suffix.is_empty()
+            || conflicts
+                .iter()
+                .any(|c: &ArchitectureConflict| c.suffix == *suffix)
+        {
+            continue;
+        }
+        if let Some((x86_index, _)) = x86.iter().find(|(_, s)| s == suffix) {
+            conflicts.push(ArchitectureConflict {
+                suffix: suffix.clone(),
+                x64_path: paths[*x64_index].clone(),
+                x86_path: paths[*x86_index].clone(),
+                winner: if x64_index < x86_index {
+                    Architecture::X64
+                } else {
+                    Architecture::X86
+                },
+            });
+        }
+    }
+    conflicts
+}
@@ -1,20 +1,86 @@
 use crate::constants::{
-    BACKUP_DIR_NAME, BACKUP_FILE_EXTENSION, BACKUP_FILE_PREFIX, BACKUP_TIMESTAMP_FORMAT,
-    MAX_BACKUPS,
+    BACKUP_DIR_NAME, BACKUP_FILE_EXTENSION, BACKUP_FILE_PREFIX, BACKUP_FORMAT_VERSION,
+    BACKUP_TIMESTAMP_FORMAT, LATEST_BACKUP_ALIAS, MAX_BACKUPS, MAX_BACKUPS_ENV_VAR,
+    QUARANTINE_DIR_NAME,
 };
 use crate::registry::RegistryHelper;
 use crate::security::acl;
+use crate::utils::normalize_for_comparison;
 use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PathBackup {
+    /// Layout version; 0 when the field is missing (pre-versioning backups).
+    #[serde(default)]
+    pub version: u32,
     pub timestamp: String,
     pub user_path: String,
     pub system_path: Option<String>,
+    /// SHA-256 of the stored PATH values; absent in older backups.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+}
+
+impl PathBackup {
+    /// Creates a backup record with its checksum filled in.
+    pub fn new(timestamp: String, user_path: String, system_path: Option<String>) -> Self {
+        let checksum = Some(Self::compute_checksum(&user_path, system_path.as_deref()));
+        Self {
+            version: BACKUP_FORMAT_VERSION,
+            timestamp,
+            user_path,
+            system_path,
+            checksum,
+        }
+    }
+    /// Hex SHA-256 of `user_path` followed by `system_path`. A NUL byte
+    /// separates the two so moving text between them changes the hash.
+    pub fn compute_checksum(user_path: &str, system_path: Option<&str>) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(user_path.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(system_path.unwrap_or_default().as_bytes());
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+    /// Checks the stored checksum, if any, against the PATH values.
+    pub fn verify_checksum(&self) -> Result<()> {
+        let Some(expected) = &self.checksum else {
+            return Ok(());
+        };
+        let actual = Self::compute_checksum(&self.user_path, self.system_path.as_deref());
+        if !actual.eq_ignore_ascii_case(expected) {
+            bail!("Backup file is corrupted: checksum does not match its PATH values");
+        }
+        Ok(())
+    }
+    /// Upgrades a backup read from disk to the current layout. Version 0
+    /// has the same fields minus the checksum; versions newer than
+    /// [`BACKUP_FORMAT_VERSION`] are rejected rather than misread.
+    pub fn migrate(mut self) -> Result<Self> {
+        match self.version {
+            0 => {
+                debug!("Migrating legacy backup from {}", self.timestamp);
+                self.version = BACKUP_FORMAT_VERSION;
+                Ok(self)
+            }
+            BACKUP_FORMAT_VERSION => Ok(self),
+            newer => bail!(
+                "Backup format version {} is newer than this spath supports ({}); upgrade spath to use it",
+                newer,
+                BACKUP_FORMAT_VERSION
+            ),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -26,16 +92,229 @@ pub struct BackupResult {
 #[derive(Debug)]
 pub struct RestoreResult {
     pub restored_from: PathBuf,
+    /// Whether the backup's USER PATH was written back.
+    pub user_path_restored: bool,
+    /// Whether the backup's SYSTEM PATH was written back.
+    pub system_path_restored: bool,
+    /// Why writing SYSTEM PATH failed (usually missing admin rights).
+    pub system_path_error: Option<String>,
+}
+
+/// What happened to SYSTEM PATH during a restore.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SystemRestoreStatus {
+    Restored,
+    /// The backup has no SYSTEM PATH, or the restore was user-only.
+    Skipped,
+    /// Writing failed, usually for lack of admin rights.
+    Failed(String),
 }
 
 impl RestoreResult {
     pub fn path(&self) -> &PathBuf {
         &self.restored_from
     }
+    pub fn system_status(&self) -> SystemRestoreStatus {
+        match (&self.system_path_error, self.system_path_restored) {
+            (Some(error), _) => SystemRestoreStatus::Failed(error.clone()),
+            (None, true) => SystemRestoreStatus::Restored,
+            (None, false) => SystemRestoreStatus::Skipped,
+        }
+    }
+}
+
+/// What a restore would write, compared with the current PATH of the
+/// restored scope.
+#[derive(Debug)]
+pub struct RestorePreview {
+    pub backup_file: PathBuf,
+    /// USER PATH value stored in the backup.
+    pub restored_path: String,
+    /// Entries in the backup that are missing from the current PATH.
+    pub added: Vec<String>,
+    /// Entries in the current PATH that the backup does not contain.
+    pub removed: Vec<String>,
+}
+
+/// How the current USER PATH differs from a backup.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PathDiff {
+    /// Entries in the current PATH that the backup does not contain.
+    pub added: Vec<String>,
+    /// Entries in the backup that are gone from the current PATH.
+    pub removed: Vec<String>,
+    /// Entries present in both that are written differently, e.g. quoted
+    /// since the backup, as `(backup, current)` pairs.
+    pub modified: Vec<(String, String)>,
+    /// Entries present in both whose position relative to the others changed.
+    pub moved: Vec<MovedEntry>,
+}
+
+/// An entry that sits in a different place than in the backup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MovedEntry {
+    /// Entry as written in the current PATH.
+    pub path: String,
+    /// Zero-based index in the backup's PATH.
+    pub from: usize,
+    /// Zero-based index in the current PATH.
+    pub to: usize,
+}
+
+impl PathDiff {
+    /// Compares two raw PATH values entry by entry; entries match when
+    /// they normalize to the same directory.
+    pub fn between(before: &str, after: &str) -> Self {
+        let before = RegistryHelper::parse_path_string(before);
+        let after = RegistryHelper::parse_path_string(after);
+        let mut before_by_key: HashMap<String, &String> = HashMap::new();
+        for path in &before {
+            before_by_key
+                .entry(normalize_for_comparison(path))
+                .or_insert(path);
+        }
+        let mut after_keys = HashSet::new();
+        let mut diff = Self::default();
+        for path in &after {
+            let key = normalize_for_comparison(path);
+            if !after_keys.insert(key.clone()) {
+                continue;
+            }
+            match before_by_key.get(&key) {
+                None => diff.added.push(path.clone()),
+                Some(old) if *old != path => diff.modified.push(((*old).clone(), path.clone())),
+                Some(_) => {}
+            }
+        }
+        let mut removed_keys = HashSet::new();
+        for path in &before {
+            let key = normalize_for_comparison(path);
+            if !after_keys.contains(&key) && removed_keys.insert(key) {
+                diff.removed.push(path.clone());
+            }
+        }
+        diff.moved = Self::moved_entries(&before, &after);
+        diff
+    }
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && self.moved.is_empty()
+    }
+    /// Entries in both PATHs outside the longest common subsequence of
+    /// their order, i.e. the fewest entries whose moves explain the change.
+    /// Additions and removals alone never make an entry count as moved.
+    fn moved_entries(before: &[String], after: &[String]) -> Vec<MovedEntry> {
+        let before_first = Self::first_positions(before);
+        let after_first = Self::first_positions(after);
+        let before_pos: HashMap<&str, usize> = before_first
+            .iter()
+            .map(|(key, index)| (key.as_str(), *index))
+            .collect();
+        let after_pos: HashMap<&str, usize> = after_first
+            .iter()
+            .map(|(key, index)| (key.as_str(), *index))
+            .collect();
+        let old: Vec<&str> = before_first
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .filter(|key| after_pos.contains_key(key))
+            .collect();
+        let new: Vec<&str> = after_first
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .filter(|key| before_pos.contains_key(key))
+            .collect();
+        let (n, m) = (old.len(), new.len());
+        let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old[i] == new[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        let mut stable = HashSet::new();
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if old[i] == new[j] {
+                stable.insert(old[i]);
+                i += 1;
+                j += 1;
+            } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        new.iter()
+            .filter(|key| !stable.contains(*key))
+            .map(|key| MovedEntry {
+                path: after[after_pos[key]].clone(),
+                from: before_pos[key],
+                to: after_pos[key],
+            })
+            .collect()
+    }
+    /// Normalized key and index of the first occurrence of each entry.
+    fn first_positions(paths: &[String]) -> Vec<(String, usize)> {
+        let mut seen = HashSet::new();
+        paths
+            .iter()
+            .enumerate()
+            .filter_map(|(index, path)| {
+                let key = normalize_for_comparison(path);
+                seen.insert(key.clone()).then_some((key, index))
+            })
+            .collect()
+    }
+}
+
+/// Classification of a file found in the backup directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackupFileStatus {
+    /// Passes path validation and parses as a backup.
+    Valid,
+    /// Fails `validate_path` (wrong name, extension or location).
+    InvalidName(String),
+    /// Has a valid name but is not a usable backup (unparseable, tampered
+    /// or written by a newer spath).
+    Corrupt(String),
+}
+
+#[derive(Debug)]
+pub struct BackupFileReport {
+    pub path: PathBuf,
+    pub status: BackupFileStatus,
+    pub quarantined_to: Option<PathBuf>,
+    pub quarantine_error: Option<String>,
+    pub acl_error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct RepairReport {
+    pub files: Vec<BackupFileReport>,
+}
+
+impl RepairReport {
+    pub fn valid_count(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|f| f.status == BackupFileStatus::Valid)
+            .count()
+    }
+    pub fn broken_count(&self) -> usize {
+        self.files.len() - self.valid_count()
+    }
 }
 
 pub struct BackupManager {
     backup_dir: PathBuf,
+    include_system: bool,
+    max_backups: usize,
 }
 
 impl BackupManager {
@@ -45,12 +324,71 @@ impl BackupManager {
         let backup_dir = PathBuf::from(local_app_data)
             .join("spath")
             .join(BACKUP_DIR_NAME);
-        fs::create_dir_all(&backup_dir).context("Failed to create backup directory")?;
-        Ok(Self { backup_dir })
+        Self::with_backup_dir(backup_dir)
+    }
+    /// Creates a manager operating on an explicit backup directory.
+    ///
+    /// The directory is created on the first backup, so read-only commands
+    /// work even when it cannot be written.
+    pub fn with_backup_dir(backup_dir: impl Into<PathBuf>) -> Result<Self> {
+        Ok(Self {
+            backup_dir: backup_dir.into(),
+            include_system: true,
+            max_backups: Self::default_max_backups(),
+        })
+    }
+    /// Backup limit from `SPATH_MAX_BACKUPS`, or [`MAX_BACKUPS`] when the
+    /// variable is unset or not a positive number.
+    pub fn default_max_backups() -> usize {
+        let Ok(value) = std::env::var(MAX_BACKUPS_ENV_VAR) else {
+            return MAX_BACKUPS;
+        };
+        match value.trim().parse::<usize>() {
+            Ok(max_backups) if max_backups > 0 => max_backups,
+            _ => {
+                warn!("Ignoring invalid {}={:?}", MAX_BACKUPS_ENV_VAR, value);
+                MAX_BACKUPS
+            }
+        }
+    }
+    /// Number of backups kept after each new backup.
+    pub fn max_backups(&self) -> usize {
+        self.max_backups
+    }
+    /// Stops backups from reading SYSTEM PATH, for purely user-scoped
+    /// operations that should not touch (or warn about) the system scope.
+    pub fn without_system_scope(mut self) -> Self {
+        self.include_system = false;
+        self
+    }
+    /// Keeps at most `max_backups` backups (at least one) instead of
+    /// [`Self::default_max_backups`].
+    pub fn with_max_backups(mut self, max_backups: usize) -> Self {
+        self.max_backups = max_backups.max(1);
+        self
     }
     pub fn create(&self) -> Result<BackupResult> {
         info!("Creating PATH backup");
         let backup = self.build_backup()?;
+        self.save(backup)
+    }
+    /// Backs up explicit PATH values instead of reading them from the registry.
+    pub fn create_from(
+        &self,
+        user_path: &str,
+        system_path: Option<String>,
+    ) -> Result<BackupResult> {
+        info!("Creating PATH backup from provided values");
+        let backup = PathBackup::new(Self::timestamp(), user_path.to_string(), system_path);
+        self.save(backup)
+    }
+    fn save(&self, backup: PathBackup) -> Result<BackupResult> {
+        fs::create_dir_all(&self.backup_dir).with_context(|| {
+            format!(
+                "Failed to create backup directory {} (is LOCALAPPDATA writable?)",
+                self.backup_dir.display()
+            )
+        })?;
         let backup_file = self.build_backup_path(&backup.timestamp);
         debug!("Writing backup to: {}", backup_file.display());
         self.write_backup(&backup_file, &backup)?;
@@ -64,15 +402,18 @@ impl BackupManager {
     }
     fn build_backup(&self) -> Result<PathBackup> {
         let user_path = RegistryHelper::read_user_path_raw().context("Failed to read user PATH")?;
-        let system_path = RegistryHelper::read_system_path_raw().ok();
-        let timestamp = chrono::Local::now()
+        let system_path = if self.include_system {
+            RegistryHelper::read_system_path_raw().ok()
+        } else {
+            debug!("Skipping SYSTEM PATH read for user-only backup");
+            None
+        };
+        Ok(PathBackup::new(Self::timestamp(), user_path, system_path))
+    }
+    fn timestamp() -> String {
+        chrono::Local::now()
             .format(BACKUP_TIMESTAMP_FORMAT)
-            .to_string();
-        Ok(PathBackup {
-            timestamp,
-            user_path,
-            system_path,
-        })
+            .to_string()
     }
     fn build_backup_path(&self, timestamp: &str) -> PathBuf {
         self.backup_dir.join(format!(
@@ -92,18 +433,142 @@ impl BackupManager {
             );
         }
     }
-    pub fn restore(&self, backup_file: &Path) -> Result<RestoreResult> {
+    /// Writes the backup's USER PATH and, unless `user_only` is set, its
+    /// SYSTEM PATH when the backup captured one.
+    ///
+    /// A failed SYSTEM PATH write is recorded in the result instead of
+    /// failing the restore, since USER PATH has already been written.
+    pub fn restore(&self, backup_file: &Path, user_only: bool) -> Result<RestoreResult> {
         info!("Restoring PATH from: {}", backup_file.display());
-        self.validate_path(backup_file)?;
-        let json = fs::read_to_string(backup_file).context("Failed to read backup file")?;
-        let backup: PathBackup =
-            serde_json::from_str(&json).context("Failed to parse backup file")?;
+        let backup = self.load(backup_file)?;
         RegistryHelper::write_user_path(&backup.user_path).context("Failed to restore PATH")?;
+        let (system_path_restored, system_path_error) = match &backup.system_path {
+            Some(system_path) if !user_only => Self::write_system_path(system_path),
+            _ => (false, None),
+        };
         info!("PATH restored successfully");
         Ok(RestoreResult {
             restored_from: backup_file.to_path_buf(),
+            user_path_restored: true,
+            system_path_restored,
+            system_path_error,
         })
     }
+    /// Writes back only the SYSTEM PATH stored in `backup_file`, leaving
+    /// USER PATH untouched. A backup without SYSTEM PATH is skipped and a
+    /// failed write is recorded in the result, as in [`Self::restore`].
+    pub fn restore_system_path(&self, backup_file: &Path) -> Result<RestoreResult> {
+        info!("Restoring SYSTEM PATH from: {}", backup_file.display());
+        let backup = self.load(backup_file)?;
+        let (system_path_restored, system_path_error) = match &backup.system_path {
+            Some(system_path) => Self::write_system_path(system_path),
+            None => {
+                warn!("Backup has no SYSTEM PATH; nothing restored");
+                (false, None)
+            }
+        };
+        Ok(RestoreResult {
+            restored_from: backup_file.to_path_buf(),
+            user_path_restored: false,
+            system_path_restored,
+            system_path_error,
+        })
+    }
+    fn write_system_path(system_path: &str) -> (bool, Option<String>) {
+        match RegistryHelper::write_system_path(system_path) {
+            Ok(()) => (true, None),
+            Err(e) => {
+                let e = anyhow::Error::from(e);
+                warn!("Failed to restore SYSTEM PATH: {:#}", e);
+                (false, Some(format!("{:#}", e)))
+            }
+        }
+    }
+    /// Validates and parses a backup, and diffs it against `current_path`
+    /// without writing anything.
+    pub fn preview_restore(
+        &self,
+        backup_file: &Path,
+        current_path: &str,
+    ) -> Result<RestorePreview> {
+        let backup = self.load(backup_file)?;
+        Ok(Self::build_preview(
+            backup_file,
+            backup.user_path,
+            current_path,
+        ))
+    }
+    /// Like [`Self::preview_restore`] for the backup's SYSTEM PATH.
+    pub fn preview_system_restore(
+        &self,
+        backup_file: &Path,
+        current_path: &str,
+    ) -> Result<RestorePreview> {
+        let backup = self.load(backup_file)?;
+        let Some(system_path) = backup.system_path else {
+            bail!("Backup has no SYSTEM PATH to restore");
+        };
+        Ok(Self::build_preview(backup_file, system_path, current_path))
+    }
+    fn build_preview(
+        backup_file: &Path,
+        restored_path: String,
+        current_path: &str,
+    ) -> RestorePreview {
+        let restored = RegistryHelper::parse_path_string(&restored_path);
+        let current = RegistryHelper::parse_path_string(current_path);
+        let restored_keys: HashSet<String> = restored
+            .iter()
+            .map(|p| normalize_for_comparison(p))
+            .collect();
+        let current_keys: HashSet<String> = current
+            .iter()
+            .map(|p| normalize_for_comparison(p))
+            .collect();
+        let added = restored
+            .iter()
+            .filter(|p| !current_keys.contains(&normalize_for_comparison(p)))
+            .cloned()
+            .collect();
+        let removed = current
+            .iter()
+            .filter(|p| !restored_keys.contains(&normalize_for_comparison(p)))
+            .cloned()
+            .collect();
+        RestorePreview {
+            backup_file: backup_file.to_path_buf(),
+            restored_path,
+            added,
+            removed,
+        }
+    }
+    /// Diffs the current USER PATH against the one stored in `backup_file`.
+    pub fn diff(&self, backup_file: &Path) -> Result<PathDiff> {
+        let current_path = RegistryHelper::read_user_path_raw()
+            .context("Failed to read user PATH from registry")?;
+        self.diff_against(backup_file, &current_path)
+    }
+    /// Diffs `current_path` against the USER PATH stored in `backup_file`.
+    pub fn diff_against(&self, backup_file: &Path, current_path: &str) -> Result<PathDiff> {
+        let backup = self.load(backup_file)?;
+        Ok(PathDiff::between(&backup.user_path, current_path))
+    }
+    /// Reads, parses and checksum-verifies a backup, migrating older
+    /// layouts to the current one.
+    pub fn load(&self, backup_file: &Path) -> Result<PathBackup> {
+        self.validate_path(backup_file)?;
+        let json = fs::read_to_string(backup_file).context("Failed to read backup file")?;
+        Self::parse_backup(&json)
+    }
+    /// Checks the layout version before the checksum, so a backup from a
+    /// newer spath is reported as such instead of as corrupted.
+    fn parse_backup(json: &str) -> Result<PathBackup> {
+        let backup: PathBackup =
+            serde_json::from_str(json).context("Failed to parse backup file")?;
+        let backup = backup.migrate()?;
+        backup.verify_checksum()?;
+        Ok(backup)
+    }
     pub fn list(&self) -> Result<Vec<PathBuf>> {
         let mut backups = Vec::new();
         if !self.backup_dir.exists() {
@@ -119,6 +584,29 @@ impl BackupManager {
         backups.reverse();
         Ok(backups)
     }
+    /// Maps `latest` or an index into [`Self::list`] (0 is the newest) to
+    /// a backup file. Anything else is taken as a file path as-is.
+    pub fn resolve(&self, backup: &str) -> Result<PathBuf> {
+        let backup = backup.trim();
+        let index = if backup.eq_ignore_ascii_case(LATEST_BACKUP_ALIAS) {
+            0
+        } else if let Ok(index) = backup.parse::<usize>() {
+            index
+        } else {
+            return Ok(PathBuf::from(backup));
+        };
+        let backups = self.list()?;
+        if backups.is_empty() {
+            bail!("No backups found in {}", self.backup_dir.display());
+        }
+        let count = backups.len();
+        backups.into_iter().nth(index).with_context(|| {
+            format!(
+                "Backup index {} is out of range: {} backup(s) available, 0 is the newest (see list-backups)",
+                index, count
+            )
+        })
+    }
     fn is_valid_backup_file(&self, path: &Path) -> bool {
         let has_json_ext = path.extension().and_then(|s| s.to_str()) == Some(BACKUP_FILE_EXTENSION);
         let has_prefix = path
@@ -129,19 +617,99 @@ impl BackupManager {
         has_json_ext && has_prefix
     }
     fn cleanup_old(&self) -> Result<Vec<PathBuf>> {
-        let mut backups = self.list()?;
+        self.cleanup_to(self.max_backups)
+    }
+    /// Deletes all but the newest `keep` backups (at least one is kept). A
+    /// file that cannot be deleted is logged and skipped so the rest are
+    /// still cleaned up.
+    pub fn cleanup_to(&self, keep: usize) -> Result<Vec<PathBuf>> {
+        let backups = self.list()?;
         let mut cleaned = Vec::new();
-        while backups.len() > MAX_BACKUPS {
-            if let Some(oldest) = backups.pop() {
-                debug!("Removing old backup: {}", oldest.display());
-                fs::remove_file(&oldest)
-                    .with_context(|| format!("Failed to remove: {}", oldest.display()))?;
-                info!("Removed old backup: {}", oldest.display());
-                cleaned.push(oldest);
+        for oldest in backups.into_iter().skip(keep.max(1)) {
+            debug!("Removing old backup: {}", oldest.display());
+            match fs::remove_file(&oldest) {
+                Ok(()) => {
+                    info!("Removed old backup: {}", oldest.display());
+                    cleaned.push(oldest);
+                }
+                Err(e) => warn!("Failed to remove old backup {}: {}", oldest.display(), e),
             }
         }
         Ok(cleaned)
     }
+    /// Scans the backup directory, classifying every file it contains.
+    ///
+    /// Valid backups get the user-only ACL re-applied. Invalid or corrupt
+    /// files are moved to the quarantine directory when `quarantine` is set;
+    /// a file that cannot be moved is reported and the rest are still
+    /// processed.
+    pub fn repair(&self, quarantine: bool) -> Result<RepairReport> {
+        info!("Repairing backups in: {}", self.backup_dir.display());
+        let mut report = RepairReport::default();
+        if !self.backup_dir.exists() {
+            return Ok(report);
+        }
+        let mut files = Vec::new();
+        for entry in fs::read_dir(&self.backup_dir)? {
+            let path = entry?.path();
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+        files.sort();
+        for path in files {
+            let status = self.classify_backup_file(&path);
+            let mut file_report = BackupFileReport {
+                path,
+                status,
+                quarantined_to: None,
+                quarantine_error: None,
+                acl_error: None,
+            };
+            if file_report.status == BackupFileStatus::Valid {
+                if let Err(e) = acl::set_user_only_acl(&file_report.path) {
+                    warn!(
+                        "Failed to re-apply ACL on {}: {}",
+                        file_report.path.display(),
+                        e
+                    );
+                    file_report.acl_error = Some(e.to_string());
+                }
+            } else if quarantine {
+                match self.quarantine_file(&file_report.path) {
+                    Ok(target) => file_report.quarantined_to = Some(target),
+                    Err(e) => {
+                        warn!("{:#}", e);
+                        file_report.quarantine_error = Some(format!("{:#}", e));
+                    }
+                }
+            }
+            report.files.push(file_report);
+        }
+        Ok(report)
+    }
+    fn classify_backup_file(&self, path: &Path) -> BackupFileStatus {
+        if let Err(e) = self.validate_path(path) {
+            return BackupFileStatus::InvalidName(e.to_string());
+        }
+        match fs::read_to_string(path) {
+            Ok(json) => match Self::parse_backup(&json) {
+                Ok(_) => BackupFileStatus::Valid,
+                Err(e) => BackupFileStatus::Corrupt(format!("{:#}", e)),
+            },
+            Err(e) => BackupFileStatus::Corrupt(e.to_string()),
+        }
+    }
+    fn quarantine_file(&self, path: &Path) -> Result<PathBuf> {
+        let quarantine_dir = self.backup_dir.join(QUARANTINE_DIR_NAME);
+        fs::create_dir_all(&quarantine_dir).context("Failed to create quarantine directory")?;
+        let file_name = path.file_name().context("Backup file has no file name")?;
+        let target = quarantine_dir.join(file_name);
+        fs::rename(path, &target)
+            .with_context(|| format!("Failed to quarantine: {}", path.display()))?;
+        info!("Quarantined {} -> {}", path.display(), target.display());
+        Ok(target)
+    }
     fn validate_path(&self, backup_file: &Path) -> Result<()> {
         let canonical_dir = self
             .backup_dir
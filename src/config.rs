@@ -0,0 +1,100 @@
+//! User preferences loaded from `%APPDATA%\spath\config.toml`.
+//!
+//! Values only supply defaults: a flag given on the command line always
+//! wins over the config file.
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming a config file to use instead of the default.
+pub const CONFIG_ENV_VAR: &str = "SPATH_CONFIG";
+
+/// Commented config written by `spath config init`.
+pub const DEFAULT_CONFIG: &str = r#"# spath configuration. Flags given on the command line override these values.
+
+# Target SYSTEM PATH by default in scan, clean and verify (like --system).
+# system = false
+
+# Preview changes instead of writing them in commands with --dry-run.
+# dry_run = false
+
+# Number of backups `spath backup` keeps (like --max-backups).
+# max_backups = 10
+
+# Drive letters scan and fix skip (like --exclude-drive).
+# exclude_drives = ["E", "Z"]
+
+# Entries scan, fix and clean skip, as case-insensitive wildcards (* and ?).
+# exclude_patterns = ["C:\\Tools\\legacy*", "*\\node_modules\\.bin"]
+
+# Rules `spath lint` skips (like --disable-rule).
+# disabled_rules = ["duplicate", "too-long"]
+"#;
+
+/// Preferences from the config file; [`DEFAULT_CONFIG`] documents each field.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpathConfig {
+    pub system: bool,
+    pub dry_run: bool,
+    pub max_backups: Option<usize>,
+    pub exclude_drives: Vec<char>,
+    pub exclude_patterns: Vec<String>,
+    pub disabled_rules: Vec<String>,
+}
+
+impl SpathConfig {
+    /// Config file location: `SPATH_CONFIG` if set, otherwise
+    /// `%APPDATA%\spath\config.toml`.
+    pub fn default_path() -> Result<PathBuf> {
+        if let Some(path) = std::env::var_os(CONFIG_ENV_VAR).filter(|p| !p.is_empty()) {
+            return Ok(PathBuf::from(path));
+        }
+        let appdata = std::env::var("APPDATA").context("APPDATA environment variable not set")?;
+        Ok(PathBuf::from(appdata).join("spath").join("config.toml"))
+    }
+    /// Loads the config from [`SpathConfig::default_path`]. A missing
+    /// default file means no preferences; a missing `SPATH_CONFIG` file is
+    /// an error.
+    pub fn load() -> Result<Self> {
+        let path = Self::default_path()?;
+        if !path.exists() && std::env::var_os(CONFIG_ENV_VAR).is_none() {
+            return Ok(Self::default());
+        }
+        Self::load_from(&path)
+    }
+    /// Reads and parses a config file.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("Invalid config {}", path.display()))
+    }
+    /// Parses config text; drive letters are upper-cased.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut config: Self = toml::from_str(text)?;
+        for drive in &mut config.exclude_drives {
+            if !drive.is_ascii_alphabetic() {
+                bail!("invalid drive '{}' in exclude_drives", drive);
+            }
+            *drive = drive.to_ascii_uppercase();
+        }
+        Ok(config)
+    }
+    /// Writes [`DEFAULT_CONFIG`] to `path`, refusing to replace an existing
+    /// file unless `overwrite` is set.
+    pub fn write_default(path: &Path, overwrite: bool) -> Result<()> {
+        if path.exists() && !overwrite {
+            bail!(
+                "{} already exists (use --force to overwrite)",
+                path.display()
+            );
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(path, DEFAULT_CONFIG)
+            .with_context(|| format!("Failed to write config {}", path.display()))
+    }
+}
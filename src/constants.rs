@@ -12,15 +12,120 @@ pub const PROGRAM_FILES_X86: &str = "c:\\program files (x86)";
 /// ProgramData directory (shared application data)
 pub const PROGRAM_DATA: &str = "c:\\programdata";
 
+/// Trailing components of the App Execution Alias directory
+/// (`%LOCALAPPDATA%\Microsoft\WindowsApps`)
+pub const WINDOWS_APPS_SUFFIX: &str = "\\microsoft\\windowsapps";
+
+/// Characters Windows does not allow in file or directory names. Control
+/// characters are rejected as well; a surrounding pair of quotes is PATH
+/// syntax, not part of the name.
+pub const RESERVED_PATH_CHARS: &[char] = &['<', '>', '"', '|', '?', '*'];
+
+/// Prefix of extended-length paths (`\\?\C:\...`), whose `?` is legal
+pub const EXTENDED_PATH_PREFIX: &str = "\\\\?\\";
+
+/// System entries that must never be removed from PATH or lose precedence
+pub const PROTECTED_PATHS: &[&str] = &[
+    "c:\\windows\\system32",
+    "c:\\windows\\syswow64",
+    "c:\\windows",
+    "c:\\windows\\system32\\windowspowershell\\v1.0",
+];
+
 /// Common user-specific path patterns
-pub const USER_PATHS: &[&str] = &[".cargo", ".dotnet", ".npm", ".bun", ".local", "\\appdata\\"];
+pub const USER_PATHS: &[&str] = &[
+    ".cargo",
+    ".dotnet",
+    ".npm",
+    ".bun",
+    ".local",
+    "\\appdata\\",
+    ".pyenv",
+    ".rbenv",
+    ".nvm",
+    "%userprofile%\\go\\bin",
+];
+
+/// Per-user tool directories and the names `analyze` shows for them
+pub const USER_TOOL_DIRS: &[(&str, &str)] = &[
+    (".pyenv\\pyenv-win\\shims", "pyenv shims"),
+    (".pyenv\\shims", "pyenv shims"),
+    (".rbenv\\shims", "rbenv shims"),
+    (".nvm", "nvm"),
+    ("%userprofile%\\go\\bin", "Go workspace"),
+    (".cargo\\bin", "Cargo"),
+    (".dotnet", ".NET tools"),
+    (".bun", "Bun"),
+];
+
+/// Language runtime executables checked for shadowing between PATH entries
+pub const LANGUAGE_RUNTIMES: &[&str] = &[
+    "python.exe",
+    "python3.exe",
+    "node.exe",
+    "ruby.exe",
+    "perl.exe",
+    "php.exe",
+    "go.exe",
+    "java.exe",
+    "deno.exe",
+    "bun.exe",
+];
+
+/// Executable extensions Windows resolves from PATH (default `PATHEXT`)
+pub const EXECUTABLE_EXTENSIONS: &[&str] = &["com", "exe", "bat", "cmd"];
+
+/// Placeholder that replaces the user name in redacted output
+pub const REDACTED_USER: &str = "<USER>";
+
+/// Directory names kept readable when redacted output hashes custom directories
+pub const WELL_KNOWN_DIRS: &[&str] = &[
+    "users",
+    "windows",
+    "system32",
+    "syswow64",
+    "program files",
+    "program files (x86)",
+    "programdata",
+    "appdata",
+    "local",
+    "roaming",
+    "microsoft",
+    "windowsapps",
+    "bin",
+    "cmd",
+    "scripts",
+];
 
 /// Backup directory name
 pub const BACKUP_DIR_NAME: &str = "backups";
 
+/// Directory (inside the backup directory) for quarantined backup files
+pub const QUARANTINE_DIR_NAME: &str = "quarantine";
+
 /// Maximum number of backup files to keep
 pub const MAX_BACKUPS: usize = 10;
 
+/// Environment variable overriding [`MAX_BACKUPS`]
+pub const MAX_BACKUPS_ENV_VAR: &str = "SPATH_MAX_BACKUPS";
+
+/// Environment variable that disables colored output when set and non-empty
+/// (see <https://no-color.org/>)
+pub const NO_COLOR_ENV_VAR: &str = "NO_COLOR";
+
+/// Default time an existence check may take before the entry is reported
+/// as unreachable, in milliseconds
+pub const DEFAULT_EXISTENCE_TIMEOUT_MS: u64 = 2000;
+
+/// Entry count above which `scan` checks entries in parallel by default
+pub const PARALLEL_SCAN_THRESHOLD: usize = 16;
+
+/// Terminal width assumed when neither `COLUMNS` nor the console reports one
+pub const DEFAULT_TERMINAL_WIDTH: usize = 100;
+
+/// Separator between PATH entries
+pub const PATH_SEPARATOR: char = ';';
+
 /// Maximum PATH environment variable length (Windows limitation)
 pub const MAX_PATH_LENGTH: usize = 2047;
 
@@ -38,8 +143,30 @@ pub const BACKUP_FILE_PREFIX: &str = "path_backup_";
 /// Backup file extension
 pub const BACKUP_FILE_EXTENSION: &str = "json";
 
+/// `restore` argument selecting the newest backup
+pub const LATEST_BACKUP_ALIAS: &str = "latest";
+
 /// Backup timestamp format
 pub const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
 
+/// Layout version written into new backups. Backups without a version
+/// field are version 0, the layout from before versioning.
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
 /// Maximum single path length (Windows MAX_PATH limitation)
 pub const MAX_SINGLE_PATH_LENGTH: usize = 260;
+
+/// Maximum length of a single path component (NTFS limit)
+pub const MAX_PATH_COMPONENT_LENGTH: usize = 255;
+
+/// Scanner issue and entry warning for PATH entries that are files
+pub const FILE_ENTRY_MESSAGE: &str = "PATH entry is a file, not a directory";
+
+/// Maximum nesting depth when expanding `%VAR%` references inside values
+pub const MAX_EXPANSION_DEPTH: usize = 16;
+
+/// Process exit code of `scan` and `check` when the worst issue is a warning
+pub const EXIT_WORST_WARNING: i32 = 1;
+
+/// Process exit code of `scan` and `check` when critical issues were found
+pub const EXIT_WORST_CRITICAL: i32 = 2;
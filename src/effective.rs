@@ -0,0 +1,143 @@
+//! Effective PATH as a new login session would build it.
+use crate::constants::EXECUTABLE_EXTENSIONS;
+use crate::registry::RegistryHelper;
+use crate::utils::{expand_env_vars, normalize_for_comparison};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Differences between the running process PATH and the registry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EffectivePathDiff {
+    /// Entries the process still sees but a new session would not.
+    pub only_in_process: Vec<String>,
+    /// Entries a new session would see that the process does not.
+    pub only_in_registry: Vec<String>,
+}
+
+impl EffectivePathDiff {
+    pub fn is_empty(&self) -> bool {
+        self.only_in_process.is_empty() && self.only_in_registry.is_empty()
+    }
+}
+
+/// Builds the effective PATH from raw SYSTEM and USER values: SYSTEM entries
+/// first, then USER, with environment variables expanded.
+pub fn build_effective_path(system_path: &str, user_path: &str) -> Vec<String> {
+    RegistryHelper::parse_path_string(system_path)
+        .iter()
+        .chain(RegistryHelper::parse_path_string(user_path).iter())
+        .map(|entry| expand_env_vars(entry.trim()))
+        .collect()
+}
+
+/// Reads both scopes from the registry and builds the effective PATH.
+pub fn registry_effective_path() -> Result<Vec<String>> {
+    let system_path = RegistryHelper::read_system_path_raw().unwrap_or_default();
+    let user_path =
+        RegistryHelper::read_user_path_raw().context("Failed to read user PATH from registry")?;
+    Ok(build_effective_path(&system_path, &user_path))
+}
+
+/// Compares the process PATH with the registry-derived effective PATH,
+/// ignoring case, quotes and trailing dots.
+pub fn compare_with_process(process: &[String], registry: &[String]) -> EffectivePathDiff {
+    let process_keys: HashSet<String> = process
+        .iter()
+        .map(|p| normalize_for_comparison(p))
+        .collect();
+    let registry_keys: HashSet<String> = registry
+        .iter()
+        .map(|p| normalize_for_comparison(p))
+        .collect();
+    EffectivePathDiff {
+        only_in_process: process
+            .iter()
+            .filter(|p| !registry_keys.contains(&normalize_for_comparison(p)))
+            .cloned()
+            .collect(),
+        only_in_registry: registry
+            .iter()
+            .filter(|p| !process_keys.contains(&normalize_for_comparison(p)))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// A PATH directory that provides a requested executable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExecutableMatch {
+    pub directory: String,
+    pub file: String,
+    /// The first match, which Windows runs.
+    pub wins: bool,
+}
+
+/// Every copy of an executable on PATH, in resolution order.
+#[derive(Debug, Clone, Serialize)]
+pub struct Resolution {
+    pub name: String,
+    pub matches: Vec<ExecutableMatch>,
+}
+
+/// Executable extensions from `PATHEXT` (lower-cased, with the dot),
+/// falling back to the Windows defaults.
+pub fn path_extensions() -> Vec<String> {
+    let configured: Vec<String> = std::env::var("PATHEXT")
+        .unwrap_or_default()
+        .split(';')
+        .map(|ext| ext.trim().to_lowercase())
+        .filter(|ext| ext.starts_with('.') && ext.len() > 1)
+        .collect();
+    if configured.is_empty() {
+        default_path_extensions()
+    } else {
+        configured
+    }
+}
+
+/// [`EXECUTABLE_EXTENSIONS`] in the dotted form [`path_extensions`] returns.
+pub fn default_path_extensions() -> Vec<String> {
+    EXECUTABLE_EXTENSIONS
+        .iter()
+        .map(|ext| format!(".{}", ext))
+        .collect()
+}
+
+/// Walks `path` in order and lists every directory providing `name`.
+///
+/// A name whose extension is in `extensions` is looked up as-is; otherwise
+/// each extension is tried in order, as the shell does.
+pub fn resolve_executable(name: &str, path: &[String], extensions: &[String]) -> Resolution {
+    let has_extension = Path::new(name)
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy().to_lowercase()))
+        .is_some_and(|ext| extensions.contains(&ext));
+    let candidates: Vec<String> = if has_extension {
+        vec![name.to_string()]
+    } else {
+        extensions
+            .iter()
+            .map(|ext| format!("{}{}", name, ext))
+            .collect()
+    };
+    let mut matches = Vec::new();
+    for directory in path {
+        let directory = directory.trim().trim_matches('"');
+        for candidate in &candidates {
+            let file = Path::new(directory).join(candidate);
+            if file.is_file() {
+                matches.push(ExecutableMatch {
+                    directory: directory.to_string(),
+                    file: file.display().to_string(),
+                    wins: matches.is_empty(),
+                });
+            }
+        }
+    }
+    Resolution {
+        name: name.to_string(),
+        matches,
+    }
+}
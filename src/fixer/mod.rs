@@ -1,10 +1,23 @@
 //! PATH fixer for security issues.
-use crate::backup::{BackupManager, BackupResult, RestoreResult};
-use crate::registry::RegistryHelper;
-use crate::utils::{expand_env_vars, quote_if_needed};
-use anyhow::{Context, Result};
-use std::collections::HashSet;
+use crate::analyzer::{detect_resolution_changes, ResolutionChange};
+use crate::backup::{
+    BackupManager, BackupResult, PathDiff, RepairReport, RestorePreview, RestoreResult,
+};
+use crate::constants::PATH_SEPARATOR;
+use crate::migrator::{ActionType, MigrationPlan};
+use crate::models::{IssueLevel, PathLocation};
+use crate::prompt::{InteractivePrompter, Prompter};
+use crate::registry::{RegistryHelper, RegistrySource, UserPathSource};
+use crate::scanner::{PathScanner, ScanResults};
+use crate::utils::{
+    canonical_case, check_path_within, expand_env_vars, is_app_execution_alias_dir,
+    is_on_excluded_drive, matches_exclude_pattern, normalize_for_comparison, protected_violations,
+    quote_if_needed, strip_trailing_dots_and_spaces,
+};
+use anyhow::{bail, Context, Result};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
 pub struct FixResults {
@@ -12,17 +25,296 @@ pub struct FixResults {
     pub dry_run: bool,
     pub changed: bool,
     pub backup_created: Option<BackupResult>,
+    /// Scope the fix was computed for.
+    pub location: PathLocation,
+    /// Why writing SYSTEM PATH failed (typically missing admin rights).
+    pub system_path_error: Option<String>,
+    /// PATH value after the fix (unchanged input when nothing was fixed).
+    pub new_path: String,
+    /// Re-scan of `new_path`, present when the fix changed anything.
+    pub verification: Option<ScanResults>,
+    /// Protected system entries the fix would remove or demote.
+    pub protected_violations: Vec<String>,
+    /// Executables that would resolve from a different USER PATH directory.
+    pub resolution_changes: Vec<ResolutionChange>,
+}
+
+/// Outcome of writing a caller-supplied PATH value.
+pub struct ApplyResult {
+    pub previous_path: String,
+    pub new_path: String,
+    pub backup: BackupResult,
+}
+
+/// Rejects a joined PATH value in which any entry has an odd number of
+/// quotes, which Windows would mis-parse.
+pub fn validate_quoting(value: &str) -> Result<()> {
+    if let Some(entry) = RegistryHelper::split_entries(value)
+        .into_iter()
+        .find(|entry| entry.matches('"').count() % 2 != 0)
+    {
+        bail!("PATH entry has unbalanced quotes: {}", entry);
+    }
+    Ok(())
+}
+
+/// Checks a complete PATH value before it is written.
+///
+/// Strips a trailing newline and separator; rejects multi-line or empty
+/// values, empty entries, unbalanced quotes, control characters and
+/// over-long values.
+pub fn validate_path_value(value: &str) -> Result<String> {
+    let value = value.trim_end_matches(['\r', '\n']);
+    if value.contains(['\r', '\n']) {
+        bail!("PATH value must be a single line");
+    }
+    let value = value.strip_suffix(PATH_SEPARATOR).unwrap_or(value);
+    if value.trim().is_empty() {
+        bail!("Refusing to write an empty PATH");
+    }
+    if RegistryHelper::split_entries(value)
+        .iter()
+        .any(|entry| entry.trim().is_empty())
+    {
+        bail!("PATH contains empty entries");
+    }
+    if value.chars().any(|c| c.is_control()) {
+        bail!("PATH contains control characters");
+    }
+    validate_quoting(value)?;
+    RegistryHelper::validate_path_length(value)?;
+    Ok(value.to_string())
+}
+
+/// How many of a scan's actionable issues each remediation would resolve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemediationProjection {
+    /// Critical and warning issues in the scan.
+    pub total: usize,
+    /// Issues `spath fix` would resolve; `None` for SYSTEM scans, which fix never touches.
+    pub fix_resolves: Option<usize>,
+    /// Issues `spath clean` would resolve; `None` without a clean plan.
+    pub clean_resolves: Option<usize>,
+}
+
+/// Simulates `fix` and the given `clean` plan against the scanned entries
+/// and counts the issues each would resolve.
+pub fn project_remediation(
+    results: &ScanResults,
+    clean_plan: Option<&MigrationPlan>,
+) -> Result<RemediationProjection> {
+    let path_var = RegistryHelper::join_paths(&results.paths);
+    let fix_resolves = if results.location == Some(PathLocation::System) {
+        None
+    } else {
+        let fixed = PathFixer::plan_fix(&path_var, &FixOptions::default())?;
+        let after = PathScanner::from_string(fixed.new_path).scan()?;
+        Some(count_resolved(results, &after))
+    };
+    let clean_resolves = match (clean_plan, results.location) {
+        (Some(plan), Some(location)) => {
+            let cleaned = simulate_clean(&results.paths, plan, location);
+            let after = PathScanner::from_string(RegistryHelper::join_paths(&cleaned)).scan()?;
+            Some(count_resolved(results, &after))
+        }
+        _ => None,
+    };
+    Ok(RemediationProjection {
+        total: actionable_issues(results).count(),
+        fix_resolves,
+        clean_resolves,
+    })
+}
+
+fn actionable_issues(results: &ScanResults) -> impl Iterator<Item = (&str, &str)> {
+    results
+        .issues
+        .iter()
+        .filter(|i| matches!(i.level, IssueLevel::Critical | IssueLevel::Warning))
+        .map(|i| (i.path.as_str(), i.message.as_str()))
+}
+
+/// Counts actionable issues in `before` that no longer appear in `after`.
+fn count_resolved(before: &ScanResults, after: &ScanResults) -> usize {
+    let mut remaining: HashMap<(&str, &str), usize> = HashMap::new();
+    for key in actionable_issues(after) {
+        *remaining.entry(key).or_default() += 1;
+    }
+    actionable_issues(before)
+        .filter(|key| match remaining.get_mut(key) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .count()
+}
+
+/// Applies the plan's actions for one scope, the way `execute_migration` does.
+fn simulate_clean(paths: &[String], plan: &MigrationPlan, location: PathLocation) -> Vec<String> {
+    let mut removals = HashSet::new();
+    let mut quoted = HashMap::new();
+    for action in plan.actions.iter().filter(|a| a.from_location == location) {
+        let key = normalize_for_comparison(&action.path);
+        match action.action_type {
+            ActionType::RemoveDuplicate | ActionType::MoveToUser => {
+                removals.insert(key);
+            }
+            ActionType::AddQuotes => {
+                quoted.insert(key, quote_if_needed(&action.path));
+            }
+            ActionType::ReorderAction { .. } => {}
+        }
+    }
+    paths
+        .iter()
+        .filter_map(|p| {
+            let key = normalize_for_comparison(p);
+            if removals.contains(&key) {
+                None
+            } else {
+                Some(quoted.get(&key).cloned().unwrap_or_else(|| p.clone()))
+            }
+        })
+        .collect()
+}
+
+/// Explicit choice of which duplicate occurrence to keep.
+///
+/// Overrides apply only to the normalized path they match; other duplicates
+/// keep their first occurrence.
+#[derive(Debug, Clone, Default)]
+pub struct KeepOverrides {
+    /// Exact entry strings; the first occurrence matching each is kept.
+    pub paths: Vec<String>,
+    /// Zero-based entry indices, as shown by `scan --group-duplicates`.
+    pub indices: Vec<usize>,
+}
+
+impl KeepOverrides {
+    /// Maps each normalized path to the index of the occurrence to keep.
+    fn resolve(&self, paths: &[String]) -> Result<HashMap<String, usize>> {
+        let mut kept = HashMap::new();
+        for (index, path) in paths.iter().enumerate() {
+            kept.entry(normalize_for_comparison(path.trim()))
+                .or_insert(index);
+        }
+        for keep_path in &self.paths {
+            let Some(index) = paths.iter().position(|p| p.trim() == keep_path.trim()) else {
+                bail!("--keep-path {} does not match any PATH entry", keep_path);
+            };
+            kept.insert(normalize_for_comparison(keep_path.trim()), index);
+        }
+        for &index in &self.indices {
+            let Some(path) = paths.get(index) else {
+                bail!(
+                    "--keep-index {} is out of range (PATH has {} entries)",
+                    index,
+                    paths.len()
+                );
+            };
+            kept.insert(normalize_for_comparison(path.trim()), index);
+        }
+        Ok(kept)
+    }
+}
+
+/// Entries `remove` would drop from one PATH scope.
+#[derive(Debug, Clone)]
+pub struct RemovalPlan {
+    pub location: PathLocation,
+    /// Removed entries as written in the PATH.
+    pub removed: Vec<String>,
+    pub new_path: String,
+    /// Protected system entries the removal would drop.
+    pub protected_violations: Vec<String>,
+}
+
+impl RemovalPlan {
+    /// Plans removing `target` from `current_path`. Entries match
+    /// case-insensitively and ignoring quotes; only the first match is
+    /// removed unless `all` is set.
+    pub fn new(
+        current_path: &str,
+        target: &str,
+        location: PathLocation,
+        all: bool,
+    ) -> Result<Self> {
+        let key = normalize_for_comparison(target);
+        let entries = RegistryHelper::parse_path_string(current_path);
+        let mut removed = Vec::new();
+        let mut kept = Vec::new();
+        for entry in &entries {
+            if normalize_for_comparison(entry) == key && (all || removed.is_empty()) {
+                removed.push(entry.clone());
+            } else {
+                kept.push(entry.clone());
+            }
+        }
+        if removed.is_empty() {
+            bail!("{} is not on the {} PATH", target, location);
+        }
+        let new_path = RegistryHelper::join_paths(&kept);
+        validate_quoting(&new_path)?;
+        Ok(Self {
+            location,
+            removed,
+            new_path,
+            protected_violations: protected_violations(&entries, &kept),
+        })
+    }
+}
+
+/// Options that tune how the fix treats individual entries.
+#[derive(Debug, Clone, Default)]
+pub struct FixOptions {
+    pub keep: KeepOverrides,
+    /// Drive letters whose entries are never removed as non-existent.
+    pub excluded_drives: Vec<char>,
+    /// Rewrite the surviving entry of case-variant duplicates to its
+    /// on-disk casing.
+    pub canonicalize_case: bool,
+    /// Wildcard patterns whose entries are left exactly as they are.
+    pub excluded_patterns: Vec<String>,
+    /// Longest an existence check may take; entries whose check times out
+    /// are kept rather than removed as non-existent.
+    pub existence_timeout: Option<Duration>,
+    /// Replaces the filesystem existence check (used to simulate slow
+    /// paths); `None` checks the disk.
+    pub existence_check: Option<fn(&Path) -> bool>,
 }
 
 pub struct PathFixer {
     backup_manager: BackupManager,
+    options: FixOptions,
+    prompter: Box<dyn Prompter>,
 }
 
 impl PathFixer {
     pub fn new() -> Result<Self> {
-        Ok(Self {
-            backup_manager: BackupManager::new()?,
-        })
+        Ok(Self::with_backup_manager(BackupManager::new()?))
+    }
+    /// Creates a fixer that uses the given backup manager.
+    pub fn with_backup_manager(backup_manager: BackupManager) -> Self {
+        Self {
+            backup_manager,
+            options: FixOptions::default(),
+            prompter: Box::new(InteractivePrompter),
+        }
+    }
+    pub fn with_options(mut self, options: FixOptions) -> Self {
+        self.options = options;
+        self
+    }
+    /// Replaces the stdin prompt used for delicate-mode confirmations.
+    pub fn with_prompter(mut self, prompter: Box<dyn Prompter>) -> Self {
+        self.prompter = prompter;
+        self
+    }
+    /// Asks the configured prompter to confirm `message`.
+    pub fn confirm(&self, message: &str) -> bool {
+        self.prompter.confirm(message)
     }
     pub fn create_backup(&self) -> Result<BackupResult> {
         self.backup_manager.create()
@@ -30,65 +322,219 @@ impl PathFixer {
     pub fn list_backups(&self) -> Result<Vec<PathBuf>> {
         self.backup_manager.list()
     }
-    pub fn restore_backup(&self, backup_file: &Path) -> Result<RestoreResult> {
-        self.backup_manager.restore(backup_file)
+    /// Resolves `latest`, a backup index or a file name to a backup file.
+    pub fn resolve_backup(&self, backup: &str) -> Result<PathBuf> {
+        self.backup_manager.resolve(backup)
     }
-    pub fn fix_user_path(&self, dry_run: bool) -> Result<FixResults> {
-        info!("Starting USER PATH fix (dry_run: {})", dry_run);
-        let current_path = RegistryHelper::read_user_path_raw()
+    pub fn restore_backup(&self, backup_file: &Path, user_only: bool) -> Result<RestoreResult> {
+        self.backup_manager.restore(backup_file, user_only)
+    }
+    /// Restores only the SYSTEM PATH of `backup_file`; needs admin rights.
+    pub fn restore_system_backup(&self, backup_file: &Path) -> Result<RestoreResult> {
+        if !RegistryHelper::can_write_system_path() {
+            bail!("Restoring SYSTEM PATH requires administrator rights");
+        }
+        self.backup_manager.restore_system_path(backup_file)
+    }
+    /// Shows what restoring `backup_file` would change in the USER PATH.
+    pub fn preview_restore(&self, backup_file: &Path) -> Result<RestorePreview> {
+        self.preview_restore_from(backup_file, &RegistrySource)
+    }
+    /// Like [`Self::preview_restore`], reading the current USER PATH from
+    /// `source`. Never writes to `source`.
+    pub fn preview_restore_from(
+        &self,
+        backup_file: &Path,
+        source: &dyn UserPathSource,
+    ) -> Result<RestorePreview> {
+        let current_path = source
+            .read_user_path()
             .context("Failed to read user PATH from registry")?;
-        let paths = RegistryHelper::parse_path_string(&current_path);
+        self.backup_manager
+            .preview_restore(backup_file, &current_path)
+    }
+    /// Shows what restoring `backup_file` would change in the SYSTEM PATH.
+    pub fn preview_system_restore(&self, backup_file: &Path) -> Result<RestorePreview> {
+        let current_path = RegistryHelper::read_system_path_raw()
+            .context("Failed to read system PATH from registry")?;
+        self.backup_manager
+            .preview_system_restore(backup_file, &current_path)
+    }
+    /// Shows how the current USER PATH differs from `backup_file`.
+    pub fn diff_backup(&self, backup_file: &Path) -> Result<PathDiff> {
+        self.backup_manager.diff(backup_file)
+    }
+    pub fn repair_backups(&self, quarantine: bool) -> Result<RepairReport> {
+        self.backup_manager.repair(quarantine)
+    }
+    /// Fixes the USER PATH.
+    ///
+    /// Refuses to write a PATH that removes or demotes a protected system
+    /// entry unless `force` is set.
+    pub fn fix_user_path(&self, dry_run: bool, force: bool) -> Result<FixResults> {
+        self.fix_path(PathLocation::User, dry_run, force)
+    }
+    /// Fixes the SYSTEM PATH the same way as [`PathFixer::fix_user_path`].
+    ///
+    /// The backup holds both USER and SYSTEM PATH unless the fixer was built
+    /// user-only.
+    ///
+    /// A failed write (e.g. without admin rights) is reported in
+    /// `system_path_error` after the backup was taken, not as an error.
+    pub fn fix_system_path(&self, dry_run: bool, force: bool) -> Result<FixResults> {
+        self.fix_path(PathLocation::System, dry_run, force)
+    }
+    fn fix_path(&self, location: PathLocation, dry_run: bool, force: bool) -> Result<FixResults> {
+        info!("Starting {} PATH fix (dry_run: {})", location, dry_run);
+        let current_path = match location {
+            PathLocation::System => RegistryHelper::read_system_path_raw()
+                .context("Failed to read system PATH from registry")?,
+            PathLocation::User => RegistryHelper::read_user_path_raw()
+                .context("Failed to read user PATH from registry")?,
+        };
+        let mut results = Self::plan_fix(&current_path, &self.options)?;
+        results.location = location;
+        results.dry_run = dry_run;
+        if !dry_run && !force && !results.protected_violations.is_empty() {
+            bail!(
+                "Fix would change protected system entries:\n  {}\nUse --force to apply anyway.",
+                results.protected_violations.join("\n  ")
+            );
+        }
+        if !dry_run && results.changed {
+            self.apply_fix(&mut results)?;
+        }
+        Ok(results)
+    }
+    /// Validates `value`, backs up the current USER PATH and writes `value`.
+    ///
+    /// Only USER PATH is backed up, since this never writes SYSTEM PATH.
+    pub fn apply_path_value(
+        &self,
+        value: &str,
+        source: &dyn UserPathSource,
+    ) -> Result<ApplyResult> {
+        let new_path = validate_path_value(value)?;
+        let previous_path = source
+            .read_user_path()
+            .context("Failed to read current user PATH")?;
+        let backup = self.backup_manager.create_from(&previous_path, None)?;
+        source
+            .write_user_path(&new_path)
+            .context("Failed to write new PATH")?;
+        info!("Applied PATH value ({} characters)", new_path.len());
+        Ok(ApplyResult {
+            previous_path,
+            new_path,
+            backup,
+        })
+    }
+    /// Computes the fix for a PATH value without touching the registry.
+    ///
+    /// The result is always a dry run; when anything changed, the fixed
+    /// PATH is re-scanned so callers can check that the fix is clean, and
+    /// executables that would resolve from another directory are listed.
+    /// Duplicates keep their first occurrence unless `options.keep` pins another.
+    pub fn plan_fix(current_path: &str, options: &FixOptions) -> Result<FixResults> {
+        let paths = RegistryHelper::parse_path_string(current_path);
         debug!("Found {} path entries to process", paths.len());
-        let (fixed_paths, changes) = self.process_paths(paths);
-        let new_path = fixed_paths.join(";");
+        let kept = options.keep.resolve(&paths)?;
+        let (fixed_paths, changes) = Self::process_paths(&paths, &kept, options);
+        let protected_violations = protected_violations(&paths, &fixed_paths);
+        let new_path = RegistryHelper::join_paths(&fixed_paths);
         let changed = new_path != current_path;
         info!(
             "PATH fix completed: {} changes, changed: {}",
             changes.len(),
             changed
         );
-        let backup_created = if !dry_run && changed {
-            Some(self.apply_fix(&new_path)?)
+        let (verification, resolution_changes) = if changed {
+            (
+                Some(PathScanner::from_string(new_path.as_str()).scan()?),
+                detect_resolution_changes(&paths, &fixed_paths),
+            )
         } else {
-            None
+            (None, Vec::new())
         };
         Ok(FixResults {
             changes,
-            dry_run,
+            dry_run: true,
             changed,
-            backup_created,
+            backup_created: None,
+            location: PathLocation::User,
+            system_path_error: None,
+            new_path,
+            verification,
+            protected_violations,
+            resolution_changes,
         })
     }
-    fn process_paths(&self, paths: Vec<String>) -> (Vec<String>, Vec<String>) {
+    fn process_paths(
+        paths: &[String],
+        kept: &HashMap<String, usize>,
+        options: &FixOptions,
+    ) -> (Vec<String>, Vec<String>) {
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        for path in paths {
+            *occurrences
+                .entry(normalize_for_comparison(path.trim()))
+                .or_default() += 1;
+        }
         let mut fixed_paths = Vec::new();
         let mut changes = Vec::new();
-        let mut seen = HashSet::new();
-        for path in paths {
-            self.process_single_path(&path, &mut fixed_paths, &mut changes, &mut seen);
+        for (index, path) in paths.iter().enumerate() {
+            if options
+                .excluded_patterns
+                .iter()
+                .any(|pattern| matches_exclude_pattern(path, pattern))
+            {
+                debug!("Leaving excluded entry unchanged: {}", path);
+                fixed_paths.push(path.trim().to_string());
+                continue;
+            }
+            let key = normalize_for_comparison(path.trim());
+            let is_kept = kept.get(&key) == Some(&index);
+            let canonicalize = options.canonicalize_case && occurrences[&key] > 1;
+            Self::process_single_path(
+                path,
+                is_kept,
+                canonicalize,
+                options,
+                &mut fixed_paths,
+                &mut changes,
+            );
         }
         (fixed_paths, changes)
     }
     fn process_single_path(
-        &self,
         path: &str,
+        is_kept: bool,
+        canonicalize: bool,
+        options: &FixOptions,
         fixed_paths: &mut Vec<String>,
         changes: &mut Vec<String>,
-        seen: &mut HashSet<String>,
     ) {
-        let trimmed = path.trim();
-        if seen.contains(trimmed) {
+        let mut trimmed = path.trim();
+        if !is_kept {
             warn!("Duplicate path found: {}", trimmed);
             changes.push(format!("Removed duplicate: {}", trimmed));
             return;
         }
-        seen.insert(trimmed.to_string());
-        if self.should_remove_path(trimmed) {
+        if Self::should_remove_path(trimmed, options) {
             warn!("Non-existent path found: {}", trimmed);
             changes.push(format!("Removed non-existent: {}", trimmed));
             return;
         }
-        if trimmed.contains(' ') && !trimmed.starts_with('"') {
-            let quoted = quote_if_needed(trimmed);
+        let canonical = canonicalize
+            .then(|| Self::canonical_entry(trimmed))
+            .flatten();
+        if let Some(canonical) = &canonical {
+            info!("Canonicalizing case: {} -> {}", trimmed, canonical);
+            changes.push(format!("Canonicalized case: {} -> {}", trimmed, canonical));
+            trimmed = canonical.as_str();
+        }
+        if expand_env_vars(trimmed).contains(' ') && !trimmed.starts_with('"') {
+            let quoted = format!("\"{}\"", trimmed);
             info!("Adding quotes to path: {}", trimmed);
             changes.push(format!("Added quotes: {} -> {}", trimmed, quoted));
             fixed_paths.push(quoted);
@@ -96,24 +542,90 @@ impl PathFixer {
             fixed_paths.push(trimmed.to_string());
         }
     }
-    fn should_remove_path(&self, trimmed: &str) -> bool {
-        let path_to_check = trimmed.trim_matches('"');
-        let exists = Path::new(path_to_check).exists();
+    /// On-disk casing of an entry, keeping its quotes; `None` when the
+    /// entry uses environment variables, cannot be resolved or is unchanged.
+    fn canonical_entry(trimmed: &str) -> Option<String> {
+        if trimmed.contains('%') {
+            return None;
+        }
+        let unquoted = trimmed.trim_matches('"');
+        let canonical = canonical_case(unquoted)?;
+        if canonical == unquoted {
+            return None;
+        }
+        Some(if trimmed.starts_with('"') {
+            format!("\"{}\"", canonical)
+        } else {
+            canonical
+        })
+    }
+    fn should_remove_path(trimmed: &str, options: &FixOptions) -> bool {
+        if is_app_execution_alias_dir(trimmed)
+            || is_on_excluded_drive(trimmed, &options.excluded_drives)
+        {
+            return false;
+        }
+        let check = options.existence_check.unwrap_or(Path::exists);
+        let timeout = options.existence_timeout;
+        let path_to_check = strip_trailing_dots_and_spaces(trimmed.trim_matches('"'));
+        let Some(exists) = check_path_within(&path_to_check, check, timeout) else {
+            warn!("Existence check timed out, keeping: {}", trimmed);
+            return false;
+        };
         if exists {
             return false;
         }
-        if trimmed.contains('%') {
-            let expanded = expand_env_vars(trimmed);
-            let expanded_exists = Path::new(&expanded).exists();
-            return !expanded_exists || expanded == trimmed;
+        if path_to_check.contains('%') {
+            let expanded = expand_env_vars(&path_to_check);
+            let Some(expanded_exists) = check_path_within(&expanded, check, timeout) else {
+                warn!("Existence check timed out, keeping: {}", trimmed);
+                return false;
+            };
+            return !expanded_exists || expanded == path_to_check;
         }
         true
     }
-    fn apply_fix(&self, new_path: &str) -> Result<BackupResult> {
+    /// Backs up both scopes, then writes the PATH without the removed
+    /// entries. SYSTEM PATH removals require administrator rights.
+    pub fn apply_removal(&self, plan: &RemovalPlan, force: bool) -> Result<BackupResult> {
+        if !force && !plan.protected_violations.is_empty() {
+            bail!(
+                "Removal would drop protected system entries:\n  {}\nUse --force to apply anyway.",
+                plan.protected_violations.join("\n  ")
+            );
+        }
+        if plan.location == PathLocation::System && !RegistryHelper::can_write_system_path() {
+            bail!("Removing from SYSTEM PATH requires administrator rights");
+        }
         let backup_result = self.backup_manager.create()?;
-        RegistryHelper::write_user_path(new_path)
-            .context("Failed to write new PATH to registry")?;
-        info!("PATH successfully updated in registry");
+        match plan.location {
+            PathLocation::System => RegistryHelper::write_system_path(&plan.new_path),
+            PathLocation::User => RegistryHelper::write_user_path(&plan.new_path),
+        }
+        .context("Failed to write PATH without the removed entries")?;
+        info!(
+            "Removed {} entr(ies) from {} PATH",
+            plan.removed.len(),
+            plan.location
+        );
         Ok(backup_result)
     }
+    fn apply_fix(&self, results: &mut FixResults) -> Result<()> {
+        validate_quoting(&results.new_path)?;
+        results.backup_created = Some(self.backup_manager.create()?);
+        match results.location {
+            PathLocation::User => RegistryHelper::write_user_path(&results.new_path)
+                .context("Failed to write new PATH to registry")?,
+            PathLocation::System => {
+                if let Err(e) = RegistryHelper::write_system_path(&results.new_path) {
+                    let e = anyhow::Error::from(e);
+                    warn!("Failed to write SYSTEM PATH: {:#}", e);
+                    results.system_path_error = Some(format!("{:#}", e));
+                    return Ok(());
+                }
+            }
+        }
+        info!("{} PATH successfully updated in registry", results.location);
+        Ok(())
+    }
 }
@@ -0,0 +1,1529 @@
+//! Console output formatting for spath results.
+use crate::analyzer::{
+    AnalysisGroups, AnalysisResults, ArchitectureConflict, RedundantUserEntry, ResolutionChange,
+    RuntimeShadowing,
+};
+use crate::backup::{
+    BackupFileStatus, BackupResult, PathDiff, RepairReport, RestorePreview, RestoreResult,
+    SystemRestoreStatus,
+};
+use crate::effective::{EffectivePathDiff, Resolution};
+use crate::fixer::{ApplyResult, FixResults, RemediationProjection, RemovalPlan};
+use crate::glyphs::{glyphs, Glyphs};
+use crate::messages::{tr, MessageKey};
+use crate::migrator::{ActionType, MigrationPlan, MigrationResult};
+use crate::models::{AuditStats, IssueLevel, PathIssue, PathLocation};
+use crate::report::AuditReport;
+use crate::rules::LintFinding;
+use crate::scanner::{
+    existence_from_issues, CrossDuplicate, IssueDiff, IssueSort, ScanResults,
+    DUPLICATE_PATH_MESSAGE,
+};
+use crate::security::exploits::{ExploitCheckResult, VerificationSummary};
+use crate::snapshot::{ImportPlan, PathSnapshot};
+use crate::stats::HealthSnapshot;
+use crate::utils::{terminal_width, truncate_with_ellipsis, user_tool_name};
+use colored::*;
+use std::path::{Path, PathBuf};
+
+pub mod sarif;
+
+/// Narrowest path column of [`ConsoleFormatter::render_path_table`].
+const TABLE_MIN_PATH_WIDTH: usize = 20;
+
+/// Room the path column leaves for issues before it is truncated.
+const TABLE_MIN_ISSUES_WIDTH: usize = 30;
+
+/// Printed after the cross glyph when SYSTEM PATH could not be written.
+pub const SYSTEM_PATH_FAILED_MESSAGE: &str = "Failed to update SYSTEM PATH (requires admin rights)";
+
+/// Formatter for console output.
+pub struct ConsoleFormatter;
+
+impl ConsoleFormatter {
+    /// Prints scan results with issues.
+    pub fn print_scan_results(results: &ScanResults, verbose: bool, sort: Option<IssueSort>) {
+        for issue in results.sorted_issues(sort) {
+            Self::print_issue(issue, verbose);
+        }
+        if results.issues.is_empty() {
+            println!("{}", tr(MessageKey::NoIssuesFound).green().bold());
+        }
+    }
+
+    /// Prints one row per PATH entry with its status and issues, fitted to
+    /// the terminal width.
+    pub fn print_path_table(results: &ScanResults) {
+        print!(
+            "{}",
+            Self::render_path_table(results, terminal_width(), glyphs())
+        );
+    }
+
+    /// Renders the table [`ConsoleFormatter::print_path_table`] prints,
+    /// `width` columns wide. Paths and issues too long for their column end
+    /// in an ellipsis, and repeated identical entries share the row of the
+    /// first. Info issues are left out, as in the non-verbose issue list.
+    pub fn render_path_table(results: &ScanResults, width: usize, glyphs: &Glyphs) -> String {
+        let mut rows: Vec<(usize, &str, Option<IssueLevel>, String)> = Vec::new();
+        for (index, path) in results.paths.iter().enumerate() {
+            if results.paths[..index].contains(path) {
+                continue;
+            }
+            let issues: Vec<&PathIssue> = results
+                .issues
+                .iter()
+                .filter(|issue| &issue.path == path && issue.level > IssueLevel::Info)
+                .collect();
+            let worst = issues.iter().map(|issue| issue.level).max();
+            let messages: Vec<&str> = issues.iter().map(|issue| issue.message.as_str()).collect();
+            rows.push((index, path, worst, messages.join("; ")));
+        }
+        let icon = |level: Option<IssueLevel>| match level {
+            None => glyphs.check,
+            Some(IssueLevel::Critical) => glyphs.cross,
+            Some(_) => glyphs.warning,
+        };
+        let index_width = rows.last().map_or(1, |(index, ..)| index.to_string().len());
+        let icon_width = [glyphs.check, glyphs.cross, glyphs.warning]
+            .iter()
+            .map(|g| g.chars().count())
+            .max()
+            .unwrap_or(1);
+        let fixed = index_width + icon_width + 9;
+        let longest_path = rows
+            .iter()
+            .map(|(_, path, ..)| path.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Path".len());
+        let path_width = longest_path.min(
+            width
+                .saturating_sub(fixed + TABLE_MIN_ISSUES_WIDTH)
+                .max(TABLE_MIN_PATH_WIDTH),
+        );
+        let issues_width = width.saturating_sub(fixed + path_width).max(1);
+        let mut out = format!(
+            "{:>iw$} | {:<sw$} | {:<pw$} | Issues\n",
+            "#",
+            "",
+            "Path",
+            iw = index_width,
+            sw = icon_width,
+            pw = path_width
+        );
+        out.push_str(&format!(
+            "{}-+-{}-+-{}-+-{}\n",
+            "-".repeat(index_width),
+            "-".repeat(icon_width),
+            "-".repeat(path_width),
+            "-".repeat(issues_width)
+        ));
+        for (index, path, worst, issues) in rows {
+            let status = format!("{:<sw$}", icon(worst), sw = icon_width);
+            let status = match worst {
+                None => status.green(),
+                Some(IssueLevel::Critical) => status.red(),
+                Some(_) => status.yellow(),
+            };
+            let line = format!(
+                "{:>iw$} | {} | {:<pw$} | {}",
+                index,
+                status,
+                truncate_with_ellipsis(path, path_width, glyphs.ellipsis),
+                truncate_with_ellipsis(&issues, issues_width, glyphs.ellipsis),
+                iw = index_width,
+                pw = path_width
+            );
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Prints scan results with duplicate warnings collapsed into one line per path.
+    pub fn print_scan_results_grouped(
+        results: &ScanResults,
+        verbose: bool,
+        sort: Option<IssueSort>,
+    ) {
+        for issue in results.sorted_issues(sort) {
+            if issue.message != DUPLICATE_PATH_MESSAGE {
+                Self::print_issue(issue, verbose);
+            }
+        }
+        for group in results.duplicate_groups() {
+            let indices: Vec<String> = group.indices.iter().map(|i| i.to_string()).collect();
+            println!(
+                "{} {}",
+                "[DUPLICATE]".yellow().bold(),
+                results.paths[group.kept_index]
+            );
+            println!(
+                "    {} occurrences at indices [{}]; keeping [{}]",
+                group.indices.len(),
+                indices.join(", "),
+                group.kept_index
+            );
+            println!();
+        }
+        if results.issues.is_empty() {
+            println!("{}", tr(MessageKey::NoIssuesFound).green().bold());
+        }
+    }
+
+    fn print_issue(issue: &PathIssue, verbose: bool) {
+        match issue.level {
+            IssueLevel::Critical => {
+                println!("{} {}", "[CRITICAL]".red().bold(), issue.path.yellow());
+                println!("    {}", issue.message.red());
+            }
+            IssueLevel::Warning => {
+                println!("{} {}", "[WARNING]".yellow().bold(), issue.path);
+                println!("    {}", issue.message.yellow());
+            }
+            IssueLevel::Info => {
+                if verbose {
+                    println!("{} {}", "[INFO]".blue().bold(), issue.path);
+                    println!("    {}", issue.message.blue());
+                }
+            }
+        }
+        println!();
+    }
+
+    /// Prints lint findings tagged with the rule that raised them.
+    pub fn print_lint_findings(findings: &[LintFinding], enabled_rules: &[&str]) {
+        println!("Rules: {}", enabled_rules.join(", ").dimmed());
+        println!();
+        if findings.is_empty() {
+            println!("{}", tr(MessageKey::NoIssuesFound).green().bold());
+            return;
+        }
+        for finding in findings {
+            let rule = format!("({})", finding.rule).dimmed();
+            let issue = &finding.issue;
+            match issue.level {
+                IssueLevel::Critical => {
+                    println!(
+                        "{} {} {}",
+                        "[CRITICAL]".red().bold(),
+                        issue.path.yellow(),
+                        rule
+                    );
+                    println!("    {}", issue.message.red());
+                }
+                IssueLevel::Warning => {
+                    println!("{} {} {}", "[WARNING]".yellow().bold(), issue.path, rule);
+                    println!("    {}", issue.message.yellow());
+                }
+                IssueLevel::Info => {
+                    println!("{} {} {}", "[INFO]".blue().bold(), issue.path, rule);
+                    println!("    {}", issue.message.blue());
+                }
+            }
+        }
+        println!();
+        println!("{} finding(s)", findings.len().to_string().bold());
+    }
+
+    /// Prints scan summary with issue counts.
+    pub fn print_scan_summary(results: &ScanResults) {
+        let critical = results
+            .issues
+            .iter()
+            .filter(|i| matches!(i.level, IssueLevel::Critical))
+            .count();
+        let warning = results
+            .issues
+            .iter()
+            .filter(|i| matches!(i.level, IssueLevel::Warning))
+            .count();
+        let info = results
+            .issues
+            .iter()
+            .filter(|i| matches!(i.level, IssueLevel::Info))
+            .count();
+        println!("{}", tr(MessageKey::SummaryHeading).bold());
+        println!("  {}: {}", tr(MessageKey::TotalPaths), results.paths.len());
+        println!(
+            "  {} {}",
+            critical.to_string().red().bold(),
+            tr(MessageKey::CriticalIssues)
+        );
+        println!(
+            "  {} {}",
+            warning.to_string().yellow().bold(),
+            tr(MessageKey::Warnings)
+        );
+        println!("  {} {}", info.to_string().blue(), tr(MessageKey::Info));
+    }
+
+    /// Notes how many issues `scan --level` left out of the listing.
+    pub fn print_hidden_issue_count(hidden: usize, min_level: IssueLevel) {
+        if hidden > 0 {
+            println!(
+                "  {}",
+                format!("({} below {:?} not shown)", hidden, min_level).dimmed()
+            );
+        }
+    }
+
+    /// Prints how many issues `fix` and `clean` would resolve.
+    pub fn print_remediation_projection(projection: &RemediationProjection) {
+        if projection.total == 0 {
+            return;
+        }
+        let projected: Vec<(&str, usize)> = [
+            ("fix", projection.fix_resolves),
+            ("clean", projection.clean_resolves),
+        ]
+        .into_iter()
+        .filter_map(|(command, resolves)| resolves.map(|r| (command, r)))
+        .collect();
+        if projected.is_empty() {
+            return;
+        }
+        let parts: Vec<String> = projected
+            .iter()
+            .enumerate()
+            .map(|(i, (command, resolves))| {
+                let prefix = if i == 0 { "Running " } else { "" };
+                format!(
+                    "{}'spath {}' would resolve {} of {} issues",
+                    prefix, command, resolves, projection.total
+                )
+            })
+            .collect();
+        println!();
+        println!("{}", format!("{}.", parts.join("; ")).cyan());
+    }
+
+    /// Prints detailed audit report.
+    pub fn print_scan_audit(results: &ScanResults) {
+        println!();
+        match results.location {
+            Some(location) => println!(
+                "{}",
+                format!("Detailed Audit Report ({} PATH)", location)
+                    .bold()
+                    .cyan()
+            ),
+            None => println!("{}", "Detailed Audit Report".bold().cyan()),
+        }
+        println!();
+        println!("{}", "Path Statistics:".bold());
+        println!(
+            "  Total paths in PATH: {}",
+            results.audit.total_paths.to_string().bold()
+        );
+        println!(
+            "  Valid paths: {}",
+            results.audit.valid_paths.to_string().green()
+        );
+        println!();
+        println!("{}", "Security Issues:".bold());
+        println!(
+            "  {} Unquoted paths with spaces (CRITICAL)",
+            results.audit.unquoted_with_spaces.to_string().red().bold()
+        );
+        println!("    These paths are vulnerable to DLL hijacking and privilege escalation");
+        println!();
+        println!("{}", "Path Quality Issues:".bold());
+        if results.offline {
+            println!("  {} Existence not checked (--offline)", "-".dimmed());
+        } else {
+            println!(
+                "  {} Non-existent paths",
+                results.audit.non_existent.to_string().yellow()
+            );
+            println!("    These paths don't exist on the filesystem");
+        }
+        println!(
+            "  {} Relative paths",
+            results.audit.relative_paths.to_string().yellow()
+        );
+        println!("    Should use absolute paths for consistency");
+        if results.audit.unreachable > 0 {
+            println!(
+                "  {} Unreachable paths",
+                results.audit.unreachable.to_string().yellow()
+            );
+            println!("    Existence checks timed out (offline network drives?)");
+        }
+        if results.audit.skipped > 0 {
+            println!(
+                "  {} Skipped paths",
+                results.audit.skipped.to_string().dimmed()
+            );
+            println!("    On drives excluded from the scan");
+        }
+        if !results.exclusions.is_empty() {
+            println!(
+                "  {} Excluded paths (not counted above)",
+                results.audit.excluded.to_string().dimmed()
+            );
+            println!("    Matching: {}", results.exclusions.join(", "));
+        }
+        println!();
+        println!("{}", "Good Practices:".bold());
+        println!(
+            "  {} Properly quoted paths with spaces",
+            results.audit.properly_quoted.to_string().green()
+        );
+        println!();
+        println!("{}", "PATH Health Score:".bold());
+        Self::print_health_score("", results.audit.health_score());
+    }
+
+    /// Prints the issues `scan --watch` saw appear or resolve after a PATH
+    /// change.
+    pub fn print_issue_diff(diff: &IssueDiff) {
+        println!();
+        println!(
+            "{}",
+            format!(
+                "PATH changed at {}",
+                chrono::Local::now().format("%H:%M:%S")
+            )
+            .bold()
+            .cyan()
+        );
+        if diff.is_empty() {
+            println!("  No change in issues.");
+        }
+        for issue in &diff.added {
+            let marker = match issue.level {
+                IssueLevel::Critical => "[NEW CRITICAL]".white().on_red().bold(),
+                IssueLevel::Warning => "[NEW WARNING]".yellow().bold(),
+                IssueLevel::Info => "[NEW INFO]".blue(),
+            };
+            println!("  {} {} - {}", marker, issue.path, issue.message);
+        }
+        for issue in &diff.resolved {
+            println!(
+                "  {} {} - {}",
+                "[RESOLVED]".green().bold(),
+                issue.path,
+                issue.message
+            );
+        }
+        if diff.remaining_critical > 0 {
+            println!(
+                "  {}",
+                format!(
+                    "{} pre-existing critical issue(s) still present",
+                    diff.remaining_critical
+                )
+                .red()
+            );
+        }
+    }
+
+    pub fn print_resolution(resolution: &Resolution) {
+        if resolution.matches.is_empty() {
+            println!(
+                "{}",
+                format!("'{}' was not found on PATH.", resolution.name).yellow()
+            );
+            return;
+        }
+        for executable in &resolution.matches {
+            if executable.wins {
+                println!(
+                    "  {} {}",
+                    glyphs().check.green().bold(),
+                    executable.file.bold()
+                );
+            } else {
+                println!("  {} {} (shadowed)", "-".dimmed(), executable.file.dimmed());
+            }
+        }
+        let shadowed = resolution.matches.len() - 1;
+        if shadowed > 0 {
+            println!();
+            println!(
+                "{}",
+                format!(
+                    "{} shadowed cop{} of '{}' on PATH",
+                    shadowed,
+                    if shadowed == 1 { "y" } else { "ies" },
+                    resolution.name
+                )
+                .yellow()
+            );
+        }
+    }
+
+    /// Lists entries found in both USER and SYSTEM PATH with their index in
+    /// each scope.
+    pub fn print_cross_duplicates(duplicates: &[CrossDuplicate]) {
+        if duplicates.is_empty() {
+            return;
+        }
+        println!(
+            "{}",
+            "Entries present in both USER and SYSTEM PATH:"
+                .yellow()
+                .bold()
+        );
+        for duplicate in duplicates {
+            println!(
+                "  {} (SYSTEM [{}], USER [{}])",
+                duplicate.system_path, duplicate.system_index, duplicate.user_index
+            );
+        }
+        println!();
+    }
+
+    /// Prints USER, SYSTEM and combined health scores for a two-scope scan.
+    pub fn print_scope_health_scores(scopes: &[&ScanResults]) {
+        println!();
+        println!("{}", "PATH Health Score by Scope:".bold());
+        let mut combined = AuditStats::default();
+        for results in scopes {
+            let label = results
+                .location
+                .map(|l| l.to_string())
+                .unwrap_or_else(|| "PATH".to_string());
+            Self::print_health_score(&format!("{}: ", label), results.audit.health_score());
+            combined = combined.merged(&results.audit);
+        }
+        Self::print_health_score("COMBINED: ", combined.health_score());
+    }
+
+    fn health_color(health_score: u32) -> &'static str {
+        match health_score {
+            90..=100 => "green",
+            70..=89 => "yellow",
+            _ => "red",
+        }
+    }
+
+    fn print_health_score(label: &str, health_score: u32) {
+        println!(
+            "  {}{}% {}",
+            label,
+            health_score
+                .to_string()
+                .color(Self::health_color(health_score))
+                .bold(),
+            match health_score {
+                90..=100 => "Excellent",
+                70..=89 => "Good",
+                50..=69 => "Fair",
+                _ => "Poor - Immediate attention required",
+            }
+        );
+    }
+
+    /// Prints the effective PATH a new session would get.
+    pub fn print_effective_path(entries: &[String]) {
+        for (index, entry) in entries.iter().enumerate() {
+            println!("  {:>3}. {}", index + 1, entry);
+        }
+    }
+
+    /// Prints how the process PATH differs from the registry.
+    pub fn print_effective_diff(diff: &EffectivePathDiff) {
+        if diff.is_empty() {
+            println!(
+                "{}",
+                "Process PATH matches the registry; no new session needed.".green()
+            );
+            return;
+        }
+        for entry in &diff.only_in_registry {
+            println!("  {} {}", "+".green().bold(), entry);
+        }
+        for entry in &diff.only_in_process {
+            println!("  {} {}", "-".red().bold(), entry);
+        }
+        println!();
+        println!(
+            "{}",
+            "  Note: these changes take effect in a new session (sign out or restart the terminal)."
+                .yellow()
+        );
+    }
+
+    /// Prints analysis results with categorized issues.
+    pub fn print_analysis_results(results: &AnalysisResults) {
+        println!("{}", "System PATH Analysis".bold().cyan());
+        println!();
+        let groups = results.groups();
+        let misplaced = &groups.misplaced;
+        if !misplaced.is_empty() {
+            println!(
+                "{}",
+                "User Paths in SYSTEM PATH (should be moved):"
+                    .yellow()
+                    .bold()
+            );
+            println!();
+            for entry in misplaced {
+                let status = if entry.needs_quotes() {
+                    format!("{} + {}", "MISPLACED".yellow(), "UNQUOTED".red())
+                } else {
+                    "MISPLACED".yellow().to_string()
+                };
+                match user_tool_name(&entry.path) {
+                    Some(tool) => println!("  [{}] {} ({})", status, entry.path, tool.cyan()),
+                    None => println!("  [{}] {}", status, entry.path),
+                }
+                if entry.is_missing() {
+                    println!("      Path does not exist");
+                } else if entry.is_file() {
+                    println!("      PATH entry is a file, not a directory");
+                }
+            }
+            println!();
+        }
+        let unquoted_system = &groups.unquoted_system;
+        if !unquoted_system.is_empty() {
+            println!("{}", "System Paths Needing Quotes:".red().bold());
+            println!();
+            for entry in unquoted_system {
+                println!("  [{}] {}", "UNQUOTED".red(), entry.path);
+            }
+            println!();
+        }
+        let unquoted_user = &groups.unquoted_user;
+        if !unquoted_user.is_empty() {
+            println!("{}", "User Paths Needing Quotes:".yellow().bold());
+            println!();
+            for entry in unquoted_user {
+                println!("  [{}] {}", "UNQUOTED".yellow(), entry.path);
+            }
+            println!();
+        }
+        let duplicates = &groups.duplicates;
+        if !duplicates.is_empty() {
+            println!("{}", "Duplicate Paths:".blue().bold());
+            println!();
+            for entry in duplicates {
+                let loc = match entry.location {
+                    PathLocation::System => "SYSTEM",
+                    PathLocation::User => "USER",
+                };
+                println!("  [{}] {}", loc.blue(), entry.path);
+            }
+            println!();
+        }
+        Self::print_analysis_summary(results, &groups);
+    }
+
+    /// Prints USER entries that SYSTEM PATH already provides.
+    pub fn print_redundant_user_entries(entries: &[RedundantUserEntry]) {
+        if entries.is_empty() {
+            return;
+        }
+        println!("{}", "Redundant USER Entries:".yellow().bold());
+        println!();
+        for entry in entries {
+            println!("  [{}] {}", "REDUNDANT".yellow(), entry.user_path);
+            println!("      Already in SYSTEM PATH as {}", entry.system_path);
+        }
+        println!(
+            "  {}",
+            "Recommendation: remove these from USER PATH; the SYSTEM entry always wins.".dimmed()
+        );
+        println!();
+    }
+
+    /// Prints language runtimes shadowed by earlier PATH entries.
+    pub fn print_runtime_shadowing(reports: &[RuntimeShadowing]) {
+        if reports.is_empty() {
+            return;
+        }
+        println!("{}", "Shadowed Language Runtimes:".magenta().bold());
+        println!();
+        for report in reports {
+            println!("  {}", report.executable.bold());
+            let version_label = |version: &Option<String>| {
+                version
+                    .as_deref()
+                    .map(|v| format!(" ({})", v))
+                    .unwrap_or_default()
+            };
+            let winner = report.winner();
+            println!(
+                "    {} {}{}",
+                "[WINS]".green(),
+                winner.directory,
+                version_label(&winner.version)
+            );
+            for provider in report.providers.iter().skip(1) {
+                println!(
+                    "    {} {}{}",
+                    "[SHADOWED]".dimmed(),
+                    provider.directory,
+                    version_label(&provider.version)
+                );
+            }
+        }
+        println!();
+    }
+
+    /// Prints tool directories present for both x64 and x86.
+    pub fn print_architecture_conflicts(conflicts: &[ArchitectureConflict]) {
+        if conflicts.is_empty() {
+            return;
+        }
+        println!("{}", "x64/x86 Tool Directories:".blue().bold());
+        println!();
+        for conflict in conflicts {
+            println!("  [{}] {}", "x64".blue(), conflict.x64_path);
+            println!("  [{}] {}", "x86".blue(), conflict.x86_path);
+            println!(
+                "      {} binaries win because their entry comes first",
+                conflict.winner
+            );
+        }
+        println!();
+    }
+
+    fn print_analysis_summary(results: &AnalysisResults, groups: &AnalysisGroups) {
+        let AnalysisGroups {
+            misplaced,
+            unquoted_system,
+            unquoted_user,
+            duplicates,
+        } = groups;
+        println!("{}", tr(MessageKey::SummaryHeading).bold());
+        println!();
+        let system_count = results
+            .entries
+            .iter()
+            .filter(|e| matches!(e.location, PathLocation::System))
+            .count();
+        let user_count = results
+            .entries
+            .iter()
+            .filter(|e| matches!(e.location, PathLocation::User))
+            .count();
+        println!(
+            "  Total paths: {}",
+            (system_count + user_count).to_string().bold()
+        );
+        println!("    SYSTEM PATH: {}", system_count);
+        println!("    USER PATH: {}", user_count);
+        println!();
+        println!("{}", "Issues Found:".bold());
+        println!(
+            "  {} User paths in SYSTEM PATH (should be moved)",
+            misplaced.len().to_string().yellow().bold()
+        );
+        println!(
+            "  {} System paths needing quotes (requires admin)",
+            unquoted_system.len().to_string().red().bold()
+        );
+        println!(
+            "  {} User paths needing quotes",
+            unquoted_user.len().to_string().yellow().bold()
+        );
+        println!(
+            "  {} Duplicate paths",
+            duplicates.len().to_string().blue().bold()
+        );
+        println!();
+        if !misplaced.is_empty() || !unquoted_system.is_empty() {
+            println!("{}", "Recommendations:".bold().green());
+            if !misplaced.is_empty() {
+                println!("  Run 'spath clean --dry-run' to see cleanup plan");
+            }
+            if !unquoted_system.is_empty() {
+                println!("  System paths require administrator rights to fix");
+            }
+            if !unquoted_user.is_empty() {
+                println!("  Run 'spath fix' to fix user paths");
+            }
+        } else {
+            println!("{}", "No major issues found.".green().bold());
+        }
+    }
+
+    /// Prints fix results with changes.
+    pub fn print_fix_results(results: &FixResults) {
+        if results.changes.is_empty() {
+            println!(
+                "{}",
+                "No issues found - PATH is already clean.".green().bold()
+            );
+            return;
+        }
+        println!("{}", "Changes to be applied:".bold());
+        println!();
+        for change in &results.changes {
+            println!("  {}", change);
+        }
+        println!();
+        Self::print_protected_violations(&results.protected_violations);
+        Self::print_resolution_changes(&results.resolution_changes);
+        if let Some(ref verification) = results.verification {
+            let remaining_critical = verification
+                .issues
+                .iter()
+                .filter(|i| matches!(i.level, IssueLevel::Critical))
+                .count();
+            if remaining_critical == 0 {
+                println!(
+                    "{}",
+                    format!(
+                        "{} Post-fix scan: no critical issues remain",
+                        glyphs().check
+                    )
+                    .green()
+                );
+            } else {
+                println!(
+                    "{}",
+                    format!(
+                        "{} Post-fix scan: {} critical issue(s) remain",
+                        glyphs().cross,
+                        remaining_critical
+                    )
+                    .red()
+                );
+            }
+            println!();
+        }
+        if results.dry_run {
+            if results.changed {
+                println!("{}", "Resulting PATH:".bold());
+                println!("  {}", results.new_path);
+                println!();
+            }
+            println!(
+                "{}",
+                "This was a dry run - no changes were made.".yellow().bold()
+            );
+            println!("Run without --dry-run to apply these changes.");
+        } else if results.changed {
+            if let Some(ref backup) = results.backup_created {
+                Self::print_backup_result(backup);
+            }
+            println!();
+            if let Some(ref error) = results.system_path_error {
+                Self::print_system_path_error(error, false);
+                return;
+            }
+            println!(
+                "{}",
+                format!("{} PATH has been fixed.", results.location)
+                    .green()
+                    .bold()
+            );
+            println!(
+                "{}",
+                "  Note: You may need to restart applications for changes to take effect.".yellow()
+            );
+        }
+    }
+    fn print_resolution_changes(changes: &[ResolutionChange]) {
+        if changes.is_empty() {
+            return;
+        }
+        println!(
+            "{}",
+            format!(
+                "{} Risk: these commands would resolve to a different executable:",
+                glyphs().warning
+            )
+            .yellow()
+            .bold()
+        );
+        for change in changes {
+            println!(
+                "  {}: {} -> {}",
+                change.executable.bold(),
+                change.before.as_deref().unwrap_or("(not found)"),
+                change.after.as_deref().unwrap_or("(not found)")
+            );
+        }
+        println!();
+    }
+    fn print_protected_violations(violations: &[String]) {
+        if violations.is_empty() {
+            return;
+        }
+        println!("{}", "Protected system entries affected:".red().bold());
+        for violation in violations {
+            println!("  {} {}", glyphs().cross.red(), violation);
+        }
+        println!(
+            "{}",
+            "  These changes are blocked unless --force is given.".yellow()
+        );
+        println!();
+    }
+    pub fn print_backup_result(result: &BackupResult) {
+        println!(
+            "{} {}",
+            "Backup created:".green().bold(),
+            result.path.display()
+        );
+        for cleaned in &result.cleaned_backups {
+            println!(
+                "{} Removed old backup: {}",
+                glyphs().check.green(),
+                cleaned.display()
+            );
+        }
+    }
+    pub fn print_prune_result(removed: &[PathBuf], keep: usize) {
+        if removed.is_empty() {
+            println!(
+                "{}",
+                format!("Nothing to prune: at most {} backup(s) present.", keep).green()
+            );
+            return;
+        }
+        for path in removed {
+            println!(
+                "{} Removed old backup: {}",
+                glyphs().check.green(),
+                path.display()
+            );
+        }
+        println!();
+        println!(
+            "{}",
+            format!(
+                "Removed {} backup(s); kept the newest {}.",
+                removed.len(),
+                keep
+            )
+            .green()
+            .bold()
+        );
+    }
+    pub fn print_restore_result(result: &RestoreResult) {
+        let heading = if result.user_path_restored {
+            "PATH restored from backup:"
+        } else {
+            "SYSTEM PATH restore from backup:"
+        };
+        println!("{} {}", heading.green().bold(), result.path().display());
+        match result.system_status() {
+            SystemRestoreStatus::Failed(error) => {
+                Self::print_system_path_error(&error, result.user_path_restored)
+            }
+            SystemRestoreStatus::Restored => {
+                println!("{}", "SYSTEM PATH restored successfully".green().bold())
+            }
+            SystemRestoreStatus::Skipped if !result.user_path_restored => {
+                println!(
+                    "{}",
+                    "Backup contains no SYSTEM PATH; nothing was restored.".yellow()
+                );
+                return;
+            }
+            SystemRestoreStatus::Skipped => {}
+        }
+        println!(
+            "{}",
+            "  Note: You may need to restart applications for changes to take effect.".yellow()
+        );
+    }
+    pub fn print_apply_result(result: &ApplyResult) {
+        if result.previous_path == result.new_path {
+            println!("{}", "PATH unchanged (value already current).".green());
+        } else {
+            println!("{}", "USER PATH written.".green().bold());
+        }
+        println!("  {}", result.new_path);
+        println!(
+            "{} {}",
+            "Backup created:".cyan(),
+            result.backup.path.display()
+        );
+    }
+    pub fn print_restore_preview(preview: &RestorePreview) {
+        println!(
+            "{} {}",
+            "Restore preview for:".cyan().bold(),
+            preview.backup_file.display()
+        );
+        println!();
+        println!("{}", "PATH that would be written:".bold());
+        println!("  {}", preview.restored_path);
+        println!();
+        if preview.added.is_empty() && preview.removed.is_empty() {
+            println!("{}", "No differences from the current PATH.".green());
+            return;
+        }
+        println!("{}", "Changes vs current PATH:".bold());
+        for path in &preview.added {
+            println!("  {} {}", "+".green().bold(), path);
+        }
+        for path in &preview.removed {
+            println!("  {} {}", "-".red().bold(), path);
+        }
+    }
+    /// Prints one row per backup with its USER and SYSTEM health scores,
+    /// oldest first.
+    pub fn print_health_history(history: &[HealthSnapshot]) {
+        if history.is_empty() {
+            println!("{}", "No backups found.".yellow());
+            return;
+        }
+        println!(
+            "  {:<17} {:>7} {:>7} {:>8} {:>6} {:>6}",
+            "Backup".bold(),
+            "Entries".bold(),
+            "Missing".bold(),
+            "Unquoted".bold(),
+            "USER".bold(),
+            "SYSTEM".bold()
+        );
+        let mut previous: Option<u32> = None;
+        for snapshot in history {
+            let score = snapshot.user_health_score;
+            let trend = match previous {
+                Some(prev) if score > prev => glyphs().up.green(),
+                Some(prev) if score < prev => glyphs().down.red(),
+                _ => " ".normal(),
+            };
+            let system = snapshot
+                .system_health_score
+                .map(|s| format!("{:>5}%", s).color(Self::health_color(s)))
+                .unwrap_or_else(|| format!("{:>6}", "-").dimmed());
+            println!(
+                "  {:<17} {:>7} {:>7} {:>8} {} {} {}",
+                snapshot.timestamp,
+                snapshot.user.total_paths,
+                snapshot.user.non_existent,
+                snapshot.user.unquoted_with_spaces,
+                format!("{:>5}%", score).color(Self::health_color(score)),
+                system,
+                trend
+            );
+            previous = Some(score);
+        }
+    }
+    /// Prints a git-style diff of the current USER PATH against a backup.
+    pub fn print_path_diff(diff: &PathDiff, backup_file: &Path) {
+        println!("{} {}", "---".red().bold(), backup_file.display());
+        println!("{} current USER PATH", "+++".green().bold());
+        if diff.is_empty() {
+            println!();
+            println!("{}", "No differences from the backup.".green());
+            return;
+        }
+        for path in &diff.removed {
+            println!("{}", format!("-{}", path).red());
+        }
+        for path in &diff.added {
+            println!("{}", format!("+{}", path).green());
+        }
+        for (before, after) in &diff.modified {
+            println!("{}", format!("-{}", before).red());
+            println!("{}", format!("+{}", after).green());
+        }
+        for moved in &diff.moved {
+            println!(
+                "{} {}",
+                format!("~{}", moved.path).yellow(),
+                format!("(moved {} -> {})", moved.from, moved.to).dimmed()
+            );
+        }
+    }
+    pub fn print_removal_plan(plan: &RemovalPlan, dry_run: bool) {
+        let heading = if dry_run {
+            format!("Would remove from {} PATH:", plan.location)
+        } else {
+            format!("Removing from {} PATH:", plan.location)
+        };
+        println!("{}", heading.bold());
+        for path in &plan.removed {
+            println!("  {} {}", "-".red().bold(), path);
+        }
+        if !plan.protected_violations.is_empty() {
+            println!();
+            println!(
+                "{}",
+                "Warning: this removes protected system entries:"
+                    .red()
+                    .bold()
+            );
+            for path in &plan.protected_violations {
+                println!("  {}", path.red());
+            }
+        }
+    }
+    pub fn print_indexed_paths(paths: &[String]) {
+        println!("{}", "USER PATH:".bold());
+        let width = paths.len().saturating_sub(1).to_string().len();
+        for (index, path) in paths.iter().enumerate() {
+            println!("  {:>width$}  {}", index.to_string().cyan(), path);
+        }
+    }
+    pub fn print_reorder_plan(plan: &MigrationPlan, reordered: &[String]) {
+        if plan.actions.is_empty() {
+            println!("{}", "Order unchanged.".green());
+            return;
+        }
+        for action in &plan.actions {
+            if let ActionType::ReorderAction { from, to } = action.action_type {
+                println!(
+                    "  {} {} -> {}  {}",
+                    glyphs().moved.blue().bold(),
+                    from,
+                    to,
+                    action.path
+                );
+            }
+        }
+        println!();
+        Self::print_indexed_paths(reordered);
+        if !plan.protected_violations.is_empty() {
+            println!();
+            println!(
+                "{}",
+                "Warning: this moves protected system entries behind others:"
+                    .red()
+                    .bold()
+            );
+            for path in &plan.protected_violations {
+                println!("  {}", path.red());
+            }
+        }
+    }
+    pub fn print_export_preview(snapshot: &PathSnapshot, output: &Path) {
+        println!("{} {}", "Would write to:".cyan().bold(), output.display());
+        println!();
+        print!("{}", snapshot.render());
+    }
+    pub fn print_import_plan(plan: &ImportPlan) {
+        println!("{}", "USER PATH that would be written:".bold());
+        println!("  {}", plan.new_path);
+        println!();
+        if plan.added.is_empty() && plan.removed.is_empty() {
+            println!("{}", "No differences from the current PATH.".green());
+            return;
+        }
+        println!("{}", "Changes vs current PATH:".bold());
+        for path in &plan.added {
+            println!("  {} {}", "+".green().bold(), path);
+        }
+        for path in &plan.removed {
+            println!("  {} {}", "-".red().bold(), path);
+        }
+    }
+    pub fn print_repair_report(report: &RepairReport) {
+        if report.files.is_empty() {
+            println!("{}", "No backup files found.".yellow());
+            return;
+        }
+        for file in &report.files {
+            match &file.status {
+                BackupFileStatus::Valid => {
+                    println!("{} {}", glyphs().check.green(), file.path.display());
+                    if let Some(ref error) = file.acl_error {
+                        println!("    {} {}", "ACL not re-applied:".yellow(), error);
+                    }
+                }
+                BackupFileStatus::InvalidName(reason) | BackupFileStatus::Corrupt(reason) => {
+                    let label = match file.status {
+                        BackupFileStatus::Corrupt(_) => "[CORRUPT]",
+                        _ => "[INVALID]",
+                    };
+                    println!("{} {}", label.red().bold(), file.path.display());
+                    println!("    {}", reason.red());
+                    if let Some(ref target) = file.quarantined_to {
+                        println!("    Quarantined to: {}", target.display());
+                    }
+                    if let Some(ref error) = file.quarantine_error {
+                        println!("    {} {}", "Not quarantined:".yellow(), error);
+                    }
+                }
+            }
+        }
+        println!();
+        println!("{}", tr(MessageKey::SummaryHeading).bold());
+        println!(
+            "  {} Valid backups",
+            report.valid_count().to_string().green()
+        );
+        println!(
+            "  {} Invalid or corrupt files",
+            report.broken_count().to_string().red().bold()
+        );
+        let quarantined = report
+            .files
+            .iter()
+            .filter(|f| f.quarantined_to.is_some())
+            .count();
+        if quarantined > 0 {
+            println!("  {} Files quarantined", quarantined);
+        } else if report.broken_count() > 0 {
+            println!("Run with --quarantine to move broken files out of the backup directory.");
+        }
+    }
+    pub fn print_migration_result(result: &MigrationResult) {
+        println!(
+            "{} {}",
+            "Backup created:".green().bold(),
+            result.backup_path.display()
+        );
+        if result.user_path_updated {
+            println!("{}", "USER PATH updated successfully".green().bold());
+        }
+        if let Some(ref error) = result.system_path_error {
+            Self::print_system_path_error(error, result.user_path_updated);
+        } else if result.system_path_updated {
+            println!("{}", "SYSTEM PATH updated successfully".green().bold());
+        }
+    }
+    fn print_system_path_error(error: &str, user_path_updated: bool) {
+        println!(
+            "{}",
+            format!("{} {}", glyphs().cross, SYSTEM_PATH_FAILED_MESSAGE)
+                .red()
+                .bold()
+        );
+        println!("  Error: {}", error);
+        println!();
+        if user_path_updated {
+            println!("{}", "  USER PATH was updated successfully.".green());
+        }
+        println!(
+            "{}",
+            "  Run as administrator to update SYSTEM PATH.".yellow()
+        );
+    }
+    /// Warns before `fix --system` that the write will fail without admin rights.
+    pub fn print_fix_requires_admin() {
+        println!(
+            "{}",
+            "Fixing SYSTEM PATH requires administrator rights!"
+                .yellow()
+                .bold()
+        );
+        println!(
+            "{}",
+            "  A backup is still taken, but the registry write will fail.".yellow()
+        );
+        println!();
+    }
+    pub fn print_migration_requires_admin() {
+        println!(
+            "{}",
+            "This migration requires administrator rights!"
+                .yellow()
+                .bold()
+        );
+        println!(
+            "{}",
+            "  Some changes will be skipped if not running as admin.".yellow()
+        );
+        println!();
+    }
+    pub fn print_verification_results(
+        results: &[ExploitCheckResult],
+        summary: &VerificationSummary,
+    ) {
+        for result in results {
+            if result.is_exploitable {
+                println!("{} {}", glyphs().cross.red().bold(), result.path);
+                println!(
+                    "  {} Potential exploit files found:",
+                    "DANGER:".red().bold()
+                );
+                for exploit in &result.found_exploits {
+                    println!("    - {}", exploit.red());
+                }
+                if result.severity_adjusted {
+                    println!(
+                        "  {} reported as a warning, but exploitable: treat as critical",
+                        "Severity raised:".red().bold()
+                    );
+                }
+            } else {
+                println!("{} {}", glyphs().check.green(), result.path);
+                println!("  No exploit files found - safe for now");
+            }
+            println!();
+        }
+        println!();
+        println!("{}", "Verification Summary:".bold());
+        println!("  Paths checked: {}", summary.total_checked);
+        println!(
+            "  {} Real threats (exploit files exist): {}",
+            glyphs().cross.red(),
+            summary.real_threats
+        );
+        println!(
+            "  {} Potential risks (no exploits yet): {}",
+            glyphs().check.green(),
+            summary.potential_risks
+        );
+        if summary.real_threats > 0 {
+            println!();
+            println!(
+                "{}",
+                format!("{} IMMEDIATE ACTION REQUIRED!", glyphs().warning)
+                    .red()
+                    .bold()
+            );
+            println!("  Malicious files detected that could exploit your PATH.");
+            println!("  Remove these files or fix your PATH immediately.");
+        } else {
+            println!();
+            println!("{}", "Current Status: SAFE".green().bold());
+            println!("  No active exploits detected, but paths are vulnerable.");
+            println!("  Consider fixing these issues to prevent future attacks.");
+        }
+    }
+
+    /// Short verb phrase for a migration action, used in prompts.
+    pub fn action_label(action_type: &ActionType) -> &'static str {
+        match action_type {
+            ActionType::RemoveDuplicate => "Remove duplicate",
+            ActionType::MoveToUser => "Move to USER PATH",
+            ActionType::AddQuotes => "Add quotes to",
+            ActionType::ReorderAction { .. } => "Reorder",
+        }
+    }
+    /// Prints migration plan with actions.
+    pub fn print_migration_plan(plan: &MigrationPlan, dry_run: bool) {
+        if plan.actions.is_empty() {
+            println!(
+                "{}",
+                "No migration needed - PATH is already optimal."
+                    .green()
+                    .bold()
+            );
+            return;
+        }
+        println!("{}", "Migration Plan:".bold().cyan());
+        println!();
+        let duplicates_count = plan
+            .actions
+            .iter()
+            .filter(|a| matches!(a.action_type, ActionType::RemoveDuplicate))
+            .count();
+        let moves_count = plan
+            .actions
+            .iter()
+            .filter(|a| matches!(a.action_type, ActionType::MoveToUser))
+            .count();
+        let duplicates: Vec<_> = plan
+            .actions
+            .iter()
+            .filter(|a| matches!(a.action_type, ActionType::RemoveDuplicate))
+            .collect();
+        let moves: Vec<_> = plan
+            .actions
+            .iter()
+            .filter(|a| matches!(a.action_type, ActionType::MoveToUser))
+            .collect();
+        let quotes: Vec<_> = plan
+            .actions
+            .iter()
+            .filter(|a| matches!(a.action_type, ActionType::AddQuotes))
+            .collect();
+        if !duplicates.is_empty() {
+            println!("{}", "Remove Duplicates:".blue().bold());
+            println!();
+            for action in duplicates {
+                let location = match action.from_location {
+                    PathLocation::System => "SYSTEM",
+                    PathLocation::User => "USER",
+                };
+                println!("  [{}] {}", location.blue(), action.path);
+                println!("      {}", action.reason.dimmed());
+            }
+            println!();
+        }
+        if !moves.is_empty() {
+            println!("{}", "Move to USER PATH:".yellow().bold());
+            println!();
+            for action in moves {
+                println!("  [SYSTEM -> USER] {}", action.path.yellow());
+                println!("      {}", action.reason.dimmed());
+            }
+            println!();
+        }
+        if !quotes.is_empty() {
+            println!("{}", "Add Quotes:".green().bold());
+            println!();
+            for action in &quotes {
+                println!("  [{}] {}", action.from_location, action.path.green());
+                println!("      {}", action.reason.dimmed());
+            }
+            println!();
+        }
+        Self::print_protected_violations(&plan.protected_violations);
+        println!("{}", tr(MessageKey::SummaryHeading).bold());
+        println!("  Total actions: {}", plan.actions.len().to_string().bold());
+        println!("  Duplicates to remove: {}", duplicates_count);
+        println!("  Paths to move: {}", moves_count);
+        println!("  Paths to quote: {}", quotes.len());
+        println!();
+        if plan.requires_admin {
+            println!(
+                "{}",
+                "Administrator rights required for SYSTEM PATH changes"
+                    .yellow()
+                    .bold()
+            );
+            println!();
+        }
+        if dry_run {
+            println!(
+                "{}",
+                "This is a DRY RUN - no changes will be made."
+                    .yellow()
+                    .bold()
+            );
+            println!("Run without --dry-run to apply these changes.");
+        }
+    }
+}
+
+/// Formatter for machine-readable JSON scan output.
+pub struct JsonFormatter;
+
+impl JsonFormatter {
+    /// Renders one scan as a JSON object, or several scopes as an array.
+    pub fn render(results: &[ScanResults]) -> serde_json::Result<String> {
+        match results {
+            [single] => serde_json::to_string_pretty(single),
+            all => serde_json::to_string_pretty(all),
+        }
+    }
+    /// Renders an analysis as its entries plus the groupings `analyze`
+    /// reports (see [`AnalysisResults::groups`]).
+    pub fn render_analysis(results: &AnalysisResults) -> serde_json::Result<String> {
+        let groups = results.groups();
+        serde_json::to_string_pretty(&serde_json::json!({
+            "entries": results.entries,
+            "misplaced": groups.misplaced,
+            "unquoted_system": groups.unquoted_system,
+            "unquoted_user": groups.unquoted_user,
+            "duplicates": groups.duplicates,
+        }))
+    }
+}
+
+/// Formatter for spreadsheet-friendly CSV scan output.
+pub struct CsvFormatter;
+
+impl CsvFormatter {
+    const HEADER: &'static str = "path,level,message,exists,has_spaces,is_quoted";
+
+    /// Renders a header row and one row per issue across all scans.
+    ///
+    /// `exists` is taken from the scanner's findings and left empty for
+    /// entries whose existence check timed out or was skipped offline.
+    pub fn render(results: &[ScanResults]) -> String {
+        let mut out = String::from(Self::HEADER);
+        out.push_str("\r\n");
+        for scan in results {
+            for issue in &scan.issues {
+                let trimmed = issue.path.trim();
+                let exists = Self::exists(scan, &issue.path)
+                    .map(|exists| exists.to_string())
+                    .unwrap_or_default();
+                let row = [
+                    Self::escape(&issue.path),
+                    Self::level(issue.level).to_string(),
+                    Self::escape(&issue.message),
+                    exists,
+                    trimmed.contains(' ').to_string(),
+                    (trimmed.starts_with('"') && trimmed.ends_with('"')).to_string(),
+                ];
+                out.push_str(&row.join(","));
+                out.push_str("\r\n");
+            }
+        }
+        out
+    }
+    fn exists(scan: &ScanResults, path: &str) -> Option<bool> {
+        if scan.offline {
+            return None;
+        }
+        existence_from_issues(scan.issues.iter().filter(|i| i.path == path))
+    }
+    fn level(level: IssueLevel) -> &'static str {
+        match level {
+            IssueLevel::Critical => "critical",
+            IssueLevel::Warning => "warning",
+            IssueLevel::Info => "info",
+        }
+    }
+    /// Quotes fields containing delimiters (`,` or the `;` some spreadsheet
+    /// locales use), quotes or line breaks, doubling embedded quotes.
+    fn escape(field: &str) -> String {
+        if field.contains([',', ';', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+}
+
+/// Formatter for Markdown reports that paste cleanly into issue trackers.
+pub struct MarkdownFormatter;
+
+impl MarkdownFormatter {
+    /// Renders a summary section and a table with one row per issue.
+    pub fn render(report: &AuditReport) -> String {
+        let mut out = String::from("# spath PATH report\n\n## Summary\n\n");
+        out.push_str("| Scope | Entries | Valid | Health |\n");
+        out.push_str("|-------|---------|-------|--------|\n");
+        for (scope, stats) in [
+            ("SYSTEM", &report.system),
+            ("USER", &report.user),
+            ("Combined", &report.summary),
+        ] {
+            out.push_str(&format!(
+                "| {} | {} | {} | {}% |\n",
+                scope,
+                stats.total_paths,
+                stats.valid_paths,
+                stats.health_score()
+            ));
+        }
+        out.push_str(&format!(
+            "\nOverall health: **{}%**\n",
+            report.health_score()
+        ));
+        if !report.cross_scope_duplicates.is_empty() {
+            out.push_str("\nIn both SYSTEM and USER PATH:\n\n");
+            for path in &report.cross_scope_duplicates {
+                out.push_str(&format!("- `{}`\n", path));
+            }
+        }
+        out.push_str(&format!("\n## Issues ({})\n\n", report.issues().count()));
+        out.push_str("| # | Scope | Path | Level | Message |\n");
+        out.push_str("|---|-------|------|-------|---------|\n");
+        for audited in &report.entries {
+            for issue in &audited.issues {
+                out.push_str(&format!(
+                    "| {} | {} | `{}` | {} | {} |\n",
+                    audited.entry.index,
+                    audited.entry.location,
+                    escape_markdown_cell(&issue.path),
+                    level_label(issue.level),
+                    escape_markdown_cell(&issue.message)
+                ));
+            }
+        }
+        out
+    }
+}
+
+fn level_label(level: IssueLevel) -> &'static str {
+    match level {
+        IssueLevel::Critical => "Critical",
+        IssueLevel::Warning => "Warning",
+        IssueLevel::Info => "Info",
+    }
+}
+
+fn escape_markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
@@ -0,0 +1,111 @@
+//! SARIF 2.1.0 output for GitHub code scanning.
+use crate::models::{IssueLevel, PathIssue};
+use crate::scanner::{
+    ScanResults, APP_ALIAS_DIR_MESSAGE, CURRENT_DIRECTORY_MESSAGE, DUPLICATE_PATH_MESSAGE,
+    EXPLOITABLE_PATH_MESSAGE, FILE_ENTRY_MESSAGE, MISSING_PATH_MESSAGE,
+    MISSING_UNQUOTED_PATH_MESSAGE, OVERLONG_COMPONENT_MESSAGE, QUOTED_PATH_MESSAGE,
+    RELATIVE_PATH_MESSAGE, RESERVED_CHARS_MESSAGE, SKIPPED_PATH_MESSAGE, UNQUOTED_PATH_MESSAGE,
+    UNREACHABLE_PATH_MESSAGE, UNRESOLVED_VARS_MESSAGE, WRITABLE_BY_OTHERS_MESSAGE,
+};
+use serde_json::{json, Value};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const INFORMATION_URI: &str = "https://github.com/vremyavnikuda/spath_cli";
+
+/// Rule id used for issues whose message matches no known rule.
+const GENERIC_RULE_ID: &str = "path-issue";
+
+/// Scanner message prefixes and the rule ids they map to.
+pub const RULES: &[(&str, &str)] = &[
+    (CURRENT_DIRECTORY_MESSAGE, "current-directory"),
+    (EXPLOITABLE_PATH_MESSAGE, "unquoted-spaces-exploitable"),
+    (UNQUOTED_PATH_MESSAGE, "unquoted-spaces"),
+    (MISSING_UNQUOTED_PATH_MESSAGE, "unquoted-spaces-missing"),
+    (QUOTED_PATH_MESSAGE, "properly-quoted"),
+    (MISSING_PATH_MESSAGE, "missing-path"),
+    (UNRESOLVED_VARS_MESSAGE, "unresolved-variable"),
+    (OVERLONG_COMPONENT_MESSAGE, "component-too-long"),
+    (RELATIVE_PATH_MESSAGE, "relative-path"),
+    (DUPLICATE_PATH_MESSAGE, "duplicate-entry"),
+    (UNREACHABLE_PATH_MESSAGE, "unreachable-path"),
+    (SKIPPED_PATH_MESSAGE, "skipped-drive"),
+    (APP_ALIAS_DIR_MESSAGE, "app-execution-alias"),
+    (WRITABLE_BY_OTHERS_MESSAGE, "writable-by-non-admin"),
+    (FILE_ENTRY_MESSAGE, "file-entry"),
+    (RESERVED_CHARS_MESSAGE, "reserved-characters"),
+];
+
+/// Returns the SARIF rule id for an issue, derived from its message.
+pub fn rule_id(issue: &PathIssue) -> &'static str {
+    RULES
+        .iter()
+        .find(|(prefix, _)| issue.message.starts_with(prefix))
+        .map(|(_, id)| *id)
+        .unwrap_or(GENERIC_RULE_ID)
+}
+
+fn sarif_level(level: IssueLevel) -> &'static str {
+    match level {
+        IssueLevel::Critical => "error",
+        IssueLevel::Warning => "warning",
+        IssueLevel::Info => "note",
+    }
+}
+
+/// Converts one scan into a SARIF log with a single run.
+pub fn to_sarif(results: &ScanResults) -> Value {
+    to_sarif_all(std::slice::from_ref(results))
+}
+
+/// Converts several scans (e.g. USER and SYSTEM) into one SARIF log with a
+/// run per scope.
+pub fn to_sarif_all(results: &[ScanResults]) -> Value {
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": results.iter().map(run).collect::<Vec<_>>(),
+    })
+}
+
+fn run(results: &ScanResults) -> Value {
+    let scope = results
+        .location
+        .map(|location| format!("{} PATH", location))
+        .unwrap_or_else(|| "PATH".to_string());
+    let mut rule_ids: Vec<&str> = results.issues.iter().map(rule_id).collect();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+    json!({
+        "tool": {
+            "driver": {
+                "name": "spath",
+                "version": env!("CARGO_PKG_VERSION"),
+                "informationUri": INFORMATION_URI,
+                "rules": rule_ids.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+            }
+        },
+        "results": results
+            .issues
+            .iter()
+            .map(|issue| result(issue, &scope))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn result(issue: &PathIssue, scope: &str) -> Value {
+    json!({
+        "ruleId": rule_id(issue),
+        "level": sarif_level(issue.level),
+        "message": {
+            "text": format!("{}: {}", issue.message, issue.path),
+        },
+        "locations": [{
+            "logicalLocations": [{
+                "name": issue.path,
+                "fullyQualifiedName": format!("{}/{}", scope, issue.path),
+                "kind": "member",
+            }]
+        }],
+    })
+}
@@ -0,0 +1,91 @@
+//! Status and tree glyphs used by console output, with an ASCII fallback
+//! for legacy consoles and log viewers that cannot show Unicode.
+use std::sync::atomic::{AtomicBool, Ordering};
+use windows::Win32::Globalization::CP_UTF8;
+use windows::Win32::System::Console::GetConsoleOutputCP;
+
+/// The symbols one output mode prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyphs {
+    /// Before a successful check or operation.
+    pub check: &'static str,
+    /// Before a failed check or operation.
+    pub cross: &'static str,
+    pub warning: &'static str,
+    pub info: &'static str,
+    /// Tree branch to an entry that has siblings below it.
+    pub branch: &'static str,
+    /// Tree branch to the last entry.
+    pub last_branch: &'static str,
+    /// Continues a tree branch past a nested line.
+    pub pipe: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+    pub moved: &'static str,
+    /// Replaces the end of truncated text.
+    pub ellipsis: &'static str,
+}
+
+/// Glyphs for UTF-8 consoles.
+pub const UNICODE_GLYPHS: Glyphs = Glyphs {
+    check: "✓",
+    cross: "✗",
+    warning: "⚠",
+    info: "ℹ",
+    branch: "├─",
+    last_branch: "└─",
+    pipe: "│",
+    up: "↑",
+    down: "↓",
+    moved: "↕",
+    ellipsis: "…",
+};
+
+/// Pure ASCII glyphs for `--ascii` and non-UTF-8 consoles.
+pub const ASCII_GLYPHS: Glyphs = Glyphs {
+    check: "[OK]",
+    cross: "[X]",
+    warning: "!",
+    info: "i",
+    branch: "+--",
+    last_branch: "\\--",
+    pipe: "|",
+    up: "^",
+    down: "v",
+    moved: "~",
+    ellipsis: "...",
+};
+
+impl Glyphs {
+    /// [`Glyphs::check`] when `ok`, otherwise [`Glyphs::cross`].
+    pub fn status(&self, ok: bool) -> &'static str {
+        if ok {
+            self.check
+        } else {
+            self.cross
+        }
+    }
+}
+
+static ASCII_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Switches [`glyphs`] to [`ASCII_GLYPHS`].
+pub fn set_ascii(ascii: bool) {
+    ASCII_MODE.store(ascii, Ordering::Relaxed);
+}
+
+/// The glyphs for the current output mode.
+pub fn glyphs() -> &'static Glyphs {
+    if ASCII_MODE.load(Ordering::Relaxed) {
+        &ASCII_GLYPHS
+    } else {
+        &UNICODE_GLYPHS
+    }
+}
+
+/// Whether the attached console uses a non-UTF-8 output codepage. Without
+/// a console (output piped or redirected) this is `false`.
+pub fn console_needs_ascii() -> bool {
+    let codepage = unsafe { GetConsoleOutputCP() };
+    codepage != 0 && codepage != CP_UTF8
+}
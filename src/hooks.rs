@@ -0,0 +1,56 @@
+//! Post-operation hooks run after PATH has been modified.
+use anyhow::{bail, Context, Result};
+use std::process::Command;
+use tracing::{debug, info, warn};
+use windows::core::w;
+use windows::Win32::Foundation::{GetLastError, ERROR_TIMEOUT, LPARAM, WPARAM};
+use windows::Win32::UI::WindowsAndMessaging::{
+    SendMessageTimeoutW, HWND_BROADCAST, SMTO_ABORTIFHUNG, WM_SETTINGCHANGE,
+};
+
+/// Milliseconds to wait for each top-level window to handle the broadcast.
+const BROADCAST_TIMEOUT_MS: u32 = 5000;
+
+/// Broadcasts `WM_SETTINGCHANGE` for "Environment" so running applications
+/// (Explorer, new terminals) pick up the updated PATH without a new login.
+///
+/// Windows that time out are not treated as an error.
+pub fn broadcast_environment_change() -> Result<()> {
+    debug!("Broadcasting WM_SETTINGCHANGE (Environment)");
+    let mut result = 0usize;
+    let sent = unsafe {
+        SendMessageTimeoutW(
+            HWND_BROADCAST,
+            WM_SETTINGCHANGE,
+            WPARAM(0),
+            LPARAM(w!("Environment").as_ptr() as isize),
+            SMTO_ABORTIFHUNG,
+            BROADCAST_TIMEOUT_MS,
+            Some(&mut result),
+        )
+    };
+    if sent.0 == 0 {
+        let error = unsafe { GetLastError() };
+        if let Err(e) = error {
+            if e.code() != ERROR_TIMEOUT.to_hresult() {
+                bail!("Failed to broadcast environment change: {}", e);
+            }
+        }
+        warn!("Some windows did not respond to the environment change broadcast");
+    }
+    info!("Environment change broadcast sent");
+    Ok(())
+}
+
+/// Runs a user-supplied command through `cmd /C` after a successful change.
+pub fn run_post_hook(command: &str) -> Result<()> {
+    info!("Running post-hook: {}", command);
+    let status = Command::new("cmd")
+        .args(["/C", command])
+        .status()
+        .with_context(|| format!("Failed to start post-hook: {}", command))?;
+    if !status.success() {
+        bail!("Post-hook '{}' failed with {}", command, status);
+    }
+    Ok(())
+}
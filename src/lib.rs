@@ -5,13 +5,25 @@
 
 pub mod analyzer;
 pub mod backup;
+pub mod config;
 pub mod constants;
+pub mod effective;
 pub mod fixer;
 pub mod formatter;
+pub mod glyphs;
+pub mod hooks;
+pub mod messages;
 pub mod migrator;
 pub mod models;
+pub mod prompt;
 pub mod registry;
+pub mod report;
+pub mod rules;
 pub mod scanner;
 pub mod security;
+pub mod snapshot;
+pub mod stats;
 pub mod utils;
 pub mod visualizer;
+
+pub use report::{audit_all, AuditReport};
@@ -1,37 +1,35 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use colored::*;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
 use tracing_subscriber::EnvFilter;
 
-mod analyzer;
-mod backup;
-mod constants;
-mod fixer;
-mod formatter;
-mod migrator;
-mod models;
-mod registry;
-mod scanner;
-mod security;
-mod utils;
-mod visualizer;
-
-use analyzer::SystemAnalyzer;
-use fixer::PathFixer;
-use formatter::ConsoleFormatter;
-use migrator::PathMigrator;
-use models::IssueLevel;
-use scanner::PathScanner;
-
-fn ask_confirmation(message: &str) -> bool {
-    print!("{} [y/N]: ", message);
-    io::stdout().flush().unwrap();
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    let answer = input.trim().to_lowercase();
-    answer == "y" || answer == "yes"
-}
+use spath_cli::analyzer::SystemAnalyzer;
+use spath_cli::backup::BackupManager;
+use spath_cli::config::SpathConfig;
+use spath_cli::constants::DEFAULT_EXISTENCE_TIMEOUT_MS;
+use spath_cli::fixer::{FixOptions, KeepOverrides, PathFixer, RemovalPlan};
+use spath_cli::formatter::{
+    sarif, ConsoleFormatter, CsvFormatter, JsonFormatter, MarkdownFormatter,
+};
+use spath_cli::glyphs::glyphs;
+use spath_cli::messages::{set_locale, tr, Locale, MessageKey};
+use spath_cli::migrator::{MigrationPlan, PathMigrator, ReorderOp};
+use spath_cli::models::{IssueLevel, PathEntry, PathIssue, PathLocation, ValidityPolicy};
+use spath_cli::prompt::{AutoConfirmPrompter, InteractivePrompter, Prompter};
+use spath_cli::registry::RegistrySource;
+use spath_cli::scanner::{
+    CombinedScanResults, CrossDuplicate, IssueDiff, IssueSort, PathScanner, ScanResults,
+};
+use spath_cli::snapshot::{ImportMode, PathSnapshot};
+use spath_cli::{
+    analyzer, constants, effective, fixer, glyphs, hooks, registry, report, rules, security, stats,
+    utils, visualizer,
+};
 
 #[derive(Parser)]
 #[command(name = "spath")]
@@ -39,45 +37,383 @@ fn ask_confirmation(message: &str) -> bool {
 #[command(about = "Windows PATH security scanner and fixer", long_about = None)]
 #[command(version)]
 struct Cli {
+    #[arg(long, global = true, value_name = "LANG", value_parser = parse_locale)]
+    lang: Option<Locale>,
+    #[arg(long, global = true)]
+    no_system_fallback: bool,
+    #[arg(long, global = true)]
+    no_dry_run: bool,
+    #[arg(long, global = true)]
+    no_system: bool,
+    #[arg(long, global = true)]
+    ascii: bool,
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
     #[command(subcommand)]
     command: Commands,
 }
 
+fn parse_drive(value: &str) -> Result<char, String> {
+    let mut chars = value.trim_end_matches(['\\', ':']).chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) if letter.is_ascii_alphabetic() => Ok(letter.to_ascii_uppercase()),
+        _ => Err(format!(
+            "invalid drive '{}' (expected a letter such as E)",
+            value
+        )),
+    }
+}
+
+fn parse_locale(value: &str) -> Result<Locale, String> {
+    Locale::parse(value)
+        .ok_or_else(|| format!("unsupported language '{}' (expected en or ru)", value))
+}
+
+#[derive(Args)]
+struct ScanArgs {
+    #[arg(short, long)]
+    verbose: bool,
+    #[arg(short, long)]
+    audit: bool,
+    #[arg(short, long)]
+    system: bool,
+    #[arg(long, conflicts_with_all = ["system", "stdin"])]
+    both: bool,
+    #[arg(long, conflicts_with = "system")]
+    stdin: bool,
+    #[arg(long, requires = "stdin")]
+    raw: bool,
+    #[arg(long)]
+    group_duplicates: bool,
+    #[arg(long)]
+    redact: bool,
+    #[arg(long, requires = "redact")]
+    hash_dirs: bool,
+    #[arg(long = "timeout", visible_alias = "timeout-ms", value_name = "MS", default_value_t = DEFAULT_EXISTENCE_TIMEOUT_MS)]
+    timeout_ms: u64,
+    #[arg(long)]
+    offline: bool,
+    #[arg(long)]
+    progress: bool,
+    #[arg(long)]
+    parallel: bool,
+    #[arg(long, value_name = "DRIVE", value_parser = parse_drive)]
+    exclude_drive: Vec<char>,
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+    #[arg(long, value_enum, value_name = "RULE")]
+    relax: Vec<ValidityRule>,
+    #[arg(long, alias = "output", value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+    #[arg(long, value_enum, default_value_t = LevelFilter::Info)]
+    level: LevelFilter,
+    #[arg(long, value_enum, value_name = "KEY")]
+    sort: Option<SortKey>,
+    #[arg(long, value_name = "BACKUP")]
+    since: Option<String>,
+    #[arg(long, conflicts_with = "stdin")]
+    watch: bool,
+    #[arg(long, value_name = "SECONDS", default_value_t = 2, requires = "watch")]
+    interval: u64,
+}
+
+/// Output format for `scan`; Markdown reports cover both scopes.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+    Markdown,
+    Sarif,
+    Csv,
+}
+
+/// Output format for `diff`, `stats` and `analyze`.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DiffFormat {
+    Text,
+    Json,
+}
+
+/// Lowest severity `scan --level` shows.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LevelFilter {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl From<LevelFilter> for IssueLevel {
+    fn from(level: LevelFilter) -> Self {
+        match level {
+            LevelFilter::Info => IssueLevel::Info,
+            LevelFilter::Warning => IssueLevel::Warning,
+            LevelFilter::Critical => IssueLevel::Critical,
+        }
+    }
+}
+
+/// Order of the issues `scan` lists.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SortKey {
+    Name,
+    Length,
+    Status,
+}
+
+impl From<SortKey> for IssueSort {
+    fn from(key: SortKey) -> Self {
+        match key {
+            SortKey::Name => IssueSort::Name,
+            SortKey::Length => IssueSort::Length,
+            SortKey::Status => IssueSort::Status,
+        }
+    }
+}
+
+/// When to color output.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ColorChoice {
+    Always,
+    Never,
+    Auto,
+}
+
+impl ColorChoice {
+    /// `Some` when the choice overrides `NO_COLOR` and terminal detection.
+    fn forced(self) -> Option<bool> {
+        match self {
+            ColorChoice::Always => Some(true),
+            ColorChoice::Never => Some(false),
+            ColorChoice::Auto => None,
+        }
+    }
+}
+
+/// Which scan issues `verify` checks for planted exploit files.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum VerifyLevel {
+    Critical,
+    Warning,
+    All,
+}
+
+impl VerifyLevel {
+    fn includes(self, level: IssueLevel) -> bool {
+        match self {
+            VerifyLevel::Critical => level == IssueLevel::Critical,
+            VerifyLevel::Warning => level == IssueLevel::Warning,
+            VerifyLevel::All => level >= IssueLevel::Warning,
+        }
+    }
+}
+
+/// Audit validity requirement that `scan --relax` can drop.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ValidityRule {
+    Exists,
+    Absolute,
+    Quotes,
+}
+
+#[derive(Args)]
+struct FixArgs {
+    #[arg(short, long)]
+    system: bool,
+    #[arg(short, long)]
+    dry_run: bool,
+    #[arg(long)]
+    delicate: bool,
+    #[arg(long)]
+    force: bool,
+    #[command(flatten)]
+    keep: KeepArgs,
+    #[arg(long, value_name = "DRIVE", value_parser = parse_drive)]
+    exclude_drive: Vec<char>,
+    #[arg(long)]
+    canonicalize_case: bool,
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+    #[arg(long = "timeout", value_name = "MS", default_value_t = DEFAULT_EXISTENCE_TIMEOUT_MS)]
+    timeout_ms: u64,
+    #[command(flatten)]
+    hooks: HookArgs,
+}
+
+#[derive(Args)]
+struct KeepArgs {
+    #[arg(long, value_name = "PATH")]
+    keep_path: Vec<String>,
+    #[arg(long, value_name = "N")]
+    keep_index: Vec<usize>,
+}
+
+#[derive(Args)]
+struct HookArgs {
+    #[arg(long)]
+    broadcast: bool,
+    #[arg(long, value_name = "CMD")]
+    post_hook: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    Init {
+        #[arg(long)]
+        force: bool,
+    },
+}
+
 #[derive(Subcommand)]
 enum Commands {
-    Scan {
-        #[arg(short, long)]
-        verbose: bool,
+    #[command(
+        after_help = "Exit codes: 0 when no warnings or critical issues were found, 1 when the worst issue is a warning, 2 when critical issues were found."
+    )]
+    Scan(ScanArgs),
+    Fix(FixArgs),
+    Apply {
+        #[arg(long, required = true)]
+        stdin: bool,
+        #[command(flatten)]
+        hooks: HookArgs,
+    },
+    Backup {
+        #[arg(long, value_name = "N")]
+        max_backups: Option<usize>,
+    },
+    ListBackups,
+    Restore {
+        backup_file: String,
+        #[arg(long)]
+        delicate: bool,
         #[arg(short, long)]
-        audit: bool,
+        dry_run: bool,
+        #[arg(long, conflicts_with = "system")]
+        user_only: bool,
         #[arg(short, long)]
         system: bool,
     },
-    Fix {
+    Diff {
+        backup_file: PathBuf,
+        #[arg(long, alias = "output", value_enum, default_value_t = DiffFormat::Text)]
+        format: DiffFormat,
+    },
+    RepairBackups {
+        #[arg(long)]
+        quarantine: bool,
+    },
+    PruneBackups {
+        #[arg(long, value_name = "N")]
+        keep: Option<usize>,
+    },
+    Stats {
+        #[arg(long, value_name = "N")]
+        last: Option<usize>,
+        #[arg(long, alias = "output", value_enum, default_value_t = DiffFormat::Text)]
+        format: DiffFormat,
+    },
+    Analyze {
+        #[arg(long)]
+        runtime_versions: bool,
+        #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+        format: DiffFormat,
+        #[arg(long)]
+        offline: bool,
+        #[arg(long = "timeout", value_name = "MS", default_value_t = DEFAULT_EXISTENCE_TIMEOUT_MS)]
+        timeout_ms: u64,
+    },
+    Effective {
+        #[arg(long)]
+        vs_registry: bool,
+    },
+    Resolve {
+        name: String,
+        #[arg(long)]
+        json: bool,
+    },
+    Clean {
+        #[arg(short, long)]
+        system: bool,
         #[arg(short, long)]
         dry_run: bool,
         #[arg(long)]
         delicate: bool,
+        #[arg(long)]
+        force: bool,
+        #[arg(long, conflicts_with_all = ["delicate", "force"])]
+        json: bool,
+        #[arg(long, value_name = "PATTERN")]
+        exclude: Vec<String>,
+        #[command(flatten)]
+        hooks: HookArgs,
     },
-    Backup,
-    ListBackups,
-    Restore {
-        backup_file: String,
+    Verify {
+        #[arg(short, long)]
+        system: bool,
+        #[arg(long, value_enum, default_value_t = VerifyLevel::Critical)]
+        level: VerifyLevel,
+        #[arg(long, conflicts_with = "level")]
+        all: bool,
+    },
+    Check {
+        #[arg(short, long)]
+        verbose: bool,
+    },
+    Export {
+        #[arg(short, long, value_name = "FILE")]
+        output: PathBuf,
+        #[arg(short, long, conflicts_with_all = ["user", "both"])]
+        system: bool,
+        #[arg(short, long, conflicts_with = "both")]
+        user: bool,
+        #[arg(long)]
+        both: bool,
+        #[arg(short, long)]
+        dry_run: bool,
+        #[arg(long)]
+        force: bool,
+    },
+    Import {
+        file: PathBuf,
+        #[arg(long)]
+        merge: bool,
+        #[arg(short, long)]
+        dry_run: bool,
         #[arg(long)]
         delicate: bool,
+        #[command(flatten)]
+        hooks: HookArgs,
     },
-    Analyze,
-    Clean {
+    Remove {
+        path: String,
         #[arg(short, long)]
         system: bool,
         #[arg(short, long)]
         dry_run: bool,
         #[arg(long)]
-        delicate: bool,
+        all: bool,
+        #[arg(long)]
+        force: bool,
     },
-    Verify {
+    Reorder {
+        commands: Vec<String>,
+        #[arg(short, long)]
+        dry_run: bool,
+        #[arg(long)]
+        force: bool,
+    },
+    Lint {
         #[arg(short, long)]
         system: bool,
+        #[arg(long = "disable-rule", value_name = "NAME")]
+        disable_rule: Vec<String>,
+    },
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    #[command(about = "Print a completion script for bash, elvish, fish, powershell or zsh")]
+    Completions {
+        shell: Shell,
     },
     Visualize {
         #[arg(short, long)]
@@ -97,165 +433,982 @@ fn main() -> Result<()> {
             EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
         )
         .init();
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    set_locale(cli.lang.unwrap_or_else(Locale::from_env));
+    glyphs::set_ascii(cli.ascii || glyphs::console_needs_ascii());
+    let use_color = utils::should_colorize(
+        cli.color.forced(),
+        std::env::var(constants::NO_COLOR_ENV_VAR).ok().as_deref(),
+        io::stdout().is_terminal(),
+    );
+    colored::control::set_override(use_color);
+    if !matches!(
+        cli.command,
+        Commands::Config { .. } | Commands::Completions { .. }
+    ) {
+        let mut config = SpathConfig::load()?;
+        config.dry_run &= !cli.no_dry_run;
+        config.system &= !cli.no_system;
+        apply_config(&mut cli.command, &config);
+    }
     match cli.command {
-        Commands::Scan {
-            verbose,
-            audit,
-            system,
-        } => handle_scan(verbose, audit, system),
-        Commands::Fix { dry_run, delicate } => handle_fix(dry_run, delicate),
-        Commands::Backup => handle_backup(),
+        Commands::Scan(args) => handle_scan(&args, cli.no_system_fallback),
+        Commands::Fix(args) => handle_fix(args, cli.no_system_fallback),
+        Commands::Apply { stdin: _, hooks } => handle_apply(&hooks),
+        Commands::Backup { max_backups } => handle_backup(cli.no_system_fallback, max_backups),
         Commands::ListBackups => handle_list_backups(),
         Commands::Restore {
             backup_file,
             delicate,
-        } => handle_restore(&backup_file, delicate),
-        Commands::Analyze => handle_analyze(),
+            dry_run,
+            user_only,
+            system,
+        } => handle_restore(&backup_file, delicate, dry_run, user_only, system),
+        Commands::Diff {
+            backup_file,
+            format,
+        } => handle_diff(&backup_file, format),
+        Commands::RepairBackups { quarantine } => handle_repair_backups(quarantine),
+        Commands::PruneBackups { keep } => handle_prune_backups(keep),
+        Commands::Stats { last, format } => handle_stats(last, format),
+        Commands::Analyze {
+            runtime_versions,
+            format,
+            offline,
+            timeout_ms,
+        } => handle_analyze(runtime_versions, format, offline, timeout_ms),
+        Commands::Effective { vs_registry } => handle_effective(vs_registry),
+        Commands::Resolve { name, json } => handle_resolve(&name, json),
         Commands::Clean {
             system,
             dry_run,
             delicate,
-        } => handle_clean(system, dry_run, delicate),
-        Commands::Verify { system } => handle_verify(system),
+            force,
+            json,
+            exclude,
+            hooks,
+        } => handle_clean(system, dry_run, delicate, force, json, &exclude, &hooks),
+        Commands::Verify { system, level, all } => {
+            handle_verify(system, if all { VerifyLevel::All } else { level })
+        }
+        Commands::Check { verbose } => handle_check(verbose),
+        Commands::Export {
+            output,
+            system,
+            user,
+            both,
+            dry_run,
+            force,
+        } => handle_export(&output, system, user, both, dry_run, force),
+        Commands::Import {
+            file,
+            merge,
+            dry_run,
+            delicate,
+            hooks,
+        } => handle_import(&file, merge, dry_run, delicate, &hooks),
+        Commands::Remove {
+            path,
+            system,
+            dry_run,
+            all,
+            force,
+        } => handle_remove(&path, system, dry_run, all, force, cli.no_system_fallback),
+        Commands::Reorder {
+            commands,
+            dry_run,
+            force,
+        } => handle_reorder(&commands, dry_run, force),
+        Commands::Lint {
+            system,
+            disable_rule,
+        } => handle_lint(system, &disable_rule),
+        Commands::Config {
+            action: ConfigAction::Init { force },
+        } => handle_config_init(force),
+        Commands::Completions { shell } => handle_completions(shell),
         Commands::Visualize {
             tree,
             system,
             user,
             no_color,
-        } => handle_visualize(tree, system, user, no_color),
+        } => handle_visualize(tree, system, user, use_color && !no_color),
     }
 }
 
-fn handle_scan(verbose: bool, audit: bool, system: bool) -> Result<()> {
-    println!("{}", "spath - Windows PATH Security Scanner".bold().cyan());
-    if system {
+/// Fills in defaults from the config file for flags not given on the
+/// command line.
+fn apply_config(command: &mut Commands, config: &SpathConfig) {
+    let default_drives = |drives: &mut Vec<char>| {
+        if drives.is_empty() {
+            drives.clone_from(&config.exclude_drives);
+        }
+    };
+    let default_patterns = |patterns: &mut Vec<String>| {
+        if patterns.is_empty() {
+            patterns.clone_from(&config.exclude_patterns);
+        }
+    };
+    match command {
+        Commands::Scan(args) => {
+            args.system |= config.system && !args.both && !args.stdin;
+            default_drives(&mut args.exclude_drive);
+            default_patterns(&mut args.exclude);
+        }
+        Commands::Fix(args) => {
+            args.dry_run |= config.dry_run;
+            default_drives(&mut args.exclude_drive);
+            default_patterns(&mut args.exclude);
+        }
+        Commands::Backup { max_backups } => {
+            *max_backups = max_backups.or(config.max_backups);
+        }
+        Commands::PruneBackups { keep } => *keep = keep.or(config.max_backups),
+        Commands::Clean {
+            system,
+            dry_run,
+            exclude,
+            ..
+        } => {
+            *system |= config.system;
+            *dry_run |= config.dry_run;
+            default_patterns(exclude);
+        }
+        Commands::Verify { system, .. } => *system |= config.system,
+        Commands::Lint {
+            system,
+            disable_rule,
+        } => {
+            *system |= config.system;
+            if disable_rule.is_empty() {
+                disable_rule.clone_from(&config.disabled_rules);
+            }
+        }
+        Commands::Restore { dry_run, .. }
+        | Commands::Export { dry_run, .. }
+        | Commands::Import { dry_run, .. }
+        | Commands::Remove { dry_run, .. }
+        | Commands::Reorder { dry_run, .. } => *dry_run |= config.dry_run,
+        _ => {}
+    }
+}
+
+fn handle_completions(shell: Shell) -> Result<()> {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, &mut io::stdout());
+    Ok(())
+}
+
+fn handle_config_init(force: bool) -> Result<()> {
+    let path = SpathConfig::default_path()?;
+    SpathConfig::write_default(&path, force)?;
+    println!("{} {}", "Config written to:".green().bold(), path.display());
+    Ok(())
+}
+
+fn handle_scan(args: &ScanArgs, user_only: bool) -> Result<()> {
+    if args.format == OutputFormat::Markdown {
+        if args.stdin {
+            anyhow::bail!("--format markdown reports on the registry PATH and cannot read --stdin");
+        }
+        let combined = PathScanner::scan_both_with(|scanner| configure_scanner(scanner, args))?;
+        let exit_code = report::exit_code(
+            combined
+                .user
+                .worst_level()
+                .max(combined.system.worst_level()),
+        );
+        let shown = CombinedScanResults::new(
+            output_results(&combined.user, args),
+            output_results(&combined.system, args),
+        );
+        print!("{}", MarkdownFormatter::render(&report::from_scans(&shown)));
+        return exit_with_code(exit_code);
+    }
+    if args.watch && args.format != OutputFormat::Text {
+        anyhow::bail!("--watch only supports text output");
+    }
+    let (results, cross_duplicates) = collect_scan_results(args)?;
+    let exit_code = report::exit_code(results.iter().filter_map(ScanResults::worst_level).max());
+    if matches!(
+        args.format,
+        OutputFormat::Json | OutputFormat::Sarif | OutputFormat::Csv
+    ) {
+        let results: Vec<ScanResults> = results.iter().map(|r| output_results(r, args)).collect();
+        match args.format {
+            OutputFormat::Sarif => {
+                let log = match results.as_slice() {
+                    [single] => sarif::to_sarif(single),
+                    all => sarif::to_sarif_all(all),
+                };
+                println!("{}", serde_json::to_string_pretty(&log)?);
+            }
+            OutputFormat::Csv => print!("{}", CsvFormatter::render(&results)),
+            _ => println!("{}", JsonFormatter::render(&results)?),
+        }
+        return exit_with_code(exit_code);
+    }
+    println!("{}", tr(MessageKey::ScanTitle).bold().cyan());
+    if let Some(backup) = &args.since {
+        println!(
+            "{}",
+            format!("Showing only issues not present in backup {}", backup).yellow()
+        );
+    }
+    if args.system {
         println!(
             "{}",
             "Scanning SYSTEM PATH (requires admin rights to fix)".yellow()
         );
     }
-    let scanner = PathScanner::new(system)?;
-    let results = scanner.scan()?;
-    ConsoleFormatter::print_scan_results(&results, verbose);
-    ConsoleFormatter::print_scan_summary(&results);
-    if audit {
-        ConsoleFormatter::print_scan_audit(&results);
+    if let [user_results, system_results] = results.as_slice() {
+        for results in [user_results, system_results] {
+            if let Some(location) = results.location {
+                println!("{}", format!("{} PATH", location).bold().cyan());
+            }
+            print_scan_report(results, args, user_only);
+            println!();
+        }
+        let cross_duplicates: Vec<CrossDuplicate> = if args.redact {
+            cross_duplicates
+                .iter()
+                .map(|duplicate| duplicate.redacted(args.hash_dirs))
+                .collect()
+        } else {
+            cross_duplicates
+        };
+        ConsoleFormatter::print_cross_duplicates(&cross_duplicates);
+        if args.audit {
+            ConsoleFormatter::print_scope_health_scores(&[user_results, system_results]);
+        }
+    } else {
+        print_scan_report(&results[0], args, user_only);
+    }
+    if args.watch {
+        return watch_scan(args, results);
+    }
+    exit_with_code(exit_code)
+}
+
+/// Applies `--level` and `--redact` to results about to be written in a
+/// machine-readable format.
+fn output_results(results: &ScanResults, args: &ScanArgs) -> ScanResults {
+    let results = results.with_min_level(args.level.into());
+    if args.redact {
+        results.redacted(args.hash_dirs)
+    } else {
+        results
+    }
+}
+
+fn collect_scan_results(args: &ScanArgs) -> Result<(Vec<ScanResults>, Vec<CrossDuplicate>)> {
+    let (results, cross_duplicates) = scan_current(args)?;
+    match &args.since {
+        Some(backup) => Ok((issues_since(args, backup, results)?, cross_duplicates)),
+        None => Ok((results, cross_duplicates)),
     }
-    Ok(())
 }
 
-fn handle_fix(dry_run: bool, delicate: bool) -> Result<()> {
-    println!("{}", "spath - PATH Fixer".bold().cyan());
+fn scan_current(args: &ScanArgs) -> Result<(Vec<ScanResults>, Vec<CrossDuplicate>)> {
+    if args.both {
+        let combined = PathScanner::scan_both_with(|scanner| configure_scanner(scanner, args))?;
+        return Ok((
+            vec![combined.user, combined.system],
+            combined.cross_duplicates,
+        ));
+    }
+    let results = if args.stdin {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        configure_scanner(PathScanner::from_input(&input, args.raw), args).scan()?
+    } else {
+        configure_scanner(PathScanner::new(args.system)?, args).scan()?
+    };
+    Ok((vec![results], Vec::new()))
+}
+
+/// Drops the issues each scope already had in the `--since` backup, so only
+/// regressions are reported. A scope the backup lacks is left unfiltered.
+fn issues_since(
+    args: &ScanArgs,
+    backup: &str,
+    results: Vec<ScanResults>,
+) -> Result<Vec<ScanResults>> {
+    let manager = BackupManager::new()?;
+    let baseline = manager.load(&manager.resolve(backup)?)?;
+    results
+        .into_iter()
+        .map(|current| {
+            let location = current.location.unwrap_or(PathLocation::User);
+            let stored = match location {
+                PathLocation::User => Some(baseline.user_path.as_str()),
+                PathLocation::System => baseline.system_path.as_deref(),
+            };
+            let Some(stored) = stored else {
+                tracing::warn!("Backup has no {} PATH; reporting all its issues", location);
+                return Ok(current);
+            };
+            let scanner = PathScanner::from_string(stored).with_location(location);
+            Ok(current.new_since(&configure_scanner(scanner, args).scan()?))
+        })
+        .collect()
+}
+
+/// Waits for registry change notifications on both PATH scopes, polling
+/// every `--interval` seconds as a fallback, and after each change re-scans
+/// and prints the issues added or resolved.
+fn watch_scan(args: &ScanArgs, mut previous: Vec<ScanResults>) -> Result<()> {
+    let interval = Duration::from_secs(args.interval.max(1));
+    let (notify, changes) = mpsc::channel();
+    let _watchers: Vec<_> = [PathLocation::User, PathLocation::System]
+        .into_iter()
+        .filter_map(|scope| {
+            let notify = notify.clone();
+            registry::RegistryHelper::watch_path_change(scope, move || {
+                let _ = notify.send(());
+            })
+            .map_err(|e| {
+                let e = anyhow::Error::from(e);
+                tracing::warn!("Falling back to polling {} PATH: {:#}", scope, e)
+            })
+            .ok()
+        })
+        .collect();
     println!();
-    if dry_run {
+    println!(
+        "{}",
+        format!(
+            "Watching PATH for changes, checking at least every {}s (Ctrl+C to stop)...",
+            interval.as_secs()
+        )
+        .cyan()
+    );
+    let mut values = registry_path_values();
+    loop {
+        let _ = changes.recv_timeout(interval);
+        let current_values = registry_path_values();
+        if current_values == values {
+            continue;
+        }
+        values = current_values;
+        let (current, _) = collect_scan_results(args)?;
+        ConsoleFormatter::print_issue_diff(&IssueDiff::between(&previous, &current));
+        previous = current;
+    }
+}
+
+fn registry_path_values() -> (Option<String>, Option<String>) {
+    (
+        registry::RegistryHelper::read_user_path_raw().ok(),
+        registry::RegistryHelper::read_system_path_raw().ok(),
+    )
+}
+
+/// Exits with `code` unless it is 0, after flushing what the scan printed,
+/// whatever the output format.
+fn exit_with_code(code: i32) -> Result<()> {
+    if code != 0 {
+        io::stdout().flush()?;
+        std::process::exit(code);
+    }
+    Ok(())
+}
+
+/// Audits both scopes silently and exits with the worst severity found.
+fn handle_check(verbose: bool) -> Result<()> {
+    let report = report::audit_all()?;
+    if verbose {
+        let count = |level| report.issues().filter(|i| i.level == level).count();
         println!(
-            "{}",
-            "Running in DRY RUN mode - no changes will be made"
-                .yellow()
-                .bold()
+            "{} critical, {} warnings, health {}%",
+            count(IssueLevel::Critical),
+            count(IssueLevel::Warning),
+            report.health_score()
         );
     }
-    let fixer = PathFixer::new()?;
-    if delicate && !dry_run {
+    io::stdout().flush()?;
+    std::process::exit(report.check_exit_code());
+}
+
+fn configure_scanner(scanner: PathScanner, args: &ScanArgs) -> PathScanner {
+    let validity = ValidityPolicy {
+        require_exists: !args.relax.contains(&ValidityRule::Exists),
+        require_absolute: !args.relax.contains(&ValidityRule::Absolute),
+        require_quotes_for_spaces: !args.relax.contains(&ValidityRule::Quotes),
+    };
+    let scanner = scanner
+        .with_excluded_drives(args.exclude_drive.iter().copied())
+        .with_excluded_patterns(args.exclude.iter().cloned())
+        .with_validity_policy(validity)
+        .with_offline(args.offline)
+        .with_progress(show_progress(args));
+    let scanner = if args.parallel {
+        scanner.with_parallel(true)
+    } else {
+        scanner
+    };
+    match existence_timeout(args.timeout_ms) {
+        Some(timeout) => scanner.with_timeout(timeout),
+        None => scanner,
+    }
+}
+
+/// The progress bar is shown for text output when asked for with
+/// `--progress` or when stderr is a terminal; machine-readable formats
+/// never get one.
+fn show_progress(args: &ScanArgs) -> bool {
+    args.format == OutputFormat::Text && (args.progress || io::stderr().is_terminal())
+}
+
+/// `--timeout` in milliseconds; `0` turns the timeout off.
+fn existence_timeout(ms: u64) -> Option<Duration> {
+    (ms > 0).then(|| Duration::from_millis(ms))
+}
+
+/// With `user_only` the `clean` projection is skipped, since planning
+/// it reads SYSTEM PATH; `--offline` skips the whole remediation
+/// projection, which checks entries on disk.
+fn print_scan_report(results: &ScanResults, args: &ScanArgs, user_only: bool) {
+    let clean_plan = if user_only || args.offline {
+        None
+    } else {
+        SystemAnalyzer::new()
+            .map(|analyzer| match existence_timeout(args.timeout_ms) {
+                Some(timeout) => analyzer.with_timeout(timeout),
+                None => analyzer,
+            })
+            .and_then(|analyzer| analyzer.analyze())
+            .and_then(|analysis| PathMigrator::new()?.plan_migration(&analysis, true, false))
+            .ok()
+    };
+    let projection = if args.offline {
+        None
+    } else {
+        fixer::project_remediation(results, clean_plan.as_ref()).ok()
+    };
+    let redacted;
+    let results = if args.redact {
+        redacted = results.redacted(args.hash_dirs);
+        &redacted
+    } else {
+        results
+    };
+    let min_level = IssueLevel::from(args.level);
+    let shown = results.with_min_level(min_level);
+    let sort = args.sort.map(IssueSort::from);
+    if args.group_duplicates {
+        ConsoleFormatter::print_scan_results_grouped(&shown, args.verbose, sort);
+    } else if args.verbose || sort.is_some() {
+        ConsoleFormatter::print_scan_results(&shown, args.verbose, sort);
+    } else {
+        ConsoleFormatter::print_path_table(&shown);
+        println!();
+    }
+    ConsoleFormatter::print_scan_summary(results);
+    ConsoleFormatter::print_hidden_issue_count(
+        results.issues.len() - shown.issues.len(),
+        min_level,
+    );
+    if let Some(projection) = projection {
+        ConsoleFormatter::print_remediation_projection(&projection);
+    }
+    if args.audit {
+        ConsoleFormatter::print_scan_audit(results);
+    }
+}
+
+fn handle_fix(args: FixArgs, user_only: bool) -> Result<()> {
+    let dry_run = args.dry_run;
+    println!("{}", tr(MessageKey::FixTitle).bold().cyan());
+    println!();
+    if dry_run {
+        println!("{}", tr(MessageKey::DryRunNotice).yellow().bold());
+    }
+    let location = if args.system {
+        PathLocation::System
+    } else {
+        PathLocation::User
+    };
+    if args.system && !dry_run && !registry::RegistryHelper::can_write_system_path() {
+        ConsoleFormatter::print_fix_requires_admin();
+    }
+    let fixer = new_fixer(user_only && !args.system)?.with_options(FixOptions {
+        keep: KeepOverrides {
+            paths: args.keep.keep_path,
+            indices: args.keep.keep_index,
+        },
+        excluded_drives: args.exclude_drive,
+        canonicalize_case: args.canonicalize_case,
+        excluded_patterns: args.exclude,
+        existence_timeout: existence_timeout(args.timeout_ms),
+        ..FixOptions::default()
+    });
+    if args.delicate && !dry_run {
         println!(
             "{}",
             "Delicate mode: You will be asked to confirm each change.".cyan()
         );
         println!();
-        if !ask_confirmation("Proceed with fixing USER PATH?") {
-            println!("{}", "Operation cancelled.".yellow());
+        if !fixer.confirm(&format!("Proceed with fixing {} PATH?", location)) {
+            println!("{}", tr(MessageKey::OperationCancelled).yellow());
             return Ok(());
         }
     }
-    let results = fixer.fix_user_path(dry_run)?;
+    let results = match location {
+        PathLocation::System => fixer.fix_system_path(dry_run, args.force)?,
+        PathLocation::User => fixer.fix_user_path(dry_run, args.force)?,
+    };
     ConsoleFormatter::print_fix_results(&results);
+    if !dry_run && results.changed && results.system_path_error.is_none() {
+        run_hooks(&args.hooks)?;
+    }
     Ok(())
 }
 
-fn handle_backup() -> Result<()> {
-    println!("{}", "spath - Create Backup".bold().cyan());
+fn run_hooks(hooks: &HookArgs) -> Result<()> {
+    if hooks.broadcast {
+        hooks::broadcast_environment_change()?;
+        println!(
+            "{}",
+            format!(
+                "{} Notified running applications of the PATH change",
+                glyphs().check
+            )
+            .green()
+        );
+    }
+    if let Some(ref command) = hooks.post_hook {
+        hooks::run_post_hook(command)?;
+        println!(
+            "{} Post-hook completed: {}",
+            glyphs().check.green(),
+            command
+        );
+    }
+    Ok(())
+}
+
+/// Creates a fixer; with `user_only` its backups never read SYSTEM PATH.
+fn new_fixer(user_only: bool) -> Result<PathFixer> {
+    Ok(PathFixer::with_backup_manager(new_backup_manager(
+        user_only,
+    )?))
+}
+
+fn new_backup_manager(user_only: bool) -> Result<BackupManager> {
+    let manager = BackupManager::new()?;
+    Ok(if user_only {
+        manager.without_system_scope()
+    } else {
+        manager
+    })
+}
+
+fn handle_backup(user_only: bool, max_backups: Option<usize>) -> Result<()> {
+    println!("{}", tr(MessageKey::BackupTitle).bold().cyan());
     println!();
-    let fixer = PathFixer::new()?;
+    let mut manager = new_backup_manager(user_only)?;
+    if let Some(max_backups) = max_backups {
+        manager = manager.with_max_backups(max_backups);
+    }
+    let fixer = PathFixer::with_backup_manager(manager);
     let result = fixer.create_backup()?;
     ConsoleFormatter::print_backup_result(&result);
     Ok(())
 }
 
 fn handle_list_backups() -> Result<()> {
-    println!("{}", "spath - Available Backups".bold().cyan());
+    println!("{}", tr(MessageKey::ListBackupsTitle).bold().cyan());
     let fixer = PathFixer::new()?;
     let backups = fixer.list_backups()?;
     if backups.is_empty() {
         println!("{}", "No backups found.".yellow());
     } else {
         println!("Found {} backup(s):", backups.len());
-        for backup in backups {
-            println!("  {}", backup.display());
+        let width = backups.len().saturating_sub(1).to_string().len();
+        for (index, backup) in backups.iter().enumerate() {
+            println!(
+                "  {:>width$}  {}",
+                index.to_string().cyan(),
+                backup.display()
+            );
         }
     }
     Ok(())
 }
 
-fn handle_restore(backup_file: &str, delicate: bool) -> Result<()> {
-    println!("{}", "spath - Restore Backup".bold().cyan());
+fn handle_apply(hooks: &HookArgs) -> Result<()> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    let fixer = PathFixer::new()?;
+    let result = fixer.apply_path_value(&input, &RegistrySource)?;
+    ConsoleFormatter::print_apply_result(&result);
+    run_hooks(hooks)
+}
+
+fn handle_restore(
+    backup_file: &str,
+    delicate: bool,
+    dry_run: bool,
+    user_only: bool,
+    system: bool,
+) -> Result<()> {
+    println!("{}", tr(MessageKey::RestoreTitle).bold().cyan());
     println!();
     let fixer = PathFixer::new()?;
-    let backup_path = std::path::PathBuf::from(backup_file);
+    let backup_path = fixer.resolve_backup(backup_file)?;
+    println!("{} {}", "Using backup:".cyan(), backup_path.display());
+    println!();
+    if dry_run {
+        println!("{}", tr(MessageKey::DryRunNotice).yellow().bold());
+        println!();
+        let preview = if system {
+            fixer.preview_system_restore(&backup_path)?
+        } else {
+            fixer.preview_restore(&backup_path)?
+        };
+        ConsoleFormatter::print_restore_preview(&preview);
+        return Ok(());
+    }
     if delicate {
         println!("{}", "Delicate mode: Confirm restore operation.".cyan());
         println!("This will replace your current PATH with the backup.");
-        if !ask_confirmation(&format!("Restore from {}?", backup_path.display())) {
-            println!("{}", "Operation cancelled.".yellow());
+        if !fixer.confirm(&format!("Restore from {}?", backup_path.display())) {
+            println!("{}", tr(MessageKey::OperationCancelled).yellow());
             return Ok(());
         }
         println!();
     }
-    let result = fixer.restore_backup(&backup_path)?;
+    let result = if system {
+        fixer.restore_system_backup(&backup_path)?
+    } else {
+        fixer.restore_backup(&backup_path, user_only)?
+    };
     ConsoleFormatter::print_restore_result(&result);
     Ok(())
 }
 
-fn handle_analyze() -> Result<()> {
-    println!("{}", "spath - System PATH Analyzer".bold().cyan());
-    let analyzer = SystemAnalyzer::new()?;
+fn handle_diff(backup_file: &Path, format: DiffFormat) -> Result<()> {
+    let diff = PathFixer::new()?.diff_backup(backup_file)?;
+    if format == DiffFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+        return Ok(());
+    }
+    println!("{}", tr(MessageKey::DiffTitle).bold().cyan());
+    println!();
+    ConsoleFormatter::print_path_diff(&diff, backup_file);
+    Ok(())
+}
+
+fn handle_repair_backups(quarantine: bool) -> Result<()> {
+    println!("{}", tr(MessageKey::RepairBackupsTitle).bold().cyan());
+    println!();
+    let fixer = PathFixer::new()?;
+    let report = fixer.repair_backups(quarantine)?;
+    ConsoleFormatter::print_repair_report(&report);
+    Ok(())
+}
+
+fn handle_prune_backups(keep: Option<usize>) -> Result<()> {
+    println!("{}", tr(MessageKey::PruneBackupsTitle).bold().cyan());
+    println!();
+    let manager = BackupManager::new()?;
+    let keep = keep.unwrap_or(manager.max_backups()).max(1);
+    let removed = manager.cleanup_to(keep)?;
+    ConsoleFormatter::print_prune_result(&removed, keep);
+    Ok(())
+}
+
+fn handle_stats(last: Option<usize>, format: DiffFormat) -> Result<()> {
+    let history = stats::health_history(&BackupManager::new()?, last)?;
+    if format == DiffFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&history)?);
+        return Ok(());
+    }
+    println!("{}", tr(MessageKey::StatsTitle).bold().cyan());
+    println!();
+    ConsoleFormatter::print_health_history(&history);
+    Ok(())
+}
+
+fn handle_analyze(
+    runtime_versions: bool,
+    format: DiffFormat,
+    offline: bool,
+    timeout_ms: u64,
+) -> Result<()> {
+    let analyzer = SystemAnalyzer::new()?.with_offline(offline);
+    let analyzer = match existence_timeout(timeout_ms) {
+        Some(timeout) => analyzer.with_timeout(timeout),
+        None => analyzer,
+    };
     let results = analyzer.analyze()?;
+    if format == DiffFormat::Json {
+        println!("{}", JsonFormatter::render_analysis(&results)?);
+        return Ok(());
+    }
+    println!("{}", tr(MessageKey::AnalyzeTitle).bold().cyan());
     ConsoleFormatter::print_analysis_results(&results);
+    let redundant = analyzer::detect_redundant_user_entries(&results);
+    ConsoleFormatter::print_redundant_user_entries(&redundant);
+    let paths: Vec<String> = results.entries.iter().map(|e| e.path.clone()).collect();
+    // Shadowing is found by looking for executables on disk.
+    if !offline {
+        let shadowing = analyzer::detect_runtime_shadowing(&paths, runtime_versions);
+        ConsoleFormatter::print_runtime_shadowing(&shadowing);
+    }
+    let conflicts = analyzer::detect_architecture_conflicts(&paths);
+    ConsoleFormatter::print_architecture_conflicts(&conflicts);
+    Ok(())
+}
+
+fn handle_effective(vs_registry: bool) -> Result<()> {
+    println!("{}", tr(MessageKey::EffectiveTitle).bold().cyan());
+    println!();
+    let registry = effective::registry_effective_path()?;
+    if !vs_registry {
+        ConsoleFormatter::print_effective_path(&registry);
+        return Ok(());
+    }
+    let process_path = std::env::var("PATH").unwrap_or_default();
+    let process = registry::RegistryHelper::parse_path_string(&process_path);
+    let diff = effective::compare_with_process(&process, &registry);
+    ConsoleFormatter::print_effective_diff(&diff);
     Ok(())
 }
 
-fn handle_clean(system: bool, dry_run: bool, delicate: bool) -> Result<()> {
-    println!("{}", "spath - PATH Cleanup".bold().cyan());
+fn handle_resolve(name: &str, json: bool) -> Result<()> {
+    let path = effective::registry_effective_path()?;
+    let resolution = effective::resolve_executable(name, &path, &effective::path_extensions());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&resolution)?);
+        return Ok(());
+    }
+    println!("{}", tr(MessageKey::ResolveTitle).bold().cyan());
     println!();
+    ConsoleFormatter::print_resolution(&resolution);
+    Ok(())
+}
+
+fn handle_export(
+    output: &Path,
+    system: bool,
+    user: bool,
+    both: bool,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    println!("{}", tr(MessageKey::ExportTitle).bold().cyan());
+    println!();
+    let system_path = if system || both {
+        Some(registry::RegistryHelper::read_system_path_raw()?)
+    } else {
+        None
+    };
+    let user_path = if user || both || !system {
+        Some(registry::RegistryHelper::read_user_path_raw()?)
+    } else {
+        None
+    };
+    let snapshot = PathSnapshot::from_values(system_path.as_deref(), user_path.as_deref());
     if dry_run {
-        println!(
-            "{}",
-            "Running in DRY RUN mode - no changes will be made"
-                .yellow()
-                .bold()
-        );
+        println!("{}", tr(MessageKey::DryRunNotice).yellow().bold());
+        println!();
+        ConsoleFormatter::print_export_preview(&snapshot, output);
+        return Ok(());
+    }
+    snapshot.write_to(output, force)?;
+    println!(
+        "{} {}",
+        "PATH exported to:".green().bold(),
+        output.display()
+    );
+    Ok(())
+}
+
+fn handle_import(
+    file: &Path,
+    merge: bool,
+    dry_run: bool,
+    delicate: bool,
+    hooks: &HookArgs,
+) -> Result<()> {
+    println!("{}", tr(MessageKey::ImportTitle).bold().cyan());
+    println!();
+    let snapshot = PathSnapshot::read_from(file)?;
+    let mode = if merge {
+        ImportMode::Merge
+    } else {
+        ImportMode::Replace
+    };
+    let current = registry::RegistryHelper::read_user_path_raw()?;
+    let plan = snapshot.plan_import(&current, mode)?;
+    if dry_run {
+        println!("{}", tr(MessageKey::DryRunNotice).yellow().bold());
+        println!();
+    }
+    ConsoleFormatter::print_import_plan(&plan);
+    if dry_run {
+        return Ok(());
+    }
+    if delicate
+        && !InteractivePrompter.confirm(&format!("Import USER PATH from {}?", file.display()))
+    {
+        println!("{}", tr(MessageKey::OperationCancelled).yellow());
+        return Ok(());
+    }
+    let result = PathFixer::new()?.apply_path_value(&plan.new_path, &RegistrySource)?;
+    println!();
+    ConsoleFormatter::print_apply_result(&result);
+    run_hooks(hooks)
+}
+
+fn handle_remove(
+    path: &str,
+    system: bool,
+    dry_run: bool,
+    all: bool,
+    force: bool,
+    user_only: bool,
+) -> Result<()> {
+    println!("{}", tr(MessageKey::RemoveTitle).bold().cyan());
+    println!();
+    let (location, current) = if system {
+        (
+            PathLocation::System,
+            registry::RegistryHelper::read_system_path_raw()?,
+        )
+    } else {
+        (
+            PathLocation::User,
+            registry::RegistryHelper::read_user_path_raw()?,
+        )
+    };
+    let plan = RemovalPlan::new(&current, path, location, all)?;
+    if dry_run {
+        println!("{}", tr(MessageKey::DryRunNotice).yellow().bold());
+        println!();
+    }
+    ConsoleFormatter::print_removal_plan(&plan, dry_run);
+    if dry_run {
+        return Ok(());
+    }
+    let backup = new_fixer(user_only && !system)?.apply_removal(&plan, force)?;
+    println!();
+    ConsoleFormatter::print_backup_result(&backup);
+    Ok(())
+}
+
+fn handle_reorder(commands: &[String], dry_run: bool, force: bool) -> Result<()> {
+    println!("{}", tr(MessageKey::ReorderTitle).bold().cyan());
+    println!();
+    let paths = registry::RegistryHelper::parse_path_string(
+        &registry::RegistryHelper::read_user_path_raw()?,
+    );
+    ConsoleFormatter::print_indexed_paths(&paths);
+    let ops = if commands.is_empty() {
+        read_reorder_commands()?
+    } else {
+        ReorderOp::parse_all(commands)?
+    };
+    if ops.is_empty() {
+        return Ok(());
+    }
+    let migrator = if force {
+        PathMigrator::new()?.with_prompter(Box::new(AutoConfirmPrompter))
+    } else {
+        PathMigrator::new()?
+    };
+    let (plan, reordered) = migrator.plan_reorder(&paths, &ops)?;
+    println!();
+    if dry_run {
+        println!("{}", tr(MessageKey::DryRunNotice).yellow().bold());
+        println!();
+    }
+    ConsoleFormatter::print_reorder_plan(&plan, &reordered);
+    if dry_run || plan.actions.is_empty() {
+        return Ok(());
+    }
+    if !migrator.confirm("Write the reordered USER PATH?") {
+        println!("{}", tr(MessageKey::OperationCancelled).yellow());
+        return Ok(());
+    }
+    let result = migrator.execute_reorder(&plan, &reordered, force)?;
+    println!();
+    ConsoleFormatter::print_migration_result(&result);
+    Ok(())
+}
+
+/// Reads `move`/`swap` commands from stdin, one per line, until a blank line.
+fn read_reorder_commands() -> Result<Vec<ReorderOp>> {
+    println!();
+    println!("Enter commands such as 'move 5 1' or 'swap 3 7'; empty line to finish.");
+    let mut ops = Vec::new();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line)? == 0 || line.trim().is_empty() {
+            return Ok(ops);
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match ReorderOp::parse_all(&tokens) {
+            Ok(parsed) => ops.extend(parsed),
+            Err(e) => println!("{} {}", "Error:".red(), e),
+        }
+    }
+}
+
+fn handle_clean(
+    system: bool,
+    dry_run: bool,
+    delicate: bool,
+    force: bool,
+    json: bool,
+    exclude: &[String],
+    hooks: &HookArgs,
+) -> Result<()> {
+    if json {
+        let plan = plan_clean(&PathMigrator::new()?, system, exclude)?;
+        println!("{}", serde_json::to_string_pretty(&plan)?);
+        return Ok(());
+    }
+    println!("{}", tr(MessageKey::CleanTitle).bold().cyan());
+    println!();
+    if dry_run {
+        println!("{}", tr(MessageKey::DryRunNotice).yellow().bold());
         println!();
     }
-    let analyzer = SystemAnalyzer::new()?;
-    let analysis = analyzer.analyze()?;
     let migrator = PathMigrator::new()?;
-    let plan = migrator.plan_migration(&analysis, true, system)?;
+    let mut plan = plan_clean(&migrator, system, exclude)?;
     ConsoleFormatter::print_migration_plan(&plan, dry_run);
     if !dry_run && !plan.actions.is_empty() {
         println!();
         if delicate {
-            println!("{}", "Delicate mode: Confirm the cleanup operation.".cyan());
-            if !ask_confirmation("Apply these changes?") {
-                println!("{}", "Operation cancelled.".yellow());
+            println!("{}", "Delicate mode: Confirm each action.".cyan());
+            plan = migrator.confirm_actions(&plan, |action| {
+                format!(
+                    "{} [{}] {}?",
+                    ConsoleFormatter::action_label(&action.action_type),
+                    action.from_location,
+                    action.path
+                )
+            });
+            if plan.actions.is_empty() {
+                println!("{}", tr(MessageKey::OperationCancelled).yellow());
                 return Ok(());
             }
         }
         if plan.requires_admin {
             ConsoleFormatter::print_migration_requires_admin();
         }
-        let result = migrator.execute_migration(&plan, dry_run)?;
+        let result = migrator.execute_migration(&plan, dry_run, force)?;
         ConsoleFormatter::print_migration_result(&result);
-        println!("{}", "Cleanup completed.".green().bold());
+        if result.user_path_updated || result.system_path_updated {
+            run_hooks(hooks)?;
+        }
+        println!("{}", tr(MessageKey::CleanupCompleted).green().bold());
         println!(
             "{}",
             "  Note: You may need to restart applications for changes to take effect.".yellow()
@@ -264,8 +1417,55 @@ fn handle_clean(system: bool, dry_run: bool, delicate: bool) -> Result<()> {
     Ok(())
 }
 
-fn handle_verify(system: bool) -> Result<()> {
-    println!("{}", "spath - Security Verification".bold().cyan());
+/// Plans `clean`, dropping actions on entries that match an exclude pattern.
+fn plan_clean(migrator: &PathMigrator, system: bool, exclude: &[String]) -> Result<MigrationPlan> {
+    let analysis = SystemAnalyzer::new()?.analyze()?;
+    let plan = migrator.plan_migration(&analysis, true, system)?;
+    if exclude.is_empty() {
+        return Ok(plan);
+    }
+    Ok(migrator.select_actions(&plan, |action| {
+        !exclude
+            .iter()
+            .any(|pattern| utils::matches_exclude_pattern(&action.path, pattern))
+    }))
+}
+
+/// Runs the enabled lint rules over USER or SYSTEM PATH and exits with the
+/// worst severity found, like `scan`.
+fn handle_lint(system: bool, disabled: &[String]) -> Result<()> {
+    let linter = rules::Linter::new().with_disabled(disabled)?;
+    println!("{}", tr(MessageKey::LintTitle).bold().cyan());
+    println!();
+    let paths = if system {
+        registry::RegistryHelper::read_system_path()?
+    } else {
+        registry::RegistryHelper::read_user_path()?
+    };
+    let location = if system {
+        PathLocation::System
+    } else {
+        PathLocation::User
+    };
+    let counts = PathEntry::path_counts(&paths);
+    let entries: Vec<PathEntry> = paths
+        .iter()
+        .enumerate()
+        .map(|(index, path)| {
+            let mut entry = PathEntry::with_path_counts(path.clone(), index, location, &counts);
+            entry.check_acl();
+            entry
+        })
+        .collect();
+    let findings = linter.lint(&entries);
+    ConsoleFormatter::print_lint_findings(&findings, &linter.rule_names());
+    exit_with_code(report::exit_code(
+        findings.iter().map(|finding| finding.issue.level).max(),
+    ))
+}
+
+fn handle_verify(system: bool, level: VerifyLevel) -> Result<()> {
+    println!("{}", tr(MessageKey::VerifyTitle).bold().cyan());
     if system {
         println!("{}", "Verifying SYSTEM PATH security...".yellow());
     } else {
@@ -273,31 +1473,41 @@ fn handle_verify(system: bool) -> Result<()> {
     }
     let scanner = PathScanner::new(system)?;
     let results = scanner.scan()?;
-    let critical_paths: Vec<&str> = results
+    let issues: Vec<&PathIssue> = results
         .issues
         .iter()
-        .filter(|i| matches!(i.level, IssueLevel::Critical))
-        .map(|i| i.path.as_str())
+        .filter(|i| level.includes(i.level))
         .collect();
-    if critical_paths.is_empty() {
-        println!("{}", "✓ No critical security issues found!".green().bold());
+    let label = match level {
+        VerifyLevel::Critical => "critical",
+        VerifyLevel::Warning => "warning",
+        VerifyLevel::All => "critical or warning",
+    };
+    if issues.is_empty() {
+        println!(
+            "{}",
+            format!("{} No {} security issues found!", glyphs().check, label)
+                .green()
+                .bold()
+        );
         return Ok(());
     }
     println!(
         "{}",
         format!(
-            "Found {} critical issue(s). Verifying exploitability...",
-            critical_paths.len()
+            "Found {} {} issue(s). Verifying exploitability...",
+            issues.len(),
+            label
         )
         .yellow()
     );
-    let (results, summary) = security::exploits::verify_paths(&critical_paths);
+    let (results, summary) =
+        security::exploits::verify_issues(&issues, security::exploits::is_dir_writable);
     ConsoleFormatter::print_verification_results(&results, &summary);
     Ok(())
 }
 
-fn handle_visualize(tree: bool, system: bool, user: bool, no_color: bool) -> Result<()> {
-    let use_color = !no_color && atty::is(atty::Stream::Stdout);
+fn handle_visualize(tree: bool, system: bool, user: bool, use_color: bool) -> Result<()> {
     let (system_paths, user_paths) = get_paths_for_visualization(system, user);
     if system && !user {
         print_path_visualization("SYSTEM PATH", &system_paths, tree, use_color);
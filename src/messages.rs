@@ -0,0 +1,159 @@
+//! Message catalog for user-facing console output.
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Output language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Ru,
+}
+
+impl Locale {
+    /// Parses a language tag such as `ru`, `ru_RU.UTF-8` or `en-US`.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let language = tag
+            .split(['_', '-', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        match language.as_str() {
+            "en" | "c" | "posix" => Some(Locale::En),
+            "ru" => Some(Locale::Ru),
+            _ => None,
+        }
+    }
+    /// Reads the locale from `LC_ALL`, `LC_MESSAGES` or `LANG`, falling back to English.
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKey {
+    ScanTitle,
+    FixTitle,
+    BackupTitle,
+    ListBackupsTitle,
+    RestoreTitle,
+    RepairBackupsTitle,
+    PruneBackupsTitle,
+    AnalyzeTitle,
+    CleanTitle,
+    VerifyTitle,
+    EffectiveTitle,
+    ExportTitle,
+    ImportTitle,
+    ResolveTitle,
+    DiffTitle,
+    StatsTitle,
+    RemoveTitle,
+    ReorderTitle,
+    LintTitle,
+    DryRunNotice,
+    OperationCancelled,
+    CleanupCompleted,
+    NoIssuesFound,
+    SummaryHeading,
+    TotalPaths,
+    CriticalIssues,
+    Warnings,
+    Info,
+}
+
+static CURRENT_LOCALE: AtomicU8 = AtomicU8::new(0);
+
+/// Sets the locale used by [`tr`].
+pub fn set_locale(locale: Locale) {
+    CURRENT_LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn current_locale() -> Locale {
+    match CURRENT_LOCALE.load(Ordering::Relaxed) {
+        1 => Locale::Ru,
+        _ => Locale::En,
+    }
+}
+
+/// Translates `key` into the current locale.
+pub fn tr(key: MessageKey) -> &'static str {
+    message(key, current_locale())
+}
+
+pub fn message(key: MessageKey, locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => english(key),
+        Locale::Ru => russian(key),
+    }
+}
+
+fn english(key: MessageKey) -> &'static str {
+    match key {
+        MessageKey::ScanTitle => "spath - Windows PATH Security Scanner",
+        MessageKey::FixTitle => "spath - PATH Fixer",
+        MessageKey::BackupTitle => "spath - Create Backup",
+        MessageKey::ListBackupsTitle => "spath - Available Backups",
+        MessageKey::RestoreTitle => "spath - Restore Backup",
+        MessageKey::RepairBackupsTitle => "spath - Repair Backups",
+        MessageKey::PruneBackupsTitle => "spath - Prune Backups",
+        MessageKey::AnalyzeTitle => "spath - System PATH Analyzer",
+        MessageKey::CleanTitle => "spath - PATH Cleanup",
+        MessageKey::VerifyTitle => "spath - Security Verification",
+        MessageKey::EffectiveTitle => "spath - Effective PATH",
+        MessageKey::ExportTitle => "spath - Export PATH",
+        MessageKey::ImportTitle => "spath - Import PATH",
+        MessageKey::ResolveTitle => "spath - Resolve Executable",
+        MessageKey::DiffTitle => "spath - Diff Against Backup",
+        MessageKey::StatsTitle => "spath - PATH Health History",
+        MessageKey::RemoveTitle => "spath - Remove PATH Entry",
+        MessageKey::ReorderTitle => "spath - Reorder USER PATH",
+        MessageKey::LintTitle => "spath - PATH Lint",
+        MessageKey::DryRunNotice => "Running in DRY RUN mode - no changes will be made",
+        MessageKey::OperationCancelled => "Operation cancelled.",
+        MessageKey::CleanupCompleted => "Cleanup completed.",
+        MessageKey::NoIssuesFound => "No security issues found.",
+        MessageKey::SummaryHeading => "Summary:",
+        MessageKey::TotalPaths => "Total paths",
+        MessageKey::CriticalIssues => "Critical issues",
+        MessageKey::Warnings => "Warnings",
+        MessageKey::Info => "Info",
+    }
+}
+
+fn russian(key: MessageKey) -> &'static str {
+    match key {
+        MessageKey::ScanTitle => "spath - сканер безопасности PATH для Windows",
+        MessageKey::FixTitle => "spath - исправление PATH",
+        MessageKey::BackupTitle => "spath - создание резервной копии",
+        MessageKey::ListBackupsTitle => "spath - доступные резервные копии",
+        MessageKey::RestoreTitle => "spath - восстановление из резервной копии",
+        MessageKey::RepairBackupsTitle => "spath - починка резервных копий",
+        MessageKey::PruneBackupsTitle => "spath - удаление старых резервных копий",
+        MessageKey::AnalyzeTitle => "spath - анализ системного PATH",
+        MessageKey::CleanTitle => "spath - очистка PATH",
+        MessageKey::VerifyTitle => "spath - проверка безопасности",
+        MessageKey::EffectiveTitle => "spath - действующий PATH",
+        MessageKey::ExportTitle => "spath - экспорт PATH",
+        MessageKey::ImportTitle => "spath - импорт PATH",
+        MessageKey::ResolveTitle => "spath - поиск исполняемого файла",
+        MessageKey::DiffTitle => "spath - сравнение с резервной копией",
+        MessageKey::StatsTitle => "spath - история состояния PATH",
+        MessageKey::RemoveTitle => "spath - удаление записи PATH",
+        MessageKey::ReorderTitle => "spath - порядок USER PATH",
+        MessageKey::LintTitle => "spath - проверка PATH по правилам",
+        MessageKey::DryRunNotice => "Пробный запуск - изменения не будут внесены",
+        MessageKey::OperationCancelled => "Операция отменена.",
+        MessageKey::CleanupCompleted => "Очистка завершена.",
+        MessageKey::NoIssuesFound => "Проблем безопасности не найдено.",
+        MessageKey::SummaryHeading => "Итого:",
+        MessageKey::TotalPaths => "Всего путей",
+        MessageKey::CriticalIssues => "Критических проблем",
+        MessageKey::Warnings => "Предупреждений",
+        MessageKey::Info => "Информационных",
+    }
+}
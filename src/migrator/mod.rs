@@ -2,25 +2,91 @@
 use crate::analyzer::AnalysisResults;
 use crate::backup::BackupManager;
 use crate::models::{PathCategory, PathEntry, PathLocation};
+use crate::prompt::{InteractivePrompter, Prompter};
 use crate::registry::RegistryHelper;
-use crate::utils::quote_if_needed;
-use anyhow::Result;
+use crate::utils::{
+    is_protected_path, normalize_for_comparison, protected_violations, quote_if_needed,
+};
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MigrationAction {
     pub action_type: ActionType,
     pub path: String,
     pub from_location: PathLocation,
+    pub reason_code: ReasonCode,
     pub reason: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ActionType {
     RemoveDuplicate,
     MoveToUser,
     AddQuotes,
+    /// Entry moved from one USER PATH position to another by `reorder`.
+    ReorderAction {
+        from: usize,
+        to: usize,
+    },
+}
+
+/// One `reorder` command. Indices are the 0-based positions `reorder` prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReorderOp {
+    /// Moves the entry at `from` so it ends up at `to`.
+    Move { from: usize, to: usize },
+    /// Exchanges two entries.
+    Swap { first: usize, second: usize },
+}
+
+impl ReorderOp {
+    /// Parses commands such as `move 5 1 swap 3 7`, three tokens each.
+    pub fn parse_all<S: AsRef<str>>(tokens: &[S]) -> Result<Vec<Self>> {
+        let tokens: Vec<&str> = tokens.iter().map(|t| t.as_ref()).collect();
+        tokens
+            .chunks(3)
+            .map(|command| {
+                let [verb, a, b] = command else {
+                    bail!(
+                        "Incomplete command '{}': expected e.g. 'move 5 1' or 'swap 3 7'",
+                        command.join(" ")
+                    );
+                };
+                let parse = |index: &str| {
+                    index
+                        .parse::<usize>()
+                        .map_err(|_| anyhow!("'{}' is not a PATH index", index))
+                };
+                let (a, b) = (parse(a)?, parse(b)?);
+                match verb.to_ascii_lowercase().as_str() {
+                    "move" => Ok(Self::Move { from: a, to: b }),
+                    "swap" => Ok(Self::Swap {
+                        first: a,
+                        second: b,
+                    }),
+                    other => bail!("Unknown reorder command '{}' (use 'move' or 'swap')", other),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Stable, machine-readable reason for a planned action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasonCode {
+    /// Entry is present in both USER and SYSTEM PATH.
+    DuplicateAcrossScopes,
+    /// User-specific entry is stored in SYSTEM PATH.
+    UserPathInSystem,
+    /// Entry contains spaces but is not quoted.
+    UnquotedSpaces,
+    /// Entry order was changed on request.
+    UserReorder,
 }
 
 #[derive(Debug)]
@@ -31,21 +97,35 @@ pub struct MigrationResult {
     pub system_path_error: Option<String>,
 }
 
+#[derive(Serialize)]
 pub struct MigrationPlan {
     pub actions: Vec<MigrationAction>,
     pub requires_admin: bool,
+    /// Protected system entries the plan would remove from SYSTEM PATH.
+    pub protected_violations: Vec<String>,
 }
 
 pub struct PathMigrator {
     backup_manager: BackupManager,
+    prompter: Box<dyn Prompter>,
 }
 
 impl PathMigrator {
     pub fn new() -> Result<Self> {
         Ok(Self {
             backup_manager: BackupManager::new()?,
+            prompter: Box::new(InteractivePrompter),
         })
     }
+    /// Replaces the stdin prompt used for delicate-mode confirmations.
+    pub fn with_prompter(mut self, prompter: Box<dyn Prompter>) -> Self {
+        self.prompter = prompter;
+        self
+    }
+    /// Asks the configured prompter to confirm `message`.
+    pub fn confirm(&self, message: &str) -> bool {
+        self.prompter.confirm(message)
+    }
     pub fn plan_migration(
         &self,
         analysis: &AnalysisResults,
@@ -59,17 +139,84 @@ impl PathMigrator {
         if move_user_paths {
             actions.extend(self.plan_user_path_migration(&analysis.entries)?);
         }
+        let planned: HashSet<(PathLocation, String)> = actions
+            .iter()
+            .map(|a| (a.from_location, normalize_for_comparison(&a.path)))
+            .collect();
+        actions.extend(
+            self.plan_quote_fixes(&analysis.entries)
+                .into_iter()
+                .filter(|a| {
+                    !planned.contains(&(a.from_location, normalize_for_comparison(&a.path)))
+                }),
+        );
         let requires_admin = move_user_paths || self.has_system_changes(&actions);
+        let protected_violations = self.find_protected_violations(&actions);
         Ok(MigrationPlan {
             actions,
             requires_admin,
+            protected_violations,
         })
     }
+    /// Plans quoting every SYSTEM or USER entry that
+    /// [needs quotes](PathEntry::needs_quotes), as `analyze` recommends.
+    /// The entry keeps its position when the plan is executed.
+    pub fn plan_quote_fixes(&self, entries: &[PathEntry]) -> Vec<MigrationAction> {
+        entries
+            .iter()
+            .filter(|entry| entry.needs_quotes())
+            .map(|entry| MigrationAction {
+                action_type: ActionType::AddQuotes,
+                path: entry.path.clone(),
+                from_location: entry.location,
+                reason_code: ReasonCode::UnquotedSpaces,
+                reason: "Path contains spaces and should be quoted".to_string(),
+            })
+            .collect()
+    }
+    /// Keeps only the actions of `plan` for which `keep` returns true, e.g.
+    /// the ones confirmed one by one in delicate mode.
+    pub fn select_actions(
+        &self,
+        plan: &MigrationPlan,
+        mut keep: impl FnMut(&MigrationAction) -> bool,
+    ) -> MigrationPlan {
+        let actions: Vec<MigrationAction> =
+            plan.actions.iter().filter(|a| keep(a)).cloned().collect();
+        MigrationPlan {
+            requires_admin: self.has_system_changes(&actions),
+            protected_violations: self.find_protected_violations(&actions),
+            actions,
+        }
+    }
+    /// Asks the prompter about each action, described by `describe`, and
+    /// keeps the confirmed ones.
+    pub fn confirm_actions(
+        &self,
+        plan: &MigrationPlan,
+        describe: impl Fn(&MigrationAction) -> String,
+    ) -> MigrationPlan {
+        self.select_actions(plan, |action| self.prompter.confirm(&describe(action)))
+    }
+    fn find_protected_violations(&self, actions: &[MigrationAction]) -> Vec<String> {
+        actions
+            .iter()
+            .filter(|a| {
+                matches!(a.from_location, PathLocation::System)
+                    && matches!(
+                        a.action_type,
+                        ActionType::RemoveDuplicate | ActionType::MoveToUser
+                    )
+                    && is_protected_path(&a.path)
+            })
+            .map(|a| format!("Would remove protected system entry: {}", a.path))
+            .collect()
+    }
     fn plan_duplicate_removal(&self, entries: &[PathEntry]) -> Result<Vec<MigrationAction>> {
         let mut actions = Vec::new();
         let mut path_locations: HashMap<String, Vec<&PathEntry>> = HashMap::new();
         for entry in entries {
-            let normalized = entry.path.trim_matches('"').to_lowercase();
+            let normalized = normalize_for_comparison(&entry.path);
             path_locations.entry(normalized).or_default().push(entry);
         }
         for (_normalized_path, locations) in path_locations {
@@ -104,6 +251,7 @@ impl PathMigrator {
                         action_type: ActionType::RemoveDuplicate,
                         path: entry.path.clone(),
                         from_location: entry.location,
+                        reason_code: ReasonCode::DuplicateAcrossScopes,
                         reason: format!("Duplicate - already exists in {}", keep_location),
                     });
                 }
@@ -121,15 +269,9 @@ impl PathMigrator {
                     action_type: ActionType::MoveToUser,
                     path: entry.path.clone(),
                     from_location: PathLocation::System,
+                    reason_code: ReasonCode::UserPathInSystem,
                     reason: "User-specific path should be in USER PATH".to_string(),
                 });
-            } else if entry.path.contains(' ') && !entry.path.starts_with('"') {
-                actions.push(MigrationAction {
-                    action_type: ActionType::AddQuotes,
-                    path: entry.path.clone(),
-                    from_location: entry.location,
-                    reason: "Path contains spaces and should be quoted".to_string(),
-                });
             }
         }
         Ok(actions)
@@ -139,11 +281,21 @@ impl PathMigrator {
             .iter()
             .any(|a| matches!(a.from_location, PathLocation::System))
     }
+    /// Applies a migration plan.
+    ///
+    /// Plans that touch protected system entries are rejected unless `force` is set.
     pub fn execute_migration(
         &self,
         plan: &MigrationPlan,
         dry_run: bool,
+        force: bool,
     ) -> Result<MigrationResult> {
+        if !dry_run && !force && !plan.protected_violations.is_empty() {
+            bail!(
+                "Migration would change protected system entries:\n  {}\nUse --force to apply anyway.",
+                plan.protected_violations.join("\n  ")
+            );
+        }
         if dry_run {
             return Ok(MigrationResult {
                 backup_path: PathBuf::new(),
@@ -153,9 +305,9 @@ impl PathMigrator {
             });
         }
         let backup_result = self.backup_manager.create()?;
-        let (system_removals, user_removals, user_additions) = self.categorize_actions(plan);
-        let user_path_updated = self.apply_user_changes(&user_removals, &user_additions)?;
-        let (system_path_updated, system_path_error) = self.apply_system_changes(&system_removals);
+        let (system_changes, user_changes) = self.categorize_actions(plan);
+        let user_path_updated = self.apply_user_changes(&user_changes)?;
+        let (system_path_updated, system_path_error) = self.apply_system_changes(&system_changes);
         Ok(MigrationResult {
             backup_path: backup_result.path,
             user_path_updated,
@@ -163,77 +315,164 @@ impl PathMigrator {
             system_path_error,
         })
     }
-    fn categorize_actions(&self, plan: &MigrationPlan) -> (Vec<String>, Vec<String>, Vec<String>) {
-        let mut system_removals = Vec::new();
-        let mut user_removals = Vec::new();
-        let mut user_additions = Vec::new();
-        for action in &plan.actions {
-            match (&action.action_type, &action.from_location) {
-                (ActionType::RemoveDuplicate, PathLocation::System) => {
-                    system_removals.push(action.path.clone());
+    /// Applies `ops` in order to the USER PATH entries `paths`.
+    ///
+    /// Returns the plan (one action per entry moved) and the reordered
+    /// entries. Moving a protected entry behind another one is reported in
+    /// `protected_violations`.
+    pub fn plan_reorder(
+        &self,
+        paths: &[String],
+        ops: &[ReorderOp],
+    ) -> Result<(MigrationPlan, Vec<String>)> {
+        let mut reordered = paths.to_vec();
+        let mut actions = Vec::new();
+        let check = |index: usize| {
+            if index >= paths.len() {
+                bail!(
+                    "Index {} is out of range (PATH has {} entries)",
+                    index,
+                    paths.len()
+                );
+            }
+            Ok(())
+        };
+        for op in ops {
+            let moves = match *op {
+                ReorderOp::Move { from, to } => {
+                    check(from)?;
+                    check(to)?;
+                    let entry = reordered.remove(from);
+                    reordered.insert(to, entry);
+                    vec![(from, to)]
+                }
+                ReorderOp::Swap { first, second } => {
+                    check(first)?;
+                    check(second)?;
+                    reordered.swap(first, second);
+                    vec![(first, second), (second, first)]
                 }
-                (ActionType::RemoveDuplicate, PathLocation::User) => {
-                    user_removals.push(action.path.clone());
+            };
+            for (from, to) in moves {
+                if from == to {
+                    continue;
                 }
-                (ActionType::MoveToUser, PathLocation::System) => {
-                    system_removals.push(action.path.clone());
-                    let path_to_add = quote_if_needed(&action.path);
-                    user_additions.push(path_to_add);
+                actions.push(MigrationAction {
+                    action_type: ActionType::ReorderAction { from, to },
+                    path: reordered[to].clone(),
+                    from_location: PathLocation::User,
+                    reason_code: ReasonCode::UserReorder,
+                    reason: format!("Moved from position {} to {}", from, to),
+                });
+            }
+        }
+        let plan = MigrationPlan {
+            actions,
+            requires_admin: false,
+            protected_violations: protected_violations(paths, &reordered),
+        };
+        Ok((plan, reordered))
+    }
+    /// Backs up PATH and writes the reordered USER PATH.
+    pub fn execute_reorder(
+        &self,
+        plan: &MigrationPlan,
+        reordered: &[String],
+        force: bool,
+    ) -> Result<MigrationResult> {
+        if !force && !plan.protected_violations.is_empty() {
+            bail!(
+                "Reorder would demote protected system entries:\n  {}\nUse --force to apply anyway.",
+                plan.protected_violations.join("\n  ")
+            );
+        }
+        let backup_result = self.backup_manager.create()?;
+        RegistryHelper::write_user_path(&RegistryHelper::join_paths(reordered))?;
+        Ok(MigrationResult {
+            backup_path: backup_result.path,
+            user_path_updated: true,
+            system_path_updated: false,
+            system_path_error: None,
+        })
+    }
+    fn categorize_actions(&self, plan: &MigrationPlan) -> (ScopeChanges, ScopeChanges) {
+        let mut system = ScopeChanges::default();
+        let mut user = ScopeChanges::default();
+        for action in &plan.actions {
+            let changes = match action.from_location {
+                PathLocation::System => &mut system,
+                PathLocation::User => &mut user,
+            };
+            match action.action_type {
+                ActionType::RemoveDuplicate => {
+                    changes.removals.push(action.path.clone());
                 }
-                (ActionType::AddQuotes, PathLocation::System) => {
-                    system_removals.push(action.path.clone());
-                    system_removals.push(format!("\"{}\"", action.path));
+                ActionType::MoveToUser => {
+                    changes.removals.push(action.path.clone());
+                    user.additions.push(quote_if_needed(&action.path));
                 }
-                (ActionType::AddQuotes, PathLocation::User) => {
-                    user_removals.push(action.path.clone());
-                    user_additions.push(format!("\"{}\"", action.path));
+                ActionType::AddQuotes => {
+                    changes
+                        .quotes
+                        .insert(normalize_for_comparison(&action.path));
                 }
-                _ => {}
+                ActionType::ReorderAction { .. } => {}
             }
         }
-        (system_removals, user_removals, user_additions)
+        (system, user)
     }
-    fn apply_user_changes(&self, removals: &[String], additions: &[String]) -> Result<bool> {
-        if removals.is_empty() && additions.is_empty() {
+    fn apply_user_changes(&self, changes: &ScopeChanges) -> Result<bool> {
+        if changes.is_empty() {
             return Ok(false);
         }
         let current_path = RegistryHelper::read_user_path_raw()?;
-        let mut paths = RegistryHelper::parse_path_string(&current_path);
-        let removals_normalized: HashSet<String> = removals
-            .iter()
-            .map(|p| p.trim_matches('"').to_lowercase())
-            .collect();
-        paths.retain(|p| {
-            let normalized = p.trim_matches('"').to_lowercase();
-            !removals_normalized.contains(&normalized)
-        });
-        paths.extend(additions.iter().cloned());
-        let new_path = RegistryHelper::join_paths(&paths);
-        RegistryHelper::write_user_path(&new_path)?;
+        let paths = changes.apply(RegistryHelper::parse_path_string(&current_path));
+        RegistryHelper::write_user_path(&RegistryHelper::join_paths(&paths))?;
         Ok(true)
     }
-    fn apply_system_changes(&self, removals: &[String]) -> (bool, Option<String>) {
-        if removals.is_empty() {
+    fn apply_system_changes(&self, changes: &ScopeChanges) -> (bool, Option<String>) {
+        if changes.is_empty() {
             return (false, None);
         }
-        match self.update_system_path(removals) {
+        match self.update_system_path(changes) {
             Ok(_) => (true, None),
-            Err(e) => (false, Some(e.to_string())),
+            Err(e) => (false, Some(format!("{:#}", e))),
         }
     }
-    fn update_system_path(&self, removals: &[String]) -> Result<()> {
+    fn update_system_path(&self, changes: &ScopeChanges) -> Result<()> {
         let current_path = RegistryHelper::read_system_path_raw()?;
-        let mut paths = RegistryHelper::parse_path_string(&current_path);
-        let removals_normalized: HashSet<String> = removals
+        let paths = changes.apply(RegistryHelper::parse_path_string(&current_path));
+        RegistryHelper::write_system_path(&RegistryHelper::join_paths(&paths))?;
+        Ok(())
+    }
+}
+
+/// Edits a migration plan makes to one scope.
+#[derive(Default)]
+struct ScopeChanges {
+    removals: Vec<String>,
+    additions: Vec<String>,
+    /// Normalized entries to quote in place.
+    quotes: HashSet<String>,
+}
+
+impl ScopeChanges {
+    fn is_empty(&self) -> bool {
+        self.removals.is_empty() && self.additions.is_empty() && self.quotes.is_empty()
+    }
+    fn apply(&self, mut paths: Vec<String>) -> Vec<String> {
+        let removals: HashSet<String> = self
+            .removals
             .iter()
-            .map(|p| p.trim_matches('"').to_lowercase())
+            .map(|p| normalize_for_comparison(p))
             .collect();
-        paths.retain(|p| {
-            let normalized = p.trim_matches('"').to_lowercase();
-            !removals_normalized.contains(&normalized)
-        });
-        let new_path = RegistryHelper::join_paths(&paths);
-        RegistryHelper::write_system_path(&new_path)?;
-        Ok(())
+        paths.retain(|p| !removals.contains(&normalize_for_comparison(p)));
+        for path in &mut paths {
+            if self.quotes.contains(&normalize_for_comparison(path)) {
+                *path = quote_if_needed(path);
+            }
+        }
+        paths.extend(self.additions.iter().cloned());
+        paths
     }
 }
@@ -1,9 +1,18 @@
 //! Unified data models for spath-cli.
-use crate::constants::MAX_SINGLE_PATH_LENGTH;
-use crate::utils::categorize_path;
+use crate::constants::{FILE_ENTRY_MESSAGE, MAX_PATH_COMPONENT_LENGTH, MAX_SINGLE_PATH_LENGTH};
+use crate::security::acl::is_writable_by_non_admin;
+use crate::utils::{
+    categorize_path, expand_env_vars, has_overlong_component, normalize_for_comparison,
+    path_exists_within, strip_trailing_dots_and_spaces,
+};
+use serde::Serialize;
+use std::collections::HashMap;
 use std::path::Path;
+use std::time::Duration;
+use tracing::warn;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PathLocation {
     System,
     User,
@@ -18,7 +27,8 @@ impl std::fmt::Display for PathLocation {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PathCategory {
     SystemProgram,
     UserProgram,
@@ -26,45 +36,92 @@ pub enum PathCategory {
     Ambiguous,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum IssueLevel {
     Info,
     Warning,
     Critical,
 }
 
-#[derive(Debug, Clone)]
+/// An issue raised for a PATH entry.
+///
+/// Equality ignores `entry`, so the same issue still matches across scans
+/// in which the entry moved.
+#[derive(Debug, Clone, Serialize)]
 pub struct PathIssue {
     pub path: String,
     pub level: IssueLevel,
     pub message: String,
+    /// Position of the entry in the scanned PATH, set by the scanner.
+    #[serde(skip)]
+    pub entry: Option<usize>,
+}
+
+impl PartialEq for PathIssue {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.level == other.level && self.message == other.message
+    }
 }
 
+impl Eq for PathIssue {}
+
 impl PathIssue {
     pub fn critical(path: impl Into<String>, message: impl Into<String>) -> Self {
-        Self {
-            path: path.into(),
-            level: IssueLevel::Critical,
-            message: message.into(),
-        }
+        Self::new(path, IssueLevel::Critical, message)
     }
     pub fn warning(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(path, IssueLevel::Warning, message)
+    }
+    pub fn info(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(path, IssueLevel::Info, message)
+    }
+    fn new(path: impl Into<String>, level: IssueLevel, message: impl Into<String>) -> Self {
         Self {
             path: path.into(),
-            level: IssueLevel::Warning,
+            level,
             message: message.into(),
+            entry: None,
         }
     }
-    pub fn info(path: impl Into<String>, message: impl Into<String>) -> Self {
+}
+
+/// Criteria an entry must meet to count towards [`AuditStats::valid_paths`].
+///
+/// The default requires an entry that exists, is absolute, and is quoted
+/// if it contains spaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidityPolicy {
+    pub require_exists: bool,
+    pub require_absolute: bool,
+    pub require_quotes_for_spaces: bool,
+}
+
+impl Default for ValidityPolicy {
+    fn default() -> Self {
         Self {
-            path: path.into(),
-            level: IssueLevel::Info,
-            message: message.into(),
+            require_exists: true,
+            require_absolute: true,
+            require_quotes_for_spaces: true,
         }
     }
 }
 
-#[derive(Debug, Default, Clone)]
+impl ValidityPolicy {
+    pub fn is_valid(
+        &self,
+        exists: bool,
+        is_absolute: bool,
+        has_spaces: bool,
+        is_quoted: bool,
+    ) -> bool {
+        (exists || !self.require_exists)
+            && (is_absolute || !self.require_absolute)
+            && (!has_spaces || is_quoted || !self.require_quotes_for_spaces)
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
 pub struct AuditStats {
     pub total_paths: usize,
     pub unquoted_with_spaces: usize,
@@ -72,18 +129,64 @@ pub struct AuditStats {
     pub relative_paths: usize,
     pub properly_quoted: usize,
     pub valid_paths: usize,
+    /// Entries whose existence check timed out.
+    pub unreachable: usize,
+    /// Entries on drives excluded from the scan.
+    pub skipped: usize,
+    /// Entries matching an exclude pattern; not part of `total_paths`.
+    pub excluded: usize,
 }
 
-#[derive(Debug, Clone)]
+impl AuditStats {
+    /// Percentage of valid paths, or 0 for an empty PATH.
+    pub fn health_score(&self) -> u32 {
+        if self.total_paths > 0 {
+            ((self.valid_paths as f64 / self.total_paths as f64) * 100.0) as u32
+        } else {
+            0
+        }
+    }
+    /// Adds up the statistics of two scopes.
+    pub fn merged(&self, other: &AuditStats) -> AuditStats {
+        AuditStats {
+            total_paths: self.total_paths + other.total_paths,
+            unquoted_with_spaces: self.unquoted_with_spaces + other.unquoted_with_spaces,
+            non_existent: self.non_existent + other.non_existent,
+            relative_paths: self.relative_paths + other.relative_paths,
+            properly_quoted: self.properly_quoted + other.properly_quoted,
+            valid_paths: self.valid_paths + other.valid_paths,
+            unreachable: self.unreachable + other.unreachable,
+            skipped: self.skipped + other.skipped,
+            excluded: self.excluded + other.excluded,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct PathEntry {
     pub path: String,
     pub index: usize,
     pub location: PathLocation,
     pub category: PathCategory,
     pub exists: bool,
+    /// `false` when the entry is missing or is a file.
+    pub is_directory: bool,
     pub has_spaces: bool,
     pub is_quoted: bool,
     pub is_duplicate: bool,
+    /// Set by [`PathEntry::check_acl`]; `false` until the DACL is read.
+    pub writable_by_others: bool,
+    /// `false` for entries built offline, whose `exists` and
+    /// `is_directory` were never looked up.
+    pub existence_checked: bool,
+}
+
+/// Expands a raw PATH entry, stripping quotes both around the entry and
+/// around what a variable expanded to.
+fn resolve_entry(path: &str) -> String {
+    expand_env_vars(path.trim_matches('"'))
+        .trim_matches('"')
+        .to_string()
 }
 
 impl Default for PathEntry {
@@ -94,37 +197,106 @@ impl Default for PathEntry {
             location: PathLocation::User,
             category: PathCategory::Ambiguous,
             exists: false,
+            is_directory: false,
             has_spaces: false,
             is_quoted: false,
             is_duplicate: false,
+            writable_by_others: false,
+            existence_checked: true,
         }
     }
 }
 
 impl PathEntry {
     pub fn new(path: String, index: usize, location: PathLocation, all_paths: &[String]) -> Self {
+        Self::with_path_counts(path, index, location, &Self::path_counts(all_paths))
+    }
+    /// Counts how often each normalized path occurs in `all_paths`, for
+    /// building many entries with [`PathEntry::with_path_counts`].
+    pub fn path_counts(all_paths: &[String]) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for path in all_paths {
+            *counts.entry(normalize_for_comparison(path)).or_insert(0) += 1;
+        }
+        counts
+    }
+    /// Like [`PathEntry::new`], taking occurrence counts from
+    /// [`PathEntry::path_counts`] so each duplicate check is a lookup.
+    pub fn with_path_counts(
+        path: String,
+        index: usize,
+        location: PathLocation,
+        path_counts: &HashMap<String, usize>,
+    ) -> Self {
+        Self::with_path_counts_within(path, index, location, path_counts, None)
+    }
+    /// Like [`PathEntry::with_path_counts`], bounding the existence check
+    /// by `timeout`; an entry whose check times out is left unchecked, as
+    /// with [`PathEntry::offline`].
+    pub fn with_path_counts_within(
+        path: String,
+        index: usize,
+        location: PathLocation,
+        path_counts: &HashMap<String, usize>,
+        timeout: Option<Duration>,
+    ) -> Self {
+        let resolved = strip_trailing_dots_and_spaces(&resolve_entry(&path));
+        let Some(exists) = path_exists_within(&resolved, timeout) else {
+            warn!("Existence check timed out: {}", path);
+            return Self::offline(path, index, location, path_counts);
+        };
+        let is_directory = exists && Path::new(&resolved).is_dir();
+        Self {
+            exists,
+            is_directory,
+            existence_checked: true,
+            ..Self::offline(path, index, location, path_counts)
+        }
+    }
+    /// Like [`PathEntry::with_path_counts`] without touching the
+    /// filesystem: existence is left unchecked rather than false.
+    pub fn offline(
+        path: String,
+        index: usize,
+        location: PathLocation,
+        path_counts: &HashMap<String, usize>,
+    ) -> Self {
         let trimmed = path.trim_matches('"');
-        let exists = Path::new(trimmed).exists();
         let has_spaces = trimmed.contains(' ');
         let is_quoted = path.starts_with('"') && path.ends_with('"');
         let category = categorize_path(trimmed);
-        let normalized = trimmed.to_lowercase();
-        let is_duplicate = all_paths
-            .iter()
-            .enumerate()
-            .filter(|(i, _)| *i != index)
-            .any(|(_, p)| p.trim_matches('"').to_lowercase() == normalized);
+        let normalized = normalize_for_comparison(trimmed);
+        let is_duplicate = path_counts.get(&normalized).copied().unwrap_or(0) > 1;
         Self {
             path,
             index,
             location,
             category,
-            exists,
+            exists: false,
+            is_directory: false,
             has_spaces,
             is_quoted,
             is_duplicate,
+            writable_by_others: false,
+            existence_checked: false,
         }
     }
+    /// The path with quotes removed and `%VAR%` references expanded, as
+    /// Windows resolves it; `path` itself stays unexpanded for display.
+    pub fn resolve_env_vars(&self) -> String {
+        resolve_entry(&self.path)
+    }
+    /// Reads the directory's DACL and records whether non-admin users can
+    /// write to it. Missing directories and unreadable DACLs count as not
+    /// writable.
+    pub fn check_acl(&mut self) -> bool {
+        self.writable_by_others = self.exists
+            && is_writable_by_non_admin(Path::new(&strip_trailing_dots_and_spaces(
+                &self.resolve_env_vars(),
+            )))
+            .unwrap_or(false);
+        self.writable_by_others
+    }
     pub fn should_be_in_user_path(&self) -> bool {
         matches!(self.category, PathCategory::UserProgram)
             && matches!(self.location, PathLocation::System)
@@ -132,29 +304,53 @@ impl PathEntry {
     pub fn needs_quotes(&self) -> bool {
         self.has_spaces && !self.is_quoted
     }
+    /// Whether the entry was looked up and does not exist.
+    pub fn is_missing(&self) -> bool {
+        self.existence_checked && !self.exists
+    }
+    /// Whether the entry exists but is a file rather than a directory.
+    pub fn is_file(&self) -> bool {
+        self.exists && !self.is_directory
+    }
     pub fn has_issues(&self) -> bool {
-        !self.exists || self.needs_quotes() || self.is_duplicate
+        self.is_missing()
+            || self.is_file()
+            || self.needs_quotes()
+            || self.is_duplicate
+            || self.writable_by_others
     }
     pub fn is_user_specific(&self) -> bool {
         matches!(self.category, PathCategory::UserProgram)
     }
     pub fn get_warnings(&self) -> Vec<String> {
         let mut warnings = Vec::new();
-        if !self.exists {
+        if self.is_missing() {
             warnings.push("Path does not exist".to_string());
         }
+        if self.is_file() {
+            warnings.push(FILE_ENTRY_MESSAGE.to_string());
+        }
         if self.needs_quotes() {
             warnings.push("Contains spaces but not quoted".to_string());
         }
         if self.is_duplicate {
             warnings.push("Duplicate path".to_string());
         }
+        if self.writable_by_others {
+            warnings.push("Writable by non-admin users".to_string());
+        }
         if self.path.len() > MAX_SINGLE_PATH_LENGTH {
             warnings.push(format!(
                 "Path exceeds {} characters",
                 MAX_SINGLE_PATH_LENGTH
             ));
         }
+        if has_overlong_component(&self.path) {
+            warnings.push(format!(
+                "Path component exceeds {} characters",
+                MAX_PATH_COMPONENT_LENGTH
+            ));
+        }
         warnings
     }
 }
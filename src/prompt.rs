@@ -0,0 +1,37 @@
+//! Yes/no confirmation prompts used by delicate mode.
+use std::io::{self, Write};
+
+/// Asks the user to confirm an operation.
+pub trait Prompter {
+    fn confirm(&self, message: &str) -> bool;
+}
+
+/// Asks on stdout and reads the answer from stdin; only "y" or "yes"
+/// (case-insensitive) confirm.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InteractivePrompter;
+
+impl Prompter for InteractivePrompter {
+    fn confirm(&self, message: &str) -> bool {
+        print!("{} [y/N]: ", message);
+        if io::stdout().flush().is_err() {
+            return false;
+        }
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input).is_err() {
+            return false;
+        }
+        let answer = input.trim().to_lowercase();
+        answer == "y" || answer == "yes"
+    }
+}
+
+/// Confirms everything without asking, for `--force`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AutoConfirmPrompter;
+
+impl Prompter for AutoConfirmPrompter {
+    fn confirm(&self, _message: &str) -> bool {
+        true
+    }
+}
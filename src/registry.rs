@@ -7,19 +7,34 @@
 //!
 //! Write operations use file-based locking via `fs2` crate to prevent concurrent
 //! modifications to PATH by multiple spath processes. Lock files are stored in
-//! `%LOCALAPPDATA%\spath\locks\`.
+//! `%LOCALAPPDATA%\spath\locks\`. A write fails with
+//! [`RegistryError::LockHeld`] rather than waiting while another process
+//! holds the lock.
+//!
+//! ## Errors
+//!
+//! [`RegistryHelper`] returns [`RegistryError`], so library users can tell
+//! a missing administrator right from an over-long PATH or a held lock.
 
-use anyhow::{bail, Context, Result};
 use fs2::FileExt;
 use std::fs::{self, File};
+use std::io;
 use std::path::PathBuf;
+use std::thread::JoinHandle;
+use thiserror::Error;
 use tracing::{debug, error, info, warn};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{CloseHandle, HANDLE, WAIT_OBJECT_0};
+use windows::Win32::System::Registry::{RegNotifyChangeKeyValue, HKEY, REG_NOTIFY_CHANGE_LAST_SET};
+use windows::Win32::System::Threading::{CreateEventW, SetEvent, WaitForMultipleObjects, INFINITE};
 use winreg::enums::*;
-use winreg::RegKey;
+use winreg::types::ToRegValue;
+use winreg::{RegKey, RegValue};
 
 use crate::constants::{
-    MAX_PATH_LENGTH, SYSTEM_ENV_KEY, SYSTEM_PATH_LOCK, USER_ENV_KEY, USER_PATH_LOCK,
+    MAX_PATH_LENGTH, PATH_SEPARATOR, SYSTEM_ENV_KEY, SYSTEM_PATH_LOCK, USER_ENV_KEY, USER_PATH_LOCK,
 };
+use crate::models::PathLocation;
 
 /// RAII guard for file lock. Automatically releases lock when dropped.
 pub struct PathLockGuard {
@@ -28,43 +43,177 @@ pub struct PathLockGuard {
 
 impl PathLockGuard {
     /// Acquires an exclusive lock on the specified lock file.
-    /// Blocks until the lock is acquired.
-    fn acquire(lock_name: &str) -> Result<Self> {
+    /// Fails with [`RegistryError::LockHeld`] instead of waiting when
+    /// another process holds it.
+    fn acquire(lock_name: &str) -> Result<Self, RegistryError> {
         debug!("Attempting to acquire lock: {}", lock_name);
-        let lock_dir = get_lock_dir()?;
-        fs::create_dir_all(&lock_dir).context("Failed to create lock directory")?;
+        let lock_dir = get_lock_dir().map_err(RegistryError::LockFailed)?;
+        fs::create_dir_all(&lock_dir).map_err(RegistryError::LockFailed)?;
         let lock_path = lock_dir.join(lock_name);
-        let file = File::create(&lock_path).context("Failed to create lock file")?;
-        file.lock_exclusive()
-            .context("Failed to acquire exclusive lock. Another spath process may be running.")?;
+        let file = File::create(&lock_path).map_err(RegistryError::LockFailed)?;
+        file.try_lock_exclusive().map_err(|e| {
+            if e.raw_os_error() == fs2::lock_contended_error().raw_os_error() {
+                RegistryError::LockHeld
+            } else {
+                RegistryError::LockFailed(e)
+            }
+        })?;
         debug!("Lock acquired: {}", lock_name);
         Ok(Self { _file: file })
     }
 }
 
+/// Background watcher started by [`RegistryHelper::watch_path_change`].
+/// Dropping it stops the watcher thread and waits for it to exit.
+pub struct WatchHandle {
+    stop_event: HANDLE,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        unsafe {
+            if let Err(e) = SetEvent(self.stop_event) {
+                warn!("Failed to signal PATH watcher to stop: {}", e);
+            }
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        unsafe {
+            let _ = CloseHandle(self.stop_event);
+        }
+    }
+}
+
+/// Re-arms the change notification on `env_key` and runs `callback` after
+/// each change until `stop_event` is signalled or the notification fails.
+fn watch_loop(env_key: &RegKey, stop_event: HANDLE, change_event: HANDLE, callback: impl Fn()) {
+    let hkey = HKEY(env_key.raw_handle());
+    loop {
+        let armed = unsafe {
+            RegNotifyChangeKeyValue(hkey, false, REG_NOTIFY_CHANGE_LAST_SET, change_event, true)
+        };
+        if let Err(e) = armed {
+            error!("Failed to watch registry for PATH changes: {}", e);
+            return;
+        }
+        let signalled =
+            unsafe { WaitForMultipleObjects(&[stop_event, change_event], false, INFINITE) };
+        if signalled.0 != WAIT_OBJECT_0.0 + 1 {
+            return;
+        }
+        callback();
+    }
+}
+
 /// Returns the directory for lock files: `%LOCALAPPDATA%\spath\locks\`
-fn get_lock_dir() -> Result<PathBuf> {
-    let local_app_data =
-        std::env::var("LOCALAPPDATA").context("LOCALAPPDATA environment variable not set")?;
+fn get_lock_dir() -> io::Result<PathBuf> {
+    let local_app_data = std::env::var("LOCALAPPDATA").map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "LOCALAPPDATA environment variable not set",
+        )
+    })?;
     Ok(PathBuf::from(local_app_data).join("spath").join("locks"))
 }
 
+/// Why a PATH value could not be read from the registry.
+#[derive(Debug, Error)]
+pub enum PathReadError {
+    /// The key or the `Path` value does not exist.
+    #[error("PATH value not found")]
+    NotFound,
+    /// The key exists but may not be read by this user.
+    #[error("access to PATH value denied")]
+    PermissionDenied,
+    /// Any other registry error.
+    #[error("failed to read PATH value")]
+    IoError(#[source] io::Error),
+}
+
+impl From<io::Error> for PathReadError {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::NotFound => PathReadError::NotFound,
+            io::ErrorKind::PermissionDenied => PathReadError::PermissionDenied,
+            _ => PathReadError::IoError(e),
+        }
+    }
+}
+
+/// Why a [`RegistryHelper`] operation failed.
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    /// SYSTEM PATH may only be written with administrator rights.
+    #[error("writing SYSTEM PATH requires administrator rights")]
+    NeedsAdmin,
+    /// The new PATH is longer than Windows accepts.
+    #[error(
+        "PATH exceeds maximum length of {max} characters (current: {len} characters). \
+        Consider removing unused paths."
+    )]
+    PathTooLong { len: usize, max: usize },
+    /// Another spath process holds the exclusive PATH lock and may be
+    /// modifying PATH.
+    #[error("PATH is locked by another spath process; try again when it has finished")]
+    LockHeld,
+    /// The lock file could not be created or locked.
+    #[error("failed to lock the PATH lock file")]
+    LockFailed(#[source] io::Error),
+    /// PATH could not be read.
+    #[error(transparent)]
+    ReadFailed(#[from] PathReadError),
+    /// The environment key could not be opened for writing, or the value
+    /// could not be written.
+    #[error("failed to write PATH value")]
+    WriteFailed(#[source] io::Error),
+    /// The value read back after a write differs from the one written.
+    /// With `restored` the previous PATH was put back.
+    #[error(
+        "PATH write verification failed: {problem}{}",
+        if *.restored { "; the previous PATH was restored" } else { "" }
+    )]
+    VerifyFailed { problem: String, restored: bool },
+    /// The change notification could not be set up.
+    #[error("failed to watch PATH for changes")]
+    WatchFailed(#[source] io::Error),
+}
+
+/// `e` followed by its `source()` chain, as `{:#}` prints an anyhow error.
+fn error_chain(e: &dyn std::error::Error) -> String {
+    let mut text = e.to_string();
+    let mut source = e.source();
+    while let Some(cause) = source {
+        text.push_str(": ");
+        text.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    text
+}
+
+/// Maps a failure to open an environment key for writing: access denied
+/// means administrator rights are missing.
+fn open_for_write_error(e: io::Error) -> RegistryError {
+    if e.kind() == io::ErrorKind::PermissionDenied {
+        RegistryError::NeedsAdmin
+    } else {
+        RegistryError::WriteFailed(e)
+    }
+}
+
 /// Helper for Windows Registry PATH operations.
 pub struct RegistryHelper;
 
 impl RegistryHelper {
     /// Reads SYSTEM PATH as raw string.
     /// May fail without administrator rights.
-    pub fn read_system_path_raw() -> Result<String> {
+    pub fn read_system_path_raw() -> Result<String, RegistryError> {
         debug!("Reading SYSTEM PATH from registry");
         let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
-        let env_key = hklm.open_subkey(SYSTEM_ENV_KEY).map_err(|e| {
-            warn!("Failed to open system environment key: {}", e);
-            anyhow::anyhow!("Failed to open system environment key. Try running as administrator.")
-        })?;
-        let path = env_key.get_value("Path").map_err(|e| {
-            error!("Failed to read system PATH: {}", e);
-            anyhow::anyhow!("Failed to read system PATH")
+        let path = Self::read_path_value(&hklm, SYSTEM_ENV_KEY).map_err(|e| {
+            error!("Failed to read system PATH: {}", error_chain(&e));
+            RegistryError::ReadFailed(e)
         })?;
         info!("Successfully read SYSTEM PATH");
         Ok(path)
@@ -73,29 +222,46 @@ impl RegistryHelper {
     /// Reads SYSTEM PATH as `Vec<String>`.
     ///
     /// May fail without administrator rights.
-    pub fn read_system_path() -> Result<Vec<String>> {
+    pub fn read_system_path() -> Result<Vec<String>, RegistryError> {
         let path = Self::read_system_path_raw()?;
         Ok(Self::parse_path_string(&path))
     }
 
     /// Reads USER PATH as raw string.
-    pub fn read_user_path_raw() -> Result<String> {
+    ///
+    /// Returns an empty string when the value does not exist, as on fresh
+    /// installs; other read errors are returned.
+    pub fn read_user_path_raw() -> Result<String, RegistryError> {
         debug!("Reading USER PATH from registry");
         let hkcu = RegKey::predef(HKEY_CURRENT_USER);
-        let env_key = hkcu.open_subkey(USER_ENV_KEY).map_err(|e| {
-            error!("Failed to open user environment key: {}", e);
-            anyhow::anyhow!("Failed to open user environment key")
-        })?;
-        let path = env_key.get_value("Path").map_err(|e| {
-            error!("Failed to read user PATH: {}", e);
-            anyhow::anyhow!("Failed to read user PATH")
+        let path = Self::read_path_with_fallback(&hkcu, USER_ENV_KEY).map_err(|e| {
+            error!("Failed to read user PATH: {}", error_chain(&e));
+            RegistryError::ReadFailed(e)
         })?;
         info!("Successfully read USER PATH");
         Ok(path)
     }
 
+    /// Reads the `Path` value under `root\subkey`.
+    pub fn read_path_value(root: &RegKey, subkey: &str) -> Result<String, PathReadError> {
+        let env_key = root.open_subkey(subkey)?;
+        Ok(env_key.get_value("Path")?)
+    }
+
+    /// Like [`RegistryHelper::read_path_value`], but a missing key or value
+    /// reads as an empty PATH.
+    pub fn read_path_with_fallback(root: &RegKey, subkey: &str) -> Result<String, PathReadError> {
+        match Self::read_path_value(root, subkey) {
+            Err(PathReadError::NotFound) => {
+                warn!("No Path value under {}; treating PATH as empty", subkey);
+                Ok(String::new())
+            }
+            result => result,
+        }
+    }
+
     /// Reads USER PATH as `Vec<String>`.
-    pub fn read_user_path() -> Result<Vec<String>> {
+    pub fn read_user_path() -> Result<Vec<String>, RegistryError> {
         let path = Self::read_user_path_raw()?;
         Ok(Self::parse_path_string(&path))
     }
@@ -103,20 +269,19 @@ impl RegistryHelper {
     /// Validates that PATH length does not exceed Windows limit.
     ///
     /// # Errors
-    /// Returns an error if the path exceeds MAX_PATH_LENGTH (2047 characters).
-    pub fn validate_path_length(path: &str) -> Result<()> {
+    /// Returns [`RegistryError::PathTooLong`] if the path exceeds
+    /// MAX_PATH_LENGTH (2047 characters).
+    pub fn validate_path_length(path: &str) -> Result<(), RegistryError> {
         if path.len() > MAX_PATH_LENGTH {
             error!(
                 "PATH exceeds maximum length: {} > {}",
                 path.len(),
                 MAX_PATH_LENGTH
             );
-            bail!(
-                "PATH exceeds maximum length of {} characters (current: {} characters). \
-                Consider removing unused paths.",
-                MAX_PATH_LENGTH,
-                path.len()
-            );
+            return Err(RegistryError::PathTooLong {
+                len: path.len(),
+                max: MAX_PATH_LENGTH,
+            });
         }
         debug!("PATH length validated: {} characters", path.len());
         Ok(())
@@ -128,30 +293,88 @@ impl RegistryHelper {
     /// spath processes try to modify PATH simultaneously.
     ///
     /// # Errors
-    /// Returns an error if:
-    /// - Lock cannot be acquired (another process is modifying PATH)
-    /// - PATH exceeds maximum length (2047 characters)
-    /// - Registry key cannot be opened for writing
-    /// - Value cannot be written to registry
-    pub fn write_user_path(path: &str) -> Result<()> {
+    /// - [`RegistryError::LockHeld`] / [`RegistryError::LockFailed`]: the
+    ///   lock cannot be acquired (another process is modifying PATH)
+    /// - [`RegistryError::PathTooLong`]: PATH exceeds 2047 characters
+    /// - [`RegistryError::WriteFailed`]: the key cannot be opened for
+    ///   writing or the value cannot be written
+    /// - [`RegistryError::VerifyFailed`]: the value read back differs
+    pub fn write_user_path(path: &str) -> Result<(), RegistryError> {
         debug!("Writing USER PATH to registry");
-        let _lock = PathLockGuard::acquire(USER_PATH_LOCK)
-            .context("Failed to acquire lock for USER PATH modification")?;
+        let _lock = PathLockGuard::acquire(USER_PATH_LOCK)?;
         Self::validate_path_length(path)?;
         let hkcu = RegKey::predef(HKEY_CURRENT_USER);
         let env_key = hkcu
-            .open_subkey_with_flags(USER_ENV_KEY, KEY_WRITE)
+            .open_subkey_with_flags(USER_ENV_KEY, KEY_READ | KEY_WRITE)
             .map_err(|e| {
                 error!("Failed to open user environment key for writing: {}", e);
-                anyhow::anyhow!("Failed to open user environment key for writing")
+                RegistryError::WriteFailed(e)
             })?;
-        env_key.set_value("Path", &path).map_err(|e| {
-            error!("Failed to write user PATH to registry: {}", e);
-            anyhow::anyhow!("Failed to write user PATH to registry")
-        })?;
+        if let Err(e) = Self::write_verified(&env_key, path) {
+            error!("Failed to write user PATH to registry: {}", error_chain(&e));
+            return Err(e);
+        }
         info!("Successfully wrote USER PATH to registry");
         Ok(())
     }
+    /// Calls `callback` from a background thread whenever a value under the
+    /// environment key of `scope` changes, e.g. when an installer edits PATH.
+    ///
+    /// Any value change under the key triggers the callback, so callers that
+    /// only care about `Path` should compare it with the previous value.
+    pub fn watch_path_change(
+        scope: PathLocation,
+        callback: impl Fn() + Send + 'static,
+    ) -> Result<WatchHandle, RegistryError> {
+        let (root, subkey) = match scope {
+            PathLocation::User => (HKEY_CURRENT_USER, USER_ENV_KEY),
+            PathLocation::System => (HKEY_LOCAL_MACHINE, SYSTEM_ENV_KEY),
+        };
+        debug!("Watching {} PATH for changes", scope);
+        Self::watch_key_change(&RegKey::predef(root), subkey, callback)
+    }
+    /// Calls `callback` from a background thread whenever a value under
+    /// `root\subkey` changes.
+    pub fn watch_key_change(
+        root: &RegKey,
+        subkey: &str,
+        callback: impl Fn() + Send + 'static,
+    ) -> Result<WatchHandle, RegistryError> {
+        let env_key = root
+            .open_subkey_with_flags(subkey, KEY_NOTIFY)
+            .map_err(|e| {
+                error!("Failed to open {} for watching: {}", subkey, e);
+                RegistryError::WatchFailed(e)
+            })?;
+        let stop_event = unsafe { CreateEventW(None, true, false, PCWSTR::null()) }
+            .map_err(|e| RegistryError::WatchFailed(e.into()))?;
+        let change_event = match unsafe { CreateEventW(None, false, false, PCWSTR::null()) } {
+            Ok(event) => event,
+            Err(e) => {
+                unsafe {
+                    let _ = CloseHandle(stop_event);
+                }
+                return Err(RegistryError::WatchFailed(e.into()));
+            }
+        };
+        let thread = std::thread::spawn(move || {
+            watch_loop(&env_key, stop_event, change_event, callback);
+            unsafe {
+                let _ = CloseHandle(change_event);
+            }
+        });
+        Ok(WatchHandle {
+            stop_event,
+            thread: Some(thread),
+        })
+    }
+    /// Whether the SYSTEM environment key can be opened for writing,
+    /// i.e. the process runs with administrator rights.
+    pub fn can_write_system_path() -> bool {
+        RegKey::predef(HKEY_LOCAL_MACHINE)
+            .open_subkey_with_flags(SYSTEM_ENV_KEY, KEY_READ | KEY_WRITE)
+            .is_ok()
+    }
 
     /// Writes SYSTEM PATH to registry with exclusive locking.
     /// Requires administrator rights.
@@ -160,43 +383,190 @@ impl RegistryHelper {
     /// spath processes try to modify PATH simultaneously.
     ///
     /// # Errors
-    /// Returns an error if:
-    /// - Lock cannot be acquired (another process is modifying PATH)
-    /// - PATH exceeds maximum length (2047 characters)
-    /// - Registry key cannot be opened (requires admin)
-    /// - Value cannot be written to registry
-    pub fn write_system_path(path: &str) -> Result<()> {
+    /// As [`RegistryHelper::write_user_path`], plus
+    /// [`RegistryError::NeedsAdmin`] when the key may not be opened for
+    /// writing.
+    pub fn write_system_path(path: &str) -> Result<(), RegistryError> {
         debug!("Writing SYSTEM PATH to registry");
-        let _lock = PathLockGuard::acquire(SYSTEM_PATH_LOCK)
-            .context("Failed to acquire lock for SYSTEM PATH modification")?;
+        let _lock = PathLockGuard::acquire(SYSTEM_PATH_LOCK)?;
         Self::validate_path_length(path)?;
         let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
         let env_key = hklm
             .open_subkey_with_flags(SYSTEM_ENV_KEY, KEY_READ | KEY_WRITE)
             .map_err(|e| {
                 warn!("Failed to open system environment key for writing: {}", e);
-                anyhow::anyhow!(
-                    "Failed to open system environment key for writing (requires admin)"
-                )
+                open_for_write_error(e)
             })?;
-        env_key.set_value("Path", &path).map_err(|e| {
-            error!("Failed to write system PATH to registry: {}", e);
-            anyhow::anyhow!("Failed to write system PATH to registry")
-        })?;
+        if let Err(e) = Self::write_verified(&env_key, path) {
+            error!(
+                "Failed to write system PATH to registry: {}",
+                error_chain(&e)
+            );
+            return Err(e);
+        }
         info!("Successfully wrote SYSTEM PATH to registry");
         Ok(())
     }
 
+    /// Builds the registry value for a PATH string.
+    ///
+    /// Keeps `REG_EXPAND_SZ` when the existing value had it, and uses it
+    /// whenever the new value contains `%VAR%` references, so they are still
+    /// expanded at use time. Otherwise writes `REG_SZ`.
+    pub fn path_reg_value(path: &str, existing: Option<RegType>) -> RegValue {
+        let mut value = path.to_reg_value();
+        if existing == Some(REG_EXPAND_SZ) || path.contains('%') {
+            value.vtype = REG_EXPAND_SZ;
+        }
+        value
+    }
+
+    /// Writes `path` as the `Path` value of `env_key`, preserving its
+    /// registry type (see [`RegistryHelper::path_reg_value`]).
+    pub fn write_path_value(env_key: &RegKey, path: &str) -> io::Result<()> {
+        let existing = env_key.get_raw_value("Path").ok().map(|value| value.vtype);
+        env_key.set_raw_value("Path", &Self::path_reg_value(path, existing))
+    }
+
+    /// Writes `path` through `store` and reads it back. When the value read
+    /// back differs, or cannot be read, the raw value from before the write
+    /// is put back with its registry type (or removed if there was none)
+    /// and an error is returned.
+    ///
+    /// Callers hold the PATH lock, so the value captured first is the one
+    /// this write replaces.
+    pub fn write_verified(store: &dyn PathValueStore, path: &str) -> Result<(), RegistryError> {
+        let previous = match store.read_raw() {
+            Ok(value) => Some(value),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(RegistryError::ReadFailed(e.into())),
+        };
+        store.write_path(path).map_err(RegistryError::WriteFailed)?;
+        let problem = match store.read_path() {
+            Ok(written) if written == path => return Ok(()),
+            Ok(written) => format!(
+                "value read back ({} characters) differs from the value written ({} characters)",
+                written.len(),
+                path.len()
+            ),
+            Err(e) => format!("value could not be read back: {}", e),
+        };
+        error!("PATH write verification failed: {}", problem);
+        let restore = match &previous {
+            Some(value) => store.write_raw(value),
+            None => store.delete_path(),
+        };
+        Err(match restore {
+            Ok(()) => RegistryError::VerifyFailed {
+                problem,
+                restored: true,
+            },
+            Err(e) => RegistryError::VerifyFailed {
+                problem: format!(
+                    "{}; restoring the previous PATH also failed: {}",
+                    problem, e
+                ),
+                restored: false,
+            },
+        })
+    }
+
     /// Parses PATH string into `Vec<String>`, filtering empty entries.
     pub fn parse_path_string(path: &str) -> Vec<String> {
-        path.split(';')
+        Self::parse_path_string_with(path, PATH_SEPARATOR)
+    }
+
+    /// Parses PATH string using a custom separator, filtering empty entries.
+    pub fn parse_path_string_with(path: &str, separator: char) -> Vec<String> {
+        Self::split_entries_with(path, separator)
+            .into_iter()
             .filter(|s| !s.is_empty())
             .map(|s| s.to_string())
             .collect()
     }
 
+    /// Splits a PATH string into entries, keeping empty ones.
+    ///
+    /// A separator inside a double-quoted span is part of the entry, so
+    /// `"C:\A;B";C:\Windows` has two entries.
+    pub fn split_entries(path: &str) -> Vec<&str> {
+        Self::split_entries_with(path, PATH_SEPARATOR)
+    }
+
+    fn split_entries_with(path: &str, separator: char) -> Vec<&str> {
+        let mut entries = Vec::new();
+        let mut start = 0;
+        let mut in_quotes = false;
+        for (index, c) in path.char_indices() {
+            if c == '"' {
+                in_quotes = !in_quotes;
+            } else if c == separator && !in_quotes {
+                entries.push(&path[start..index]);
+                start = index + c.len_utf8();
+            }
+        }
+        entries.push(&path[start..]);
+        entries
+    }
+
     /// Joins path entries into a single PATH string.
     pub fn join_paths(paths: &[String]) -> String {
-        paths.join(";")
+        Self::join_paths_with(paths, PATH_SEPARATOR)
+    }
+
+    /// Joins path entries using a custom separator.
+    pub fn join_paths_with(paths: &[String], separator: char) -> String {
+        paths.join(separator.encode_utf8(&mut [0; 4]))
+    }
+}
+
+/// Raw access to one `Path` registry value.
+///
+/// Lets [`RegistryHelper::write_verified`] be exercised against a fake value.
+pub trait PathValueStore {
+    fn read_path(&self) -> io::Result<String>;
+    fn write_path(&self, path: &str) -> io::Result<()>;
+    /// The `Path` value as stored, including its registry type.
+    fn read_raw(&self) -> io::Result<RegValue>;
+    fn write_raw(&self, value: &RegValue) -> io::Result<()>;
+    fn delete_path(&self) -> io::Result<()>;
+}
+
+/// The `Path` value of an open environment key.
+impl PathValueStore for RegKey {
+    fn read_path(&self) -> io::Result<String> {
+        self.get_value("Path")
+    }
+    fn write_path(&self, path: &str) -> io::Result<()> {
+        RegistryHelper::write_path_value(self, path)
+    }
+    fn read_raw(&self) -> io::Result<RegValue> {
+        self.get_raw_value("Path")
+    }
+    fn write_raw(&self, value: &RegValue) -> io::Result<()> {
+        self.set_raw_value("Path", value)
+    }
+    fn delete_path(&self) -> io::Result<()> {
+        self.delete_value("Path")
+    }
+}
+
+/// Read/write access to the USER PATH value.
+///
+/// Lets write paths be exercised against a fake store instead of the registry.
+pub trait UserPathSource {
+    fn read_user_path(&self) -> anyhow::Result<String>;
+    fn write_user_path(&self, path: &str) -> anyhow::Result<()>;
+}
+
+/// [`UserPathSource`] backed by the Windows registry.
+pub struct RegistrySource;
+
+impl UserPathSource for RegistrySource {
+    fn read_user_path(&self) -> anyhow::Result<String> {
+        Ok(RegistryHelper::read_user_path_raw()?)
+    }
+    fn write_user_path(&self, path: &str) -> anyhow::Result<()> {
+        Ok(RegistryHelper::write_user_path(path)?)
     }
 }
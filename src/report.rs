@@ -0,0 +1,147 @@
+//! Unified scan + analysis report for library embedders.
+use crate::constants::{EXIT_WORST_CRITICAL, EXIT_WORST_WARNING};
+use crate::models::{AuditStats, IssueLevel, PathEntry, PathIssue, PathLocation};
+use crate::registry::RegistryHelper;
+use crate::scanner::{
+    existence_from_issues, CombinedScanResults, PathScanner, ScanResults, FILE_ENTRY_MESSAGE,
+    WRITABLE_BY_OTHERS_MESSAGE,
+};
+use anyhow::{Context, Result};
+
+/// A PATH entry together with the scanner issues raised for it.
+#[derive(Debug, Clone)]
+pub struct AuditedEntry {
+    pub entry: PathEntry,
+    pub issues: Vec<PathIssue>,
+}
+
+/// Scan and analysis results for both PATH scopes in one structure.
+#[derive(Debug, Clone)]
+pub struct AuditReport {
+    /// SYSTEM entries first, then USER, in effective PATH order.
+    pub entries: Vec<AuditedEntry>,
+    /// Entries present in both SYSTEM and USER PATH, as written in SYSTEM.
+    pub cross_scope_duplicates: Vec<String>,
+    pub system: AuditStats,
+    pub user: AuditStats,
+    /// Combined statistics of both scopes.
+    pub summary: AuditStats,
+}
+
+impl AuditReport {
+    /// Combined health score of both scopes.
+    pub fn health_score(&self) -> u32 {
+        self.summary.health_score()
+    }
+    /// All issues across both scopes.
+    pub fn issues(&self) -> impl Iterator<Item = &PathIssue> {
+        self.entries.iter().flat_map(|e| e.issues.iter())
+    }
+    /// Most severe issue level in the report, if any issue was found.
+    pub fn worst_level(&self) -> Option<IssueLevel> {
+        self.issues().map(|issue| issue.level).max()
+    }
+    /// Exit code of `spath check`, see [`exit_code`].
+    pub fn check_exit_code(&self) -> i32 {
+        exit_code(self.worst_level())
+    }
+}
+
+/// Exit code for the most severe issue found: 0 when clean (info only),
+/// then [`EXIT_WORST_WARNING`] or [`EXIT_WORST_CRITICAL`].
+pub fn exit_code(worst: Option<IssueLevel>) -> i32 {
+    match worst {
+        Some(IssueLevel::Critical) => EXIT_WORST_CRITICAL,
+        Some(IssueLevel::Warning) => EXIT_WORST_WARNING,
+        Some(IssueLevel::Info) | None => 0,
+    }
+}
+
+/// Reads both PATH scopes from the registry and builds an [`AuditReport`].
+pub fn audit_all() -> Result<AuditReport> {
+    let system_path =
+        RegistryHelper::read_system_path_raw().context("Failed to read system PATH")?;
+    let user_path = RegistryHelper::read_user_path_raw().context("Failed to read user PATH")?;
+    build_report(&system_path, &user_path)
+}
+
+/// Builds an [`AuditReport`] from raw SYSTEM and USER PATH values.
+pub fn build_report(system_path: &str, user_path: &str) -> Result<AuditReport> {
+    let combined = CombinedScanResults::new(
+        PathScanner::from_string(user_path)
+            .with_location(PathLocation::User)
+            .scan()?,
+        PathScanner::from_string(system_path)
+            .with_location(PathLocation::System)
+            .scan()?,
+    );
+    Ok(from_scans(&combined))
+}
+
+/// Builds an [`AuditReport`] from scans of both scopes, keeping whatever
+/// scanner options, level filter or redaction the caller applied.
+pub fn from_scans(combined: &CombinedScanResults) -> AuditReport {
+    let (system, user) = (&combined.system, &combined.user);
+    let all_paths: Vec<String> = system.paths.iter().chain(&user.paths).cloned().collect();
+    let mut entries = audited_entries(system, PathLocation::System, 0, &all_paths);
+    entries.extend(audited_entries(
+        user,
+        PathLocation::User,
+        system.paths.len(),
+        &all_paths,
+    ));
+    AuditReport {
+        entries,
+        cross_scope_duplicates: combined
+            .cross_duplicates
+            .iter()
+            .map(|d| d.system_path.clone())
+            .collect(),
+        summary: system.audit.merged(&user.audit),
+        system: system.audit.clone(),
+        user: user.audit.clone(),
+    }
+}
+
+/// Attaches each issue to the entry the scanner raised it for, so identical
+/// duplicate strings keep their own issues. Existence is taken from those
+/// issues rather than checked again, so `--offline` and `--timeout` hold and
+/// redacted paths are never looked up.
+fn audited_entries(
+    results: &ScanResults,
+    location: PathLocation,
+    first_index: usize,
+    all_paths: &[String],
+) -> Vec<AuditedEntry> {
+    let counts = PathEntry::path_counts(all_paths);
+    let mut entries: Vec<AuditedEntry> = results
+        .paths
+        .iter()
+        .enumerate()
+        .map(|(i, path)| AuditedEntry {
+            entry: PathEntry::offline(path.clone(), first_index + i, location, &counts),
+            issues: Vec::new(),
+        })
+        .collect();
+    for issue in &results.issues {
+        if let Some(audited) = issue.entry.and_then(|index| entries.get_mut(index)) {
+            audited.issues.push(issue.clone());
+        }
+    }
+    for audited in &mut entries {
+        let has_issue = |message: &str| audited.issues.iter().any(|i| i.message == message);
+        audited.entry.writable_by_others = has_issue(WRITABLE_BY_OTHERS_MESSAGE);
+        let is_file = has_issue(FILE_ENTRY_MESSAGE);
+        let exists = if results.offline {
+            None
+        } else {
+            existence_from_issues(&audited.issues)
+        };
+        if let Some(exists) = exists {
+            audited.entry.exists = exists;
+            audited.entry.is_directory = exists && !is_file;
+            audited.entry.existence_checked = true;
+        }
+    }
+    entries
+}
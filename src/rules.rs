@@ -0,0 +1,175 @@
+//! Named, individually switchable lint rules for `spath lint`.
+use crate::constants::MAX_SINGLE_PATH_LENGTH;
+use crate::models::{PathEntry, PathIssue};
+use crate::scanner::{
+    DUPLICATE_PATH_MESSAGE, MISSING_PATH_MESSAGE, RELATIVE_PATH_MESSAGE, UNQUOTED_PATH_MESSAGE,
+    WRITABLE_BY_OTHERS_MESSAGE,
+};
+use crate::utils::is_absolute_path;
+use anyhow::{bail, Result};
+
+/// A check applied to each PATH entry on its own.
+pub trait Rule: Send + Sync {
+    /// Kebab-case name used in reports, `--disable-rule` and the config.
+    fn name(&self) -> &'static str;
+    fn check(&self, entry: &PathEntry) -> Option<PathIssue>;
+}
+
+/// Entries with spaces that are not quoted.
+pub struct UnquotedSpacesRule;
+
+impl Rule for UnquotedSpacesRule {
+    fn name(&self) -> &'static str {
+        "unquoted-spaces"
+    }
+    fn check(&self, entry: &PathEntry) -> Option<PathIssue> {
+        entry
+            .needs_quotes()
+            .then(|| PathIssue::warning(&entry.path, UNQUOTED_PATH_MESSAGE))
+    }
+}
+
+/// Entries that do not exist; entries built offline are never flagged.
+pub struct NonExistentRule;
+
+impl Rule for NonExistentRule {
+    fn name(&self) -> &'static str {
+        "non-existent"
+    }
+    fn check(&self, entry: &PathEntry) -> Option<PathIssue> {
+        entry
+            .is_missing()
+            .then(|| PathIssue::warning(&entry.path, MISSING_PATH_MESSAGE))
+    }
+}
+
+/// Entries that are not absolute paths.
+pub struct RelativePathRule;
+
+impl Rule for RelativePathRule {
+    fn name(&self) -> &'static str {
+        "relative"
+    }
+    fn check(&self, entry: &PathEntry) -> Option<PathIssue> {
+        (!is_absolute_path(&entry.path))
+            .then(|| PathIssue::warning(&entry.path, RELATIVE_PATH_MESSAGE))
+    }
+}
+
+/// Directories non-admin users can write to. Needs
+/// [`PathEntry::check_acl`] to have run on the entry.
+pub struct WritableByOthersRule;
+
+impl Rule for WritableByOthersRule {
+    fn name(&self) -> &'static str {
+        "writable-by-others"
+    }
+    fn check(&self, entry: &PathEntry) -> Option<PathIssue> {
+        entry
+            .writable_by_others
+            .then(|| PathIssue::critical(&entry.path, WRITABLE_BY_OTHERS_MESSAGE))
+    }
+}
+
+/// Entries longer than [`MAX_SINGLE_PATH_LENGTH`].
+pub struct TooLongRule;
+
+impl Rule for TooLongRule {
+    fn name(&self) -> &'static str {
+        "too-long"
+    }
+    fn check(&self, entry: &PathEntry) -> Option<PathIssue> {
+        (entry.path.len() > MAX_SINGLE_PATH_LENGTH).then(|| {
+            PathIssue::warning(
+                &entry.path,
+                format!("Path exceeds {} characters", MAX_SINGLE_PATH_LENGTH),
+            )
+        })
+    }
+}
+
+/// Entries that occur more than once; every occurrence is reported.
+pub struct DuplicateRule;
+
+impl Rule for DuplicateRule {
+    fn name(&self) -> &'static str {
+        "duplicate"
+    }
+    fn check(&self, entry: &PathEntry) -> Option<PathIssue> {
+        entry
+            .is_duplicate
+            .then(|| PathIssue::warning(&entry.path, DUPLICATE_PATH_MESSAGE))
+    }
+}
+
+/// All rules spath ships, in report order.
+pub fn builtin_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(UnquotedSpacesRule),
+        Box::new(NonExistentRule),
+        Box::new(RelativePathRule),
+        Box::new(WritableByOthersRule),
+        Box::new(TooLongRule),
+        Box::new(DuplicateRule),
+    ]
+}
+
+/// An issue together with the rule that raised it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    pub rule: &'static str,
+    pub issue: PathIssue,
+}
+
+/// Runs the enabled rules over PATH entries.
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self {
+            rules: builtin_rules(),
+        }
+    }
+}
+
+impl Linter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Drops the rules named in `names`; an unknown name is an error so a
+    /// typo does not silently leave a rule on.
+    pub fn with_disabled(mut self, names: &[String]) -> Result<Self> {
+        for name in names {
+            if !self.rules.iter().any(|rule| rule.name() == name) {
+                bail!(
+                    "unknown rule '{}' (expected one of: {})",
+                    name,
+                    self.rule_names().join(", ")
+                );
+            }
+        }
+        self.rules
+            .retain(|rule| !names.iter().any(|name| name == rule.name()));
+        Ok(self)
+    }
+    /// Names of the enabled rules.
+    pub fn rule_names(&self) -> Vec<&'static str> {
+        self.rules.iter().map(|rule| rule.name()).collect()
+    }
+    /// Findings for every entry, in entry order and then rule order.
+    pub fn lint(&self, entries: &[PathEntry]) -> Vec<LintFinding> {
+        entries
+            .iter()
+            .flat_map(|entry| {
+                self.rules.iter().filter_map(move |rule| {
+                    rule.check(entry).map(|issue| LintFinding {
+                        rule: rule.name(),
+                        issue,
+                    })
+                })
+            })
+            .collect()
+    }
+}
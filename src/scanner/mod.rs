@@ -1,28 +1,372 @@
 //! PATH scanner for security issues.
-use crate::constants::{PROGRAM_FILES, PROGRAM_FILES_X86, WINDOWS_PATH};
-use crate::models::{AuditStats, IssueLevel, PathIssue};
+use crate::constants::{
+    EXTENDED_PATH_PREFIX, MAX_PATH_COMPONENT_LENGTH, PARALLEL_SCAN_THRESHOLD, RESERVED_PATH_CHARS,
+};
+use crate::models::{AuditStats, IssueLevel, PathIssue, PathLocation, ValidityPolicy};
 use crate::registry::RegistryHelper;
-use crate::utils::{expand_env_vars, is_absolute_path};
-use anyhow::{Context, Result};
-use std::collections::HashSet;
+use crate::security::acl::is_writable_by_non_admin;
+use crate::security::exploits::{is_dir_writable, is_path_exploitable, primary_exploit_candidate};
+use crate::utils::{
+    expand_env_vars, expand_vars, has_overlong_component, is_absolute_path,
+    is_app_execution_alias_dir, is_current_directory_entry, is_on_excluded_drive,
+    matches_exclude_pattern, normalize_for_comparison, redact_path, run_with_timeout,
+    strip_trailing_dots_and_spaces, unc_share, ExpandResult,
+};
+use anyhow::{anyhow, Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
-fn check_path_exploitable(path: &str) -> bool {
-    let path_lower = path.to_lowercase();
-    path_lower.starts_with(PROGRAM_FILES)
-        || path_lower.starts_with(PROGRAM_FILES_X86)
-        || path_lower.starts_with(WINDOWS_PATH)
+/// Message used for duplicate entry warnings.
+pub const DUPLICATE_PATH_MESSAGE: &str = "Duplicate path entry";
+
+/// Message used when an existence check does not finish within the timeout.
+/// Entries on a UNC share get the share appended, see [`unreachable_message`].
+pub const UNREACHABLE_PATH_MESSAGE: &str = "Path unreachable (possibly offline network drive)";
+
+/// The unreachable message for `path`, naming the share for UNC entries.
+pub fn unreachable_message(path: &str) -> String {
+    match unc_share(path) {
+        Some(share) => format!("{} - network share {}", UNREACHABLE_PATH_MESSAGE, share),
+        None => UNREACHABLE_PATH_MESSAGE.to_string(),
+    }
+}
+
+/// Message used for entries that resolve to the current directory.
+pub const CURRENT_DIRECTORY_MESSAGE: &str = "Current directory on PATH is a security risk";
+
+/// Message used for unquoted entries with spaces that can be hijacked; a
+/// planted-file hint may follow.
+pub const EXPLOITABLE_PATH_MESSAGE: &str =
+    "Path contains spaces without quotes and could be exploited by creating malicious files/directories";
+
+/// Message used for quoted entries with spaces that exist.
+pub const QUOTED_PATH_MESSAGE: &str = "Path is properly quoted";
+
+/// Prefix of the message for entries with a component over the NTFS limit.
+pub const OVERLONG_COMPONENT_MESSAGE: &str = "Path component exceeds";
+
+/// Message used for entries that are not absolute paths.
+pub const RELATIVE_PATH_MESSAGE: &str = "Relative path detected - should use absolute paths";
+
+/// Opens the planted-file hint appended to exploitable-entry messages.
+const EXPLOIT_CANDIDATE_PREFIX: &str = " (attacker could place ";
+
+/// Redacts the paths inside an issue message like [`redact_path`] does for
+/// entries, keeping the closing parenthesis of a planted-file hint.
+fn redact_message(message: &str, hash_dirs: bool) -> String {
+    match message.split_once(EXPLOIT_CANDIDATE_PREFIX) {
+        Some((text, hint)) => {
+            let candidate = hint.strip_suffix(')').unwrap_or(hint);
+            format!(
+                "{}{}{})",
+                redact_path(text, hash_dirs),
+                EXPLOIT_CANDIDATE_PREFIX,
+                redact_path(candidate, hash_dirs)
+            )
+        }
+        None => redact_path(message, hash_dirs),
+    }
+}
+
+/// Message used for entries on drives excluded from the scan.
+pub const SKIPPED_PATH_MESSAGE: &str = "Skipped (drive excluded from scan)";
+
+/// Message used for entries that do not exist.
+pub const MISSING_PATH_MESSAGE: &str = "Path does not exist";
+
+/// Prefix of the message for entries that reference unset variables.
+pub const UNRESOLVED_VARS_MESSAGE: &str = "Path references unset environment variables";
+
+/// Message used for unquoted entries with spaces whose exploitability was
+/// not judged, e.g. in offline scans.
+pub const UNQUOTED_PATH_MESSAGE: &str = "Path contains spaces but is not quoted";
+
+/// Message used for unquoted entries with spaces that do not exist.
+pub const MISSING_UNQUOTED_PATH_MESSAGE: &str =
+    "Path contains spaces, is not quoted, and does not exist";
+
+/// Prefix of the message for entries containing reserved characters.
+pub const RESERVED_CHARS_MESSAGE: &str = "Path contains characters not allowed in Windows paths";
+
+pub use crate::constants::FILE_ENTRY_MESSAGE;
+
+/// Message used for directories whose DACL lets non-admin users write.
+pub const WRITABLE_BY_OTHERS_MESSAGE: &str = "Directory writable by non-admin users";
+
+/// Message used for the App Execution Alias directory.
+pub const APP_ALIAS_DIR_MESSAGE: &str =
+    "App Execution Alias directory (WindowsApps): zero-byte stubs here are expected, not broken files";
+
+/// Whether an entry exists, judged from the issues the scanner raised for
+/// it: `None` when its existence check was skipped, timed out or could not
+/// expand the entry.
+pub fn existence_from_issues<'a>(issues: impl IntoIterator<Item = &'a PathIssue>) -> Option<bool> {
+    let mut exists = Some(true);
+    for issue in issues {
+        match issue.message.as_str() {
+            SKIPPED_PATH_MESSAGE => exists = None,
+            message if message.starts_with(UNREACHABLE_PATH_MESSAGE) => exists = None,
+            message if message.starts_with(UNRESOLVED_VARS_MESSAGE) => exists = None,
+            MISSING_PATH_MESSAGE | MISSING_UNQUOTED_PATH_MESSAGE => return Some(false),
+            _ => {}
+        }
+    }
+    exists
 }
 
+/// Progress line drawn on stderr while entries are checked.
+const PROGRESS_TEMPLATE: &str = "[{pos}/{len}] Checking path...";
+
+fn path_exists(path: &Path) -> bool {
+    path.exists()
+}
+
+fn path_is_file(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct ScanResults {
     pub paths: Vec<String>,
     pub issues: Vec<PathIssue>,
     pub audit: AuditStats,
+    /// Scope the PATH was read from, if known.
+    pub location: Option<PathLocation>,
+    /// Exclude patterns active during the scan.
+    pub exclusions: Vec<String>,
+    /// Existence checks were skipped, so `audit.non_existent` is not
+    /// meaningful.
+    pub offline: bool,
+}
+
+/// Order in which scan issues are listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueSort {
+    /// Case-insensitive alphabetical order of the bare path.
+    Name,
+    /// Longest path first.
+    Length,
+    /// Critical, then Warning, then Info.
+    Status,
+}
+
+/// All occurrences of one normalized path in the scanned PATH.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    pub normalized: String,
+    /// Indices of every occurrence, in PATH order.
+    pub indices: Vec<usize>,
+    /// Index of the occurrence that `spath fix` keeps (the first one).
+    pub kept_index: usize,
+}
+
+impl ScanResults {
+    /// Most severe issue level in the results, if any issue was found.
+    pub fn worst_level(&self) -> Option<IssueLevel> {
+        self.issues.iter().map(|issue| issue.level).max()
+    }
+    /// Copy of the results keeping only issues at `min_level` or above.
+    pub fn with_min_level(&self, min_level: IssueLevel) -> ScanResults {
+        let mut results = self.clone();
+        results.issues.retain(|issue| issue.level >= min_level);
+        results
+    }
+    /// Copy of the results keeping only issues whose `(path, level,
+    /// message)` does not appear in `baseline`; repeated identical issues
+    /// are matched one-to-one. Paths and statistics are unchanged.
+    pub fn new_since(&self, baseline: &ScanResults) -> ScanResults {
+        let current: Vec<&PathIssue> = self.issues.iter().collect();
+        let before: Vec<&PathIssue> = baseline.issues.iter().collect();
+        let mut results = self.clone();
+        results.issues = IssueDiff::subtract(&current, &before);
+        results
+    }
+    /// The issues in `sort` order, or in scan order without one. The sort is
+    /// stable, so issues with equal keys keep their scan order.
+    pub fn sorted_issues(&self, sort: Option<IssueSort>) -> Vec<&PathIssue> {
+        let mut issues: Vec<&PathIssue> = self.issues.iter().collect();
+        let bare = |issue: &PathIssue| issue.path.trim().trim_matches('"').to_string();
+        match sort {
+            None => {}
+            Some(IssueSort::Name) => issues.sort_by_cached_key(|i| bare(i).to_lowercase()),
+            Some(IssueSort::Length) => {
+                issues.sort_by_cached_key(|i| std::cmp::Reverse(bare(i).chars().count()))
+            }
+            Some(IssueSort::Status) => issues.sort_by_key(|i| std::cmp::Reverse(i.level)),
+        }
+        issues
+    }
+    /// Returns a copy with user names (and optionally custom directory names)
+    /// redacted from every displayed string. Counts and levels are unchanged.
+    pub fn redacted(&self, hash_dirs: bool) -> ScanResults {
+        let mut results = self.clone();
+        for path in &mut results.paths {
+            *path = redact_path(path, hash_dirs);
+        }
+        for issue in &mut results.issues {
+            issue.path = redact_path(&issue.path, hash_dirs);
+            issue.message = redact_message(&issue.message, hash_dirs);
+        }
+        results
+    }
+    /// Groups duplicate entries by their normalized path.
+    pub fn duplicate_groups(&self) -> Vec<DuplicateGroup> {
+        let mut order: Vec<String> = Vec::new();
+        let mut occurrences: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, path) in self.paths.iter().enumerate() {
+            let normalized = normalize_for_comparison(path);
+            let indices = occurrences.entry(normalized.clone()).or_default();
+            if indices.is_empty() {
+                order.push(normalized);
+            }
+            indices.push(index);
+        }
+        order
+            .into_iter()
+            .filter_map(|normalized| {
+                let indices = occurrences.remove(&normalized)?;
+                if indices.len() < 2 {
+                    return None;
+                }
+                Some(DuplicateGroup {
+                    kept_index: indices[0],
+                    normalized,
+                    indices,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Issues that appeared or were resolved between two scans.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IssueDiff {
+    pub added: Vec<PathIssue>,
+    pub resolved: Vec<PathIssue>,
+    /// Critical issues present in both scans.
+    pub remaining_critical: usize,
+}
+
+impl IssueDiff {
+    /// Compares the issues of two sets of scans; repeated identical issues
+    /// are matched one-to-one.
+    pub fn between(previous: &[ScanResults], current: &[ScanResults]) -> Self {
+        let before: Vec<&PathIssue> = previous.iter().flat_map(|r| &r.issues).collect();
+        let after: Vec<&PathIssue> = current.iter().flat_map(|r| &r.issues).collect();
+        let added = Self::subtract(&after, &before);
+        let new_critical = added
+            .iter()
+            .filter(|i| i.level == IssueLevel::Critical)
+            .count();
+        let critical = after
+            .iter()
+            .filter(|i| i.level == IssueLevel::Critical)
+            .count();
+        Self {
+            resolved: Self::subtract(&before, &after),
+            added,
+            remaining_critical: critical - new_critical,
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.resolved.is_empty()
+    }
+    fn subtract(from: &[&PathIssue], other: &[&PathIssue]) -> Vec<PathIssue> {
+        let mut unmatched: Vec<&PathIssue> = other.to_vec();
+        from.iter()
+            .filter(|issue| match unmatched.iter().position(|o| o == *issue) {
+                Some(index) => {
+                    unmatched.swap_remove(index);
+                    false
+                }
+                None => true,
+            })
+            .map(|issue| (*issue).clone())
+            .collect()
+    }
+}
+
+/// A path present in both SYSTEM and USER PATH.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CrossDuplicate {
+    /// The entry as written in SYSTEM PATH.
+    pub system_path: String,
+    /// The entry as written in USER PATH.
+    pub user_path: String,
+    pub system_index: usize,
+    pub user_index: usize,
+}
+
+impl CrossDuplicate {
+    /// Returns a copy with both paths redacted, as [`ScanResults::redacted`].
+    pub fn redacted(&self, hash_dirs: bool) -> CrossDuplicate {
+        CrossDuplicate {
+            system_path: redact_path(&self.system_path, hash_dirs),
+            user_path: redact_path(&self.user_path, hash_dirs),
+            ..self.clone()
+        }
+    }
+}
+
+/// USER and SYSTEM scan results with the entries shared by both scopes.
+#[derive(Debug, Clone, Serialize)]
+pub struct CombinedScanResults {
+    pub user: ScanResults,
+    pub system: ScanResults,
+    pub cross_duplicates: Vec<CrossDuplicate>,
+}
+
+impl CombinedScanResults {
+    /// Cross-references two scans. Each shared path is reported once, at its
+    /// first occurrence in each scope.
+    pub fn new(user: ScanResults, system: ScanResults) -> Self {
+        let mut user_indices: HashMap<String, usize> = HashMap::new();
+        for (index, path) in user.paths.iter().enumerate() {
+            user_indices
+                .entry(normalize_for_comparison(path))
+                .or_insert(index);
+        }
+        let mut reported = HashSet::new();
+        let cross_duplicates = system
+            .paths
+            .iter()
+            .enumerate()
+            .filter_map(|(system_index, system_path)| {
+                let key = normalize_for_comparison(system_path);
+                let user_index = *user_indices.get(&key)?;
+                reported.insert(key).then(|| CrossDuplicate {
+                    system_path: system_path.clone(),
+                    user_path: user.paths[user_index].clone(),
+                    system_index,
+                    user_index,
+                })
+            })
+            .collect();
+        Self {
+            user,
+            system,
+            cross_duplicates,
+        }
+    }
 }
 
 pub struct PathScanner {
     path_var: String,
+    location: Option<PathLocation>,
+    timeout: Option<Duration>,
+    exists_check: fn(&Path) -> bool,
+    writable_check: fn(&Path) -> bool,
+    excluded_drives: Vec<char>,
+    excluded_patterns: Vec<String>,
+    validity: ValidityPolicy,
+    offline: bool,
+    progress: bool,
+    parallel: Option<bool>,
 }
 
 impl PathScanner {
@@ -34,20 +378,160 @@ impl PathScanner {
             RegistryHelper::read_user_path_raw()
                 .context("Failed to read USER PATH from registry")?
         };
-        Ok(Self { path_var })
+        let location = if scan_system {
+            PathLocation::System
+        } else {
+            PathLocation::User
+        };
+        Ok(Self::from_string(path_var).with_location(location))
+    }
+    /// Scans USER and SYSTEM PATH from the registry and cross-references them.
+    pub fn scan_both() -> Result<CombinedScanResults> {
+        Self::scan_both_with(|scanner| scanner)
+    }
+    /// Like [`PathScanner::scan_both`], applying `configure` to both scanners.
+    pub fn scan_both_with(configure: impl Fn(Self) -> Self) -> Result<CombinedScanResults> {
+        let user = configure(Self::new(false)?).scan()?;
+        let system = configure(Self::new(true)?).scan()?;
+        Ok(CombinedScanResults::new(user, system))
+    }
+    /// Scans an arbitrary PATH value with the default settings, without
+    /// touching the registry.
+    pub fn scan_custom(path_string: &str) -> Result<ScanResults> {
+        Self::from_string(path_string).scan()
+    }
+    /// Creates a scanner for an arbitrary PATH value instead of the registry.
+    pub fn from_string(path_var: impl Into<String>) -> Self {
+        Self {
+            path_var: path_var.into(),
+            location: None,
+            timeout: None,
+            exists_check: path_exists,
+            writable_check: is_dir_writable,
+            excluded_drives: Vec::new(),
+            excluded_patterns: Vec::new(),
+            validity: ValidityPolicy::default(),
+            offline: false,
+            progress: false,
+            parallel: None,
+        }
+    }
+    /// Replaces the check for whether non-admin users can plant files in a
+    /// directory, which decides if an unquoted path is exploitable.
+    pub fn with_writable_check(mut self, writable_check: fn(&Path) -> bool) -> Self {
+        self.writable_check = writable_check;
+        self
+    }
+    /// Sets the criteria used to count valid paths in the audit.
+    pub fn with_validity_policy(mut self, validity: ValidityPolicy) -> Self {
+        self.validity = validity;
+        self
+    }
+    /// Skips existence checks for entries on the given drive letters.
+    pub fn with_excluded_drives(mut self, drives: impl IntoIterator<Item = char>) -> Self {
+        self.excluded_drives = drives.into_iter().collect();
+        self
+    }
+    /// Silently skips entries matching any of the wildcard `patterns`: they
+    /// get no issues, take no part in duplicate detection and are only
+    /// counted in `audit.excluded`.
+    pub fn with_excluded_patterns(mut self, patterns: impl IntoIterator<Item = String>) -> Self {
+        self.excluded_patterns = patterns.into_iter().collect();
+        self
+    }
+    /// Bounds each existence check; entries that take longer are reported
+    /// as unreachable instead of non-existent.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// Skips every filesystem check: entries are judged on their structure
+    /// only (quoting, duplicates, relative paths, reserved characters) and
+    /// none is reported as missing. Combined with
+    /// [`PathScanner::from_string`] the scan never touches the filesystem.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+    /// Shows a progress bar on stderr while entries are checked. It is
+    /// cleared when the scan ends and never drawn when stderr is not a
+    /// terminal.
+    pub fn with_progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+    /// Checks entries in parallel (`true`) or one after another (`false`).
+    /// Without a choice, PATHs longer than [`PARALLEL_SCAN_THRESHOLD`] are
+    /// checked in parallel. Results are in PATH order either way.
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = Some(parallel);
+        self
+    }
+    /// Replaces the filesystem existence check (used to simulate slow paths).
+    pub fn with_existence_check(mut self, exists_check: fn(&Path) -> bool) -> Self {
+        self.exists_check = exists_check;
+        self
+    }
+    /// Tags the results with the scope the PATH came from.
+    pub fn with_location(mut self, location: PathLocation) -> Self {
+        self.location = Some(location);
+        self
+    }
+    /// Creates a scanner from piped input.
+    ///
+    /// With `raw` the input is a complete semicolon-joined PATH value,
+    /// otherwise it is read as one entry per line.
+    pub fn from_input(input: &str, raw: bool) -> Self {
+        if raw {
+            return Self::from_string(input.trim());
+        }
+        let entries: Vec<String> = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect();
+        Self::from_string(RegistryHelper::join_paths(&entries))
     }
     pub fn scan(&self) -> Result<ScanResults> {
         info!("Starting PATH scan");
         let paths = RegistryHelper::parse_path_string(&self.path_var);
         debug!("Found {} path entries to scan", paths.len());
-        let mut issues = Vec::new();
-        let mut audit = AuditStats {
-            total_paths: paths.len(),
-            ..Default::default()
+        // Entries are checked in parallel on long PATHs (existence checks can
+        // block on slow drives); the indexed collect keeps results in PATH
+        // order, and duplicates are flagged afterwards since they depend on
+        // earlier entries.
+        let progress = self.progress_bar(paths.len());
+        let scan_entry = |path: &String| {
+            let entry = self.scan_entry(path);
+            progress.inc(1);
+            entry
+        };
+        let parallel = self
+            .parallel
+            .unwrap_or(paths.len() > PARALLEL_SCAN_THRESHOLD);
+        let per_entry: Vec<Option<(Vec<PathIssue>, AuditStats)>> = if parallel {
+            paths.par_iter().map(scan_entry).collect()
+        } else {
+            paths.iter().map(scan_entry).collect()
         };
+        progress.finish_and_clear();
+        let mut issues = Vec::new();
+        let mut audit = AuditStats::default();
         let mut seen = HashSet::new();
-        for path in &paths {
-            self.scan_single_path(path, &mut issues, &mut audit, &mut seen);
+        for (index, (path, entry)) in paths.iter().zip(per_entry).enumerate() {
+            let Some((entry_issues, entry_audit)) = entry else {
+                audit.excluded += 1;
+                continue;
+            };
+            audit.total_paths += 1;
+            let first = issues.len();
+            self.check_duplicate(path, path.trim(), &mut issues, &mut seen);
+            issues.extend(entry_issues);
+            for issue in &mut issues[first..] {
+                issue.entry = Some(index);
+            }
+            audit = audit.merged(&entry_audit);
         }
         info!(
             "Scan completed: {} issues found, {} critical",
@@ -61,47 +545,149 @@ impl PathScanner {
             paths,
             issues,
             audit,
+            location: self.location,
+            exclusions: self.excluded_patterns.clone(),
+            offline: self.offline,
         })
     }
-    fn scan_single_path(
-        &self,
-        path: &str,
-        issues: &mut Vec<PathIssue>,
-        audit: &mut AuditStats,
-        seen: &mut HashSet<String>,
-    ) {
+    /// Issues and statistics for one entry, or `None` when it matches an
+    /// exclude pattern.
+    fn scan_entry(&self, path: &str) -> Option<(Vec<PathIssue>, AuditStats)> {
+        if self.is_excluded(path) {
+            debug!("Skipping entry matching an exclude pattern: {}", path);
+            return None;
+        }
+        let mut issues = Vec::new();
+        let mut audit = AuditStats::default();
+        self.scan_single_path(path, &mut issues, &mut audit);
+        Some((issues, audit))
+    }
+    fn progress_bar(&self, len: usize) -> ProgressBar {
+        if !self.progress {
+            return ProgressBar::hidden();
+        }
+        let style = ProgressStyle::with_template(PROGRESS_TEMPLATE)
+            .unwrap_or_else(|_| ProgressStyle::default_bar());
+        ProgressBar::new(len as u64).with_style(style)
+    }
+    fn is_excluded(&self, path: &str) -> bool {
+        self.excluded_patterns
+            .iter()
+            .any(|pattern| matches_exclude_pattern(path, pattern))
+    }
+    fn scan_single_path(&self, path: &str, issues: &mut Vec<PathIssue>, audit: &mut AuditStats) {
         let trimmed = path.trim();
-        let has_spaces = trimmed.contains(' ');
+        // %VAR% entries are judged by what they expand to: %ProgramFiles%\App
+        // has no literal space but is just as exploitable unquoted.
+        let expansion = expand_vars(trimmed);
+        let expanded = &expansion.expanded;
+        let has_spaces = expanded.contains(' ');
         let is_quoted = trimmed.starts_with('"');
         let path_to_check = self.resolve_path(trimmed);
-        let exists = Path::new(&path_to_check).exists();
         let is_absolute = is_absolute_path(trimmed);
-        self.update_audit_stats(audit, has_spaces, is_quoted, exists, is_absolute, trimmed);
-        self.check_duplicate(path, trimmed, issues, seen);
-        self.check_unquoted_spaces(path, trimmed, has_spaces, is_quoted, exists, issues);
-        self.check_existence(path, exists, issues);
+        if is_app_execution_alias_dir(trimmed) {
+            debug!("App Execution Alias directory on PATH: {}", trimmed);
+            self.update_audit_stats(
+                audit,
+                has_spaces,
+                is_quoted,
+                Some(true),
+                is_absolute,
+                trimmed,
+            );
+            issues.push(PathIssue::info(path, APP_ALIAS_DIR_MESSAGE));
+            return;
+        }
+        if is_on_excluded_drive(trimmed, &self.excluded_drives) {
+            debug!("Skipping entry on excluded drive: {}", trimmed);
+            audit.skipped += 1;
+            issues.push(PathIssue::info(path, SKIPPED_PATH_MESSAGE));
+            return;
+        }
+        if self.offline {
+            self.update_audit_stats(audit, has_spaces, is_quoted, None, is_absolute, trimmed);
+            if self.check_current_directory(path, trimmed, issues) {
+                return;
+            }
+            if has_spaces && !is_quoted {
+                issues.push(PathIssue::warning(path, UNQUOTED_PATH_MESSAGE));
+            }
+            self.check_component_length(path, trimmed, issues);
+            self.check_relative_path(path, is_absolute, trimmed, issues);
+            self.check_reserved_chars(path, trimmed, issues);
+            return;
+        }
+        let Some(exists) = self.check_exists(&path_to_check) else {
+            warn!("Existence check timed out: {}", trimmed);
+            audit.unreachable += 1;
+            issues.push(PathIssue::warning(path, unreachable_message(trimmed)));
+            self.check_relative_path(path, is_absolute, trimmed, issues);
+            return;
+        };
+        self.update_audit_stats(
+            audit,
+            has_spaces,
+            is_quoted,
+            Some(exists),
+            is_absolute,
+            trimmed,
+        );
+        if self.check_current_directory(path, trimmed, issues) {
+            return;
+        }
+        self.check_unquoted_spaces(path, expanded, has_spaces, is_quoted, exists, issues);
+        self.check_existence(path, exists, &expansion, issues);
+        self.check_is_directory(path, &path_to_check, exists, issues);
+        self.check_writable_by_others(path, &path_to_check, exists, issues);
+        self.check_component_length(path, trimmed, issues);
         self.check_relative_path(path, is_absolute, trimmed, issues);
+        self.check_reserved_chars(path, trimmed, issues);
     }
-    fn resolve_path(&self, trimmed: &str) -> String {
-        if trimmed.contains('%') {
-            expand_env_vars(trimmed)
-        } else {
-            trimmed.trim_matches('"').to_string()
+    /// Flags a current-directory entry; no other check applies to it.
+    fn check_current_directory(
+        &self,
+        path: &str,
+        trimmed: &str,
+        issues: &mut Vec<PathIssue>,
+    ) -> bool {
+        if !is_current_directory_entry(trimmed) {
+            return false;
         }
+        warn!("Current directory entry found on PATH: {:?}", path);
+        issues.push(PathIssue::critical(path, CURRENT_DIRECTORY_MESSAGE));
+        true
+    }
+    fn check_exists(&self, path_to_check: &str) -> Option<bool> {
+        self.run_check(path_to_check, self.exists_check)
+    }
+    /// Runs a filesystem check, bounded by the scan timeout if one is set.
+    fn run_check(&self, path_to_check: &str, check: fn(&Path) -> bool) -> Option<bool> {
+        match self.timeout {
+            None => Some(check(Path::new(path_to_check))),
+            Some(timeout) => {
+                let path = path_to_check.to_string();
+                run_with_timeout(move || check(Path::new(&path)), timeout)
+            }
+        }
+    }
+    fn resolve_path(&self, trimmed: &str) -> String {
+        strip_trailing_dots_and_spaces(&expand_env_vars(trimmed.trim_matches('"')))
     }
+    /// `exists` is `None` when existence was not checked; such entries are
+    /// neither counted as non-existent nor held to the existence rule.
     fn update_audit_stats(
         &self,
         audit: &mut AuditStats,
         has_spaces: bool,
         is_quoted: bool,
-        exists: bool,
+        exists: Option<bool>,
         is_absolute: bool,
         trimmed: &str,
     ) {
         if has_spaces && !is_quoted {
             audit.unquoted_with_spaces += 1;
         }
-        if !exists {
+        if exists == Some(false) {
             audit.non_existent += 1;
         }
         if !is_absolute && !trimmed.is_empty() {
@@ -110,7 +696,10 @@ impl PathScanner {
         if has_spaces && is_quoted {
             audit.properly_quoted += 1;
         }
-        if exists && is_absolute && (!has_spaces || is_quoted) {
+        if self
+            .validity
+            .is_valid(exists != Some(false), is_absolute, has_spaces, is_quoted)
+        {
             audit.valid_paths += 1;
         }
     }
@@ -121,15 +710,14 @@ impl PathScanner {
         issues: &mut Vec<PathIssue>,
         seen: &mut HashSet<String>,
     ) {
-        if seen.contains(trimmed) {
-            issues.push(PathIssue::warning(path, "Duplicate path entry"));
+        if !seen.insert(normalize_for_comparison(trimmed)) {
+            issues.push(PathIssue::warning(path, DUPLICATE_PATH_MESSAGE));
         }
-        seen.insert(trimmed.to_string());
     }
     fn check_unquoted_spaces(
         &self,
         path: &str,
-        trimmed: &str,
+        expanded: &str,
         has_spaces: bool,
         is_quoted: bool,
         exists: bool,
@@ -137,27 +725,107 @@ impl PathScanner {
     ) {
         if !has_spaces || is_quoted {
             if has_spaces && is_quoted && exists {
-                issues.push(PathIssue::info(path, "Path is properly quoted"));
+                issues.push(PathIssue::info(path, QUOTED_PATH_MESSAGE));
             }
             return;
         }
         if exists {
-            if check_path_exploitable(trimmed) {
-                warn!("Critical security issue found: {}", trimmed);
-                issues.push(PathIssue::critical(path, "Path contains spaces without quotes and could be exploited by creating malicious files/directories"));
+            if is_path_exploitable(expanded, self.writable_check) {
+                warn!("Critical security issue found: {}", expanded);
+                let mut message = EXPLOITABLE_PATH_MESSAGE.to_string();
+                if let Some(candidate) = primary_exploit_candidate(expanded) {
+                    message.push_str(&format!("{}{})", EXPLOIT_CANDIDATE_PREFIX, candidate));
+                }
+                issues.push(PathIssue::critical(path, message));
             } else {
-                issues.push(PathIssue::info(path, "Path contains spaces but is not quoted. Consider adding quotes for better compatibility."));
+                issues.push(PathIssue::info(
+                    path,
+                    format!(
+                        "{}. Consider adding quotes for better compatibility.",
+                        UNQUOTED_PATH_MESSAGE
+                    ),
+                ));
             }
         } else {
+            issues.push(PathIssue::warning(path, MISSING_UNQUOTED_PATH_MESSAGE));
+        }
+    }
+    /// An entry with unset variables is reported as such rather than as
+    /// missing: it may well exist for the user whose environment sets them.
+    fn check_existence(
+        &self,
+        path: &str,
+        exists: bool,
+        expansion: &ExpandResult,
+        issues: &mut Vec<PathIssue>,
+    ) {
+        if exists {
+            return;
+        }
+        if expansion.is_complete() {
+            issues.push(PathIssue::warning(path, MISSING_PATH_MESSAGE));
+        } else {
+            let names: Vec<String> = expansion
+                .unresolved
+                .iter()
+                .map(|n| format!("%{}%", n))
+                .collect();
             issues.push(PathIssue::warning(
                 path,
-                "Path contains spaces, is not quoted, and does not exist",
+                format!("{}: {}", UNRESOLVED_VARS_MESSAGE, names.join(", ")),
             ));
         }
     }
-    fn check_existence(&self, path: &str, exists: bool, issues: &mut Vec<PathIssue>) {
-        if !exists {
-            issues.push(PathIssue::warning(path, "Path does not exist"));
+    /// Flags existing entries that are files. A check that times out is not
+    /// reported, since the entry was already found to exist.
+    fn check_is_directory(
+        &self,
+        path: &str,
+        path_to_check: &str,
+        exists: bool,
+        issues: &mut Vec<PathIssue>,
+    ) {
+        if exists && self.run_check(path_to_check, path_is_file) == Some(true) {
+            issues.push(PathIssue::warning(path, FILE_ENTRY_MESSAGE));
+        }
+    }
+    fn check_writable_by_others(
+        &self,
+        path: &str,
+        path_to_check: &str,
+        exists: bool,
+        issues: &mut Vec<PathIssue>,
+    ) {
+        let dir = Path::new(path_to_check);
+        if !exists || !dir.is_dir() {
+            return;
+        }
+        let writable = match self.timeout {
+            None => is_writable_by_non_admin(dir),
+            Some(timeout) => {
+                let dir = dir.to_path_buf();
+                run_with_timeout(move || is_writable_by_non_admin(&dir), timeout)
+                    .unwrap_or_else(|| Err(anyhow!("DACL read timed out")))
+            }
+        };
+        match writable {
+            Ok(true) => {
+                warn!("Directory writable by non-admin users: {}", path_to_check);
+                issues.push(PathIssue::critical(path, WRITABLE_BY_OTHERS_MESSAGE));
+            }
+            Ok(false) => {}
+            Err(e) => debug!("Skipping ACL check for {}: {:#}", path_to_check, e),
+        }
+    }
+    fn check_component_length(&self, path: &str, trimmed: &str, issues: &mut Vec<PathIssue>) {
+        if has_overlong_component(trimmed) {
+            issues.push(PathIssue::warning(
+                path,
+                format!(
+                    "{} the {}-character NTFS limit and cannot exist",
+                    OVERLONG_COMPONENT_MESSAGE, MAX_PATH_COMPONENT_LENGTH
+                ),
+            ));
         }
     }
     fn check_relative_path(
@@ -168,10 +836,38 @@ impl PathScanner {
         issues: &mut Vec<PathIssue>,
     ) {
         if !is_absolute && !trimmed.is_empty() {
-            issues.push(PathIssue::warning(
-                path,
-                "Relative path detected - should use absolute paths",
-            ));
+            issues.push(PathIssue::warning(path, RELATIVE_PATH_MESSAGE));
+        }
+    }
+    /// Flags reserved characters, naming each one. Only interior quotes
+    /// count, and the `?` of an extended-length prefix is allowed.
+    fn check_reserved_chars(&self, path: &str, trimmed: &str, issues: &mut Vec<PathIssue>) {
+        let inner = trimmed.strip_prefix('"').unwrap_or(trimmed);
+        let inner = inner.strip_suffix('"').unwrap_or(inner);
+        let inner = inner.strip_prefix(EXTENDED_PATH_PREFIX).unwrap_or(inner);
+        let mut found: Vec<char> = Vec::new();
+        for c in inner.chars() {
+            if (RESERVED_PATH_CHARS.contains(&c) || c.is_control()) && !found.contains(&c) {
+                found.push(c);
+            }
+        }
+        if found.is_empty() {
+            return;
         }
+        let names: Vec<String> = found
+            .iter()
+            .map(|c| {
+                if c.is_control() {
+                    format!("U+{:04X}", *c as u32)
+                } else {
+                    format!("'{}'", c)
+                }
+            })
+            .collect();
+        warn!("Reserved characters in PATH entry: {:?}", path);
+        issues.push(PathIssue::warning(
+            path,
+            format!("{}: {}", RESERVED_CHARS_MESSAGE, names.join(", ")),
+        ));
     }
 }
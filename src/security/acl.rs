@@ -5,12 +5,14 @@ use tracing::{debug, info};
 use windows::core::PCWSTR;
 use windows::Win32::Foundation::{LocalFree, HLOCAL, PSID};
 use windows::Win32::Security::Authorization::{
-    SetEntriesInAclW, SetNamedSecurityInfoW, EXPLICIT_ACCESS_W, SET_ACCESS, SE_FILE_OBJECT,
-    TRUSTEE_IS_SID, TRUSTEE_TYPE, TRUSTEE_W,
+    GetNamedSecurityInfoW, SetEntriesInAclW, SetNamedSecurityInfoW, EXPLICIT_ACCESS_W, SET_ACCESS,
+    SE_FILE_OBJECT, TRUSTEE_IS_SID, TRUSTEE_TYPE, TRUSTEE_W,
 };
 use windows::Win32::Security::{
-    GetTokenInformation, TokenUser, ACE_FLAGS, ACL, DACL_SECURITY_INFORMATION,
-    PROTECTED_DACL_SECURITY_INFORMATION, TOKEN_QUERY, TOKEN_USER,
+    GetAce, GetTokenInformation, IsWellKnownSid, TokenUser, WinAuthenticatedUserSid,
+    WinBuiltinUsersSid, WinWorldSid, ACCESS_ALLOWED_ACE, ACE_FLAGS, ACL, DACL_SECURITY_INFORMATION,
+    INHERIT_ONLY_ACE, PROTECTED_DACL_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR, TOKEN_QUERY,
+    TOKEN_USER, WELL_KNOWN_SID_TYPE,
 };
 use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
 
@@ -71,6 +73,83 @@ pub fn set_user_only_acl(path: &Path) -> Result<()> {
     Ok(())
 }
 
+const ACCESS_ALLOWED_ACE_TYPE: u8 = 0;
+const ACCESS_DENIED_ACE_TYPE: u8 = 1;
+
+/// Rights that let a trustee plant files in a directory or take it over:
+/// add file, add subdirectory, write DACL, take ownership, generic write/all.
+const DIRECTORY_WRITE_MASK: u32 =
+    0x0000_0002 | 0x0000_0004 | 0x0004_0000 | 0x0008_0000 | 0x4000_0000 | 0x1000_0000;
+
+/// Groups every interactive non-admin account belongs to.
+const BROAD_SIDS: [WELL_KNOWN_SID_TYPE; 3] =
+    [WinWorldSid, WinAuthenticatedUserSid, WinBuiltinUsersSid];
+
+/// Checks whether Everyone, Authenticated Users or `BUILTIN\Users` may
+/// write to `path`.
+///
+/// Reads the DACL and applies its ACEs for those groups in order, so a
+/// deny ACE before an allow ACE wins. Inherit-only ACEs are ignored
+/// because they do not apply to the directory itself. A NULL DACL grants
+/// everyone full access.
+pub fn is_writable_by_non_admin(path: &Path) -> Result<bool> {
+    debug!("Checking DACL of: {}", path.display());
+    let path_wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut dacl: *mut ACL = std::ptr::null_mut();
+    let mut descriptor = PSECURITY_DESCRIPTOR::default();
+    unsafe {
+        GetNamedSecurityInfoW(
+            PCWSTR(path_wide.as_ptr()),
+            SE_FILE_OBJECT,
+            DACL_SECURITY_INFORMATION,
+            None,
+            None,
+            Some(&mut dacl),
+            None,
+            &mut descriptor,
+        )
+        .with_context(|| format!("Failed to read DACL of {}", path.display()))?;
+        let writable = dacl.is_null() || grants_write_to_broad_sids(dacl);
+        let _ = LocalFree(HLOCAL(descriptor.0));
+        Ok(writable)
+    }
+}
+
+/// # Safety
+/// `dacl` must point to a valid ACL.
+unsafe fn grants_write_to_broad_sids(dacl: *const ACL) -> bool {
+    let mut allowed = 0u32;
+    let mut denied = 0u32;
+    for index in 0..u32::from((*dacl).AceCount) {
+        let mut ace: *mut std::ffi::c_void = std::ptr::null_mut();
+        if GetAce(dacl, index, &mut ace).is_err() {
+            continue;
+        }
+        // Allow and deny ACEs share the ACCESS_ALLOWED_ACE layout.
+        let ace = &*(ace as *const ACCESS_ALLOWED_ACE);
+        if u32::from(ace.Header.AceFlags) & INHERIT_ONLY_ACE.0 != 0 {
+            continue;
+        }
+        let sid = PSID(&ace.SidStart as *const u32 as *mut _);
+        if !BROAD_SIDS
+            .iter()
+            .any(|&kind| IsWellKnownSid(sid, kind).as_bool())
+        {
+            continue;
+        }
+        match ace.Header.AceType {
+            ACCESS_ALLOWED_ACE_TYPE => allowed |= ace.Mask & !denied,
+            ACCESS_DENIED_ACE_TYPE => denied |= ace.Mask,
+            _ => {}
+        }
+    }
+    allowed & DIRECTORY_WRITE_MASK != 0
+}
+
 /// Gets the SID of the current user.
 /// Returns a buffer containing the TOKEN_USER structure and the SID.
 /// The PSID points into this buffer, so the buffer must be kept alive.
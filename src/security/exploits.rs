@@ -1,11 +1,19 @@
 //! PATH vulnerability exploitability verification.
-use std::path::Path;
+use crate::models::{IssueLevel, PathIssue};
+use crate::security::acl::is_writable_by_non_admin;
+use crate::utils::{expand_env_vars, normalize_for_comparison};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
 
 #[derive(Debug, Clone)]
 pub struct ExploitCheckResult {
     pub path: String,
     pub is_exploitable: bool,
     pub found_exploits: Vec<String>,
+    /// Set when a warning-level entry turned out to be exploitable, i.e.
+    /// it should be treated as critical.
+    pub severity_adjusted: bool,
 }
 
 impl ExploitCheckResult {
@@ -14,6 +22,7 @@ impl ExploitCheckResult {
             path: path.into(),
             is_exploitable: false,
             found_exploits: Vec::new(),
+            severity_adjusted: false,
         }
     }
     pub fn exploitable(path: impl Into<String>, exploits: Vec<String>) -> Self {
@@ -21,6 +30,7 @@ impl ExploitCheckResult {
             path: path.into(),
             is_exploitable: true,
             found_exploits: exploits,
+            severity_adjusted: false,
         }
     }
 }
@@ -56,6 +66,60 @@ pub fn generate_exploit_paths(path: &str) -> Vec<String> {
     exploits
 }
 
+/// Returns the first file an attacker could plant to hijack an unquoted path.
+///
+/// Windows tries the text before the first space as an executable, so
+/// `C:\Program Files\Git\cmd` is first resolved as `C:\Program.exe`.
+pub fn primary_exploit_candidate(path: &str) -> Option<String> {
+    let clean_path = path.trim_matches('"');
+    let space = clean_path.find(' ')?;
+    let prefix = &clean_path[..space];
+    if prefix.is_empty() || prefix.ends_with('\\') {
+        return None;
+    }
+    Some(format!("{}{}", prefix, EXPLOIT_EXTENSIONS[0]))
+}
+
+/// Directories an attacker would have to write to for the exploit files
+/// of an unquoted `path`.
+///
+/// `D:\My Tools\bin` is first tried as `D:\My.exe`, so the planted file
+/// lands in `D:\`, the directory preceding the component with the space.
+pub fn exploit_target_dirs(path: &str) -> Vec<PathBuf> {
+    let clean_path = path.trim_matches('"');
+    let parts: Vec<&str> = clean_path.split('\\').collect();
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for i in 1..parts.len().saturating_sub(1) {
+        if !parts[i].contains(' ') {
+            continue;
+        }
+        let mut dir = parts[..i].join("\\");
+        if dir.ends_with(':') {
+            dir.push('\\');
+        }
+        let dir = PathBuf::from(dir);
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// Whether non-admin users may plant files in `dir`. A DACL that cannot be
+/// read counts as not writable.
+pub fn is_dir_writable(dir: &Path) -> bool {
+    is_writable_by_non_admin(dir).unwrap_or_else(|e| {
+        debug!("Could not check DACL of {}: {:#}", dir.display(), e);
+        false
+    })
+}
+
+/// Whether an unquoted `path` could be hijacked, i.e. `writable` holds for
+/// one of its [`exploit_target_dirs`]. Shared by `scan` and `verify`.
+pub fn is_path_exploitable(path: &str, writable: fn(&Path) -> bool) -> bool {
+    exploit_target_dirs(path).iter().any(|dir| writable(dir))
+}
+
 pub fn check_exploitability(path: &str) -> ExploitCheckResult {
     let exploit_paths = generate_exploit_paths(path);
     let found_exploits: Vec<String> = exploit_paths
@@ -86,3 +150,38 @@ pub fn verify_paths(paths: &[&str]) -> (Vec<ExploitCheckResult>, VerificationSum
     }
     (results, summary)
 }
+
+/// Verifies the distinct paths behind `issues` that [`is_path_exploitable`]
+/// accepts under `writable`, the same test `scan` uses for critical issues.
+/// `%VAR%` references are expanded first, so results name expanded paths.
+///
+/// Each path is checked once at its most severe level; a warning-level
+/// path with exploit files present is marked `severity_adjusted`.
+pub fn verify_issues(
+    issues: &[&PathIssue],
+    writable: fn(&Path) -> bool,
+) -> (Vec<ExploitCheckResult>, VerificationSummary) {
+    let mut order: Vec<String> = Vec::new();
+    let mut levels: HashMap<String, IssueLevel> = HashMap::new();
+    for issue in issues {
+        let expanded = expand_env_vars(&issue.path);
+        if !is_path_exploitable(&expanded, writable) {
+            continue;
+        }
+        let key = normalize_for_comparison(&expanded);
+        match levels.get_mut(&key) {
+            Some(level) => *level = (*level).max(issue.level),
+            None => {
+                levels.insert(key, issue.level);
+                order.push(expanded);
+            }
+        }
+    }
+    let order: Vec<&str> = order.iter().map(String::as_str).collect();
+    let (mut results, summary) = verify_paths(&order);
+    for result in &mut results {
+        let level = levels[&normalize_for_comparison(&result.path)];
+        result.severity_adjusted = result.is_exploitable && level < IssueLevel::Critical;
+    }
+    (results, summary)
+}
@@ -0,0 +1,165 @@
+//! Plain-text PATH snapshots for `spath export` and `spath import`.
+use crate::constants::PATH_SEPARATOR;
+use crate::fixer::validate_path_value;
+use crate::registry::RegistryHelper;
+use crate::utils::normalize_for_comparison;
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// Header line that starts the SYSTEM PATH section of a snapshot.
+pub const SYSTEM_SECTION_HEADER: &str = "# SYSTEM PATH";
+
+/// Header line that starts the USER PATH section of a snapshot.
+pub const USER_SECTION_HEADER: &str = "# USER PATH";
+
+/// PATH entries of one or both scopes, one entry per line on disk.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathSnapshot {
+    pub system: Option<Vec<String>>,
+    pub user: Option<Vec<String>>,
+}
+
+/// How `import` combines the snapshot with the current USER PATH.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// The snapshot entries become the whole USER PATH.
+    Replace,
+    /// Snapshot entries missing from the current USER PATH are appended.
+    Merge,
+}
+
+/// USER PATH value an import would write, compared with the current one.
+#[derive(Debug, Clone)]
+pub struct ImportPlan {
+    pub new_path: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl PathSnapshot {
+    /// Builds a snapshot from raw registry values of the selected scopes.
+    pub fn from_values(system_path: Option<&str>, user_path: Option<&str>) -> Self {
+        Self {
+            system: system_path.map(RegistryHelper::parse_path_string),
+            user: user_path.map(RegistryHelper::parse_path_string),
+        }
+    }
+    /// Renders each entry on its own line, under a header per scope.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for (header, entries) in [
+            (SYSTEM_SECTION_HEADER, &self.system),
+            (USER_SECTION_HEADER, &self.user),
+        ] {
+            if let Some(entries) = entries {
+                out.push_str(header);
+                out.push('\n');
+                for entry in entries {
+                    out.push_str(entry);
+                    out.push('\n');
+                }
+            }
+        }
+        out
+    }
+    /// Parses a snapshot. Lines before any section header belong to USER
+    /// PATH, so a hand-written list of entries imports as-is.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut snapshot = Self::default();
+        let mut current = &mut snapshot.user;
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            match line.trim() {
+                "" => continue,
+                SYSTEM_SECTION_HEADER => {
+                    current = &mut snapshot.system;
+                    current.get_or_insert_with(Vec::new);
+                    continue;
+                }
+                USER_SECTION_HEADER => {
+                    current = &mut snapshot.user;
+                    current.get_or_insert_with(Vec::new);
+                    continue;
+                }
+                comment if comment.starts_with('#') => continue,
+                _ => {}
+            }
+            if RegistryHelper::split_entries(line).len() > 1 {
+                bail!(
+                    "Line {} contains '{}'; put each PATH entry on its own line",
+                    number + 1,
+                    PATH_SEPARATOR
+                );
+            }
+            if line.chars().any(char::is_control) {
+                bail!("Line {} contains control characters", number + 1);
+            }
+            current.get_or_insert_with(Vec::new).push(line.to_string());
+        }
+        Ok(snapshot)
+    }
+    /// Reads and parses a snapshot file.
+    pub fn read_from(file: &Path) -> Result<Self> {
+        let text = fs::read_to_string(file)
+            .with_context(|| format!("Failed to read snapshot {}", file.display()))?;
+        Self::parse(&text)
+    }
+    /// Writes the snapshot to `file`, refusing to replace an existing file
+    /// unless `overwrite` is set.
+    pub fn write_to(&self, file: &Path, overwrite: bool) -> Result<()> {
+        if self.system.is_none() && self.user.is_none() {
+            bail!("Nothing to export: no PATH scope selected");
+        }
+        if file.exists() && !overwrite {
+            bail!(
+                "{} already exists (use --force to overwrite)",
+                file.display()
+            );
+        }
+        fs::write(file, self.render())
+            .with_context(|| format!("Failed to write snapshot {}", file.display()))
+    }
+    /// Computes the USER PATH an import would write. SYSTEM entries in the
+    /// snapshot are never imported.
+    pub fn plan_import(&self, current_user_path: &str, mode: ImportMode) -> Result<ImportPlan> {
+        let Some(imported) = &self.user else {
+            bail!("Snapshot has no USER PATH entries to import");
+        };
+        let current = RegistryHelper::parse_path_string(current_user_path);
+        let mut entries = match mode {
+            ImportMode::Replace => Vec::new(),
+            ImportMode::Merge => current.clone(),
+        };
+        let mut seen: HashSet<String> = entries
+            .iter()
+            .map(|p| normalize_for_comparison(p))
+            .collect();
+        for entry in imported {
+            if seen.insert(normalize_for_comparison(entry)) {
+                entries.push(entry.clone());
+            }
+        }
+        let new_path = validate_path_value(&RegistryHelper::join_paths(&entries))?;
+        let current_keys: HashSet<String> = current
+            .iter()
+            .map(|p| normalize_for_comparison(p))
+            .collect();
+        let added = entries
+            .iter()
+            .filter(|p| !current_keys.contains(&normalize_for_comparison(p)))
+            .cloned()
+            .collect();
+        let removed = current
+            .iter()
+            .filter(|p| !seen.contains(&normalize_for_comparison(p)))
+            .cloned()
+            .collect();
+        Ok(ImportPlan {
+            new_path,
+            added,
+            removed,
+        })
+    }
+}
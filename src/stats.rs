@@ -0,0 +1,76 @@
+//! PATH health history recomputed from backups for `spath stats`.
+use crate::backup::BackupManager;
+use crate::models::{AuditStats, PathLocation};
+use crate::scanner::PathScanner;
+use anyhow::Result;
+use serde::Serialize;
+use std::path::PathBuf;
+use tracing::warn;
+
+/// Audit statistics of one backup, rescanned against the current filesystem.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthSnapshot {
+    pub timestamp: String,
+    pub backup_file: PathBuf,
+    pub user: AuditStats,
+    pub user_health_score: u32,
+    /// Present when the backup captured SYSTEM PATH.
+    pub system: Option<AuditStats>,
+    pub system_health_score: Option<u32>,
+}
+
+impl HealthSnapshot {
+    /// Scans the PATH values of a backup and records their statistics.
+    pub fn from_values(
+        timestamp: impl Into<String>,
+        backup_file: impl Into<PathBuf>,
+        user_path: &str,
+        system_path: Option<&str>,
+    ) -> Result<Self> {
+        let user = PathScanner::from_string(user_path)
+            .with_location(PathLocation::User)
+            .scan()?
+            .audit;
+        let system = system_path
+            .map(|path| {
+                PathScanner::from_string(path)
+                    .with_location(PathLocation::System)
+                    .scan()
+                    .map(|results| results.audit)
+            })
+            .transpose()?;
+        Ok(Self {
+            timestamp: timestamp.into(),
+            backup_file: backup_file.into(),
+            user_health_score: user.health_score(),
+            system_health_score: system.as_ref().map(AuditStats::health_score),
+            user,
+            system,
+        })
+    }
+}
+
+/// Health of every backup, oldest first. `last` keeps only the N most
+/// recent backups. Backups that fail to load are logged and skipped.
+pub fn health_history(manager: &BackupManager, last: Option<usize>) -> Result<Vec<HealthSnapshot>> {
+    let files = manager.list()?;
+    let take = last.unwrap_or(files.len());
+    let mut history = Vec::new();
+    for file in files.into_iter().take(take) {
+        let backup = match manager.load(&file) {
+            Ok(backup) => backup,
+            Err(e) => {
+                warn!("Skipping backup {}: {:#}", file.display(), e);
+                continue;
+            }
+        };
+        history.push(HealthSnapshot::from_values(
+            backup.timestamp,
+            file,
+            &backup.user_path,
+            backup.system_path.as_deref(),
+        )?);
+    }
+    history.reverse();
+    Ok(history)
+}
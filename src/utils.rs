@@ -1,6 +1,20 @@
-use crate::constants::{PROGRAM_DATA, PROGRAM_FILES, PROGRAM_FILES_X86, USER_PATHS, WINDOWS_PATH};
+use crate::constants::{
+    DEFAULT_TERMINAL_WIDTH, MAX_EXPANSION_DEPTH, MAX_PATH_COMPONENT_LENGTH, PROGRAM_DATA,
+    PROGRAM_FILES, PROGRAM_FILES_X86, PROTECTED_PATHS, REDACTED_USER, USER_PATHS, USER_TOOL_DIRS,
+    WELL_KNOWN_DIRS, WINDOWS_APPS_SUFFIX, WINDOWS_PATH,
+};
 use crate::models::PathCategory;
+use sha2::{Digest, Sha256};
 use std::env;
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use windows::core::PCWSTR;
+use windows::Win32::Storage::FileSystem::{GetLongPathNameW, GetShortPathNameW};
+use windows::Win32::System::Console::{
+    GetConsoleScreenBufferInfo, GetStdHandle, CONSOLE_SCREEN_BUFFER_INFO, STD_OUTPUT_HANDLE,
+};
 
 pub fn categorize_path(path: &str) -> PathCategory {
     let lower = path.to_lowercase();
@@ -25,26 +39,398 @@ pub fn categorize_path(path: &str) -> PathCategory {
     PathCategory::Ambiguous
 }
 
+/// Names the per-user tool a directory belongs to (e.g. "pyenv shims"),
+/// if it is one of [`USER_TOOL_DIRS`].
+pub fn user_tool_name(path: &str) -> Option<&'static str> {
+    let lower = path.to_lowercase();
+    USER_TOOL_DIRS
+        .iter()
+        .find(|(pattern, _)| lower.contains(pattern))
+        .map(|(_, name)| *name)
+}
+
+/// Outcome of expanding `%VAR%` references in a PATH entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpandResult {
+    pub expanded: String,
+    /// Names of referenced variables that are not set, in order of appearance.
+    pub unresolved: Vec<String>,
+}
+
+impl ExpandResult {
+    /// Whether every variable reference was expanded.
+    pub fn is_complete(&self) -> bool {
+        self.unresolved.is_empty()
+    }
+}
+
+/// Expands `%VAR%` references, including references inside expanded values.
+///
+/// Each value is expanded on its own before it is spliced in, so a lone `%`
+/// inside a value never pairs with text outside it. A variable already being
+/// expanded further up the same chain is left as written and reported as
+/// unresolved, so `A=%B%` with `B=%A%` stops instead of looping, while the
+/// same variable may still appear in separate references of one entry.
+/// Nesting stops at [`MAX_EXPANSION_DEPTH`]. An unset variable is kept as
+/// written and its closing `%` may open the next reference, as `cmd.exe` does.
+pub fn expand_vars(path: &str) -> ExpandResult {
+    let mut unresolved = Vec::new();
+    let expanded = expand_text(path, &mut Vec::new(), &mut unresolved);
+    ExpandResult {
+        expanded,
+        unresolved,
+    }
+}
+
+/// Expands the references in `text` left to right. `chain` holds the
+/// (upper-cased) variables whose values are being expanded around `text`.
+fn expand_text(text: &str, chain: &mut Vec<String>, unresolved: &mut Vec<String>) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('%') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('%') else {
+            expanded.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+        let name = &after[..end];
+        let key = name.to_uppercase();
+        match env::var(name) {
+            Ok(value)
+                if !name.is_empty()
+                    && !chain.contains(&key)
+                    && chain.len() < MAX_EXPANSION_DEPTH =>
+            {
+                chain.push(key);
+                expanded.push_str(&expand_text(&value, chain, unresolved));
+                chain.pop();
+                rest = &after[end + 1..];
+            }
+            _ => {
+                // A backslash means the two '%' belong to different path
+                // components (e.g. `C:\50% off\100%`), not a reference.
+                if !name.is_empty() && !name.contains('\\') {
+                    unresolved.push(name.to_string());
+                }
+                expanded.push('%');
+                expanded.push_str(name);
+                rest = &after[end..];
+            }
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Whether output should be colored. An explicit `--color always|never`
+/// wins; otherwise a non-empty `NO_COLOR` value disables colors, and they
+/// are used only when stdout is a terminal.
+pub fn should_colorize(forced: Option<bool>, no_color: Option<&str>, is_terminal: bool) -> bool {
+    if let Some(color) = forced {
+        return color;
+    }
+    let no_color_set = matches!(no_color, Some(value) if !value.is_empty());
+    !no_color_set && is_terminal
+}
+
+/// Width of the terminal in columns: `COLUMNS` when set, otherwise the
+/// console window, otherwise [`DEFAULT_TERMINAL_WIDTH`].
+pub fn terminal_width() -> usize {
+    if let Some(columns) = env::var("COLUMNS")
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|&columns| columns > 0)
+    {
+        return columns;
+    }
+    let mut info = CONSOLE_SCREEN_BUFFER_INFO::default();
+    let console = unsafe {
+        GetStdHandle(STD_OUTPUT_HANDLE)
+            .and_then(|handle| GetConsoleScreenBufferInfo(handle, &mut info))
+    };
+    let width = i32::from(info.srWindow.Right) - i32::from(info.srWindow.Left) + 1;
+    match console {
+        Ok(()) if width > 0 => width as usize,
+        _ => DEFAULT_TERMINAL_WIDTH,
+    }
+}
+
+/// Cuts `text` to at most `width` characters, ending it with `ellipsis`
+/// when anything was cut.
+pub fn truncate_with_ellipsis(text: &str, width: usize, ellipsis: &str) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let keep = width.saturating_sub(ellipsis.chars().count());
+    let mut truncated: String = text.chars().take(keep).collect();
+    truncated.push_str(ellipsis);
+    truncated.chars().take(width).collect()
+}
+
+/// Expands `%VAR%` references, leaving unset ones as written.
 pub fn expand_env_vars(path: &str) -> String {
-    let mut result = path.to_string();
-    while let Some(start) = result.find('%') {
-        if let Some(end) = result[start + 1..].find('%') {
-            let var_name = &result[start + 1..start + 1 + end];
-            if let Ok(value) = env::var(var_name) {
-                result = result.replace(&format!("%{}%", var_name), &value);
+    expand_vars(path).expanded
+}
+
+/// Strips the trailing dots and spaces Windows ignores in each path component,
+/// so `C:\Tools.` and `C:\Tools` refer to the same directory.
+pub fn strip_trailing_dots_and_spaces(path: &str) -> String {
+    path.split('\\')
+        .map(|component| {
+            if component == "." || component == ".." {
+                return component;
+            }
+            let stripped = component.trim_end_matches(['.', ' ']);
+            if stripped.is_empty() {
+                component
             } else {
-                break;
+                stripped
             }
+        })
+        .collect::<Vec<_>>()
+        .join("\\")
+}
+
+/// Normalizes a PATH entry into the key used for duplicate detection.
+///
+/// Quotes, case and trailing dots/spaces are ignored, and separators are
+/// collapsed (see [`collapse_separators`]), so `"C:\Tools"`, `C:\Tools\`
+/// and `C:\Tools\..\Tools` share a key. A drive root keeps its backslash.
+pub fn normalize_for_comparison(path: &str) -> String {
+    let stripped = strip_trailing_dots_and_spaces(path.trim().trim_matches('"')).to_lowercase();
+    collapse_separators(&stripped)
+}
+
+/// Rewrites `/` as `\`, drops empty and `.` components and resolves `..`
+/// lexically. A leading `\\` (UNC) or `\` and the backslash of a drive root
+/// (`C:\`) are kept; any other trailing backslash is dropped.
+pub fn collapse_separators(path: &str) -> String {
+    let path = path.replace('/', "\\");
+    let prefix = if path.starts_with("\\\\") {
+        "\\\\"
+    } else if path.starts_with('\\') {
+        "\\"
+    } else {
+        ""
+    };
+    let mut components: Vec<&str> = Vec::new();
+    for component in path.split('\\') {
+        match component {
+            "" | "." => {}
+            ".." => match components.last() {
+                Some(&last) if last != ".." && !(components.len() == 1 && last.ends_with(':')) => {
+                    components.pop();
+                }
+                _ => components.push(".."),
+            },
+            _ => components.push(component),
+        }
+    }
+    if components.is_empty() && prefix.is_empty() {
+        return path;
+    }
+    let mut collapsed = format!("{}{}", prefix, components.join("\\"));
+    if components.len() == 1
+        && prefix.is_empty()
+        && collapsed.ends_with(':')
+        && path.len() > collapsed.len()
+    {
+        collapsed.push('\\');
+    }
+    collapsed
+}
+
+/// Returns true for entries that make Windows search the current directory
+/// (`.`, `..` or an entry that is empty once whitespace and quotes are removed).
+pub fn is_current_directory_entry(path: &str) -> bool {
+    let cleaned = path.trim().trim_matches('"').trim().trim_end_matches('\\');
+    cleaned.is_empty() || cleaned == "." || cleaned == ".."
+}
+
+/// Returns true if the entry is one of the built-in protected system directories.
+pub fn is_protected_path(path: &str) -> bool {
+    let normalized = normalize_for_comparison(&expand_env_vars(path.trim().trim_matches('"')));
+    let normalized = normalized.trim_end_matches('\\');
+    PROTECTED_PATHS.contains(&normalized)
+}
+
+/// Returns true if the entry is the App Execution Alias directory, whose
+/// zero-byte reparse stubs look like broken files to ordinary checks.
+pub fn is_app_execution_alias_dir(path: &str) -> bool {
+    normalize_for_comparison(path)
+        .trim_end_matches('\\')
+        .ends_with(WINDOWS_APPS_SUFFIX)
+}
+
+/// Returns true if any component of the entry is longer than NTFS allows,
+/// so the directory cannot exist.
+pub fn has_overlong_component(path: &str) -> bool {
+    expand_env_vars(path.trim().trim_matches('"'))
+        .split(['\\', '/'])
+        .any(|component| component.chars().count() > MAX_PATH_COMPONENT_LENGTH)
+}
+
+/// Returns the upper-case drive letter of an entry, if it has one.
+pub fn drive_letter(path: &str) -> Option<char> {
+    let expanded = expand_env_vars(path.trim().trim_matches('"'));
+    let mut chars = expanded.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), Some(':')) if letter.is_ascii_alphabetic() => {
+            Some(letter.to_ascii_uppercase())
+        }
+        _ => None,
+    }
+}
+
+/// Returns true if the entry matches the wildcard `pattern` (`*` for any
+/// run of characters, `?` for one), ignoring case, quotes and trailing
+/// backslashes.
+pub fn matches_exclude_pattern(path: &str, pattern: &str) -> bool {
+    let normalized = normalize_for_comparison(path);
+    let text: Vec<char> = normalized.trim_end_matches('\\').chars().collect();
+    let pattern: Vec<char> = pattern.trim().to_lowercase().chars().collect();
+    let (mut t, mut p) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            t += 1;
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
         } else {
-            break;
+            return false;
         }
     }
-    result
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Whether `path` exists, or `None` when the check does not finish within
+/// `timeout`. Without a timeout the check runs inline.
+pub fn path_exists_within(path: &str, timeout: Option<Duration>) -> Option<bool> {
+    check_path_within(path, Path::exists, timeout)
+}
+
+/// Runs `check` on `path` like [`path_exists_within`], e.g. with a check
+/// that simulates a slow path.
+pub fn check_path_within(
+    path: &str,
+    check: fn(&Path) -> bool,
+    timeout: Option<Duration>,
+) -> Option<bool> {
+    match timeout {
+        None => Some(check(Path::new(path))),
+        Some(timeout) => {
+            let path = path.to_string();
+            run_with_timeout(move || check(Path::new(&path)), timeout)
+        }
+    }
+}
+
+/// Returns true if the entry lives on one of `drives` (compared case-insensitively).
+pub fn is_on_excluded_drive(path: &str, drives: &[char]) -> bool {
+    drive_letter(path)
+        .map(|letter| drives.iter().any(|d| d.eq_ignore_ascii_case(&letter)))
+        .unwrap_or(false)
+}
+
+/// Returns the `\\server\share` root of a UNC path, or `None` for other
+/// paths. Extended-length (`\\?\`) and device (`\\.\`) paths are not UNC
+/// shares.
+pub fn unc_share(path: &str) -> Option<String> {
+    let rest = path.trim().trim_matches('"').strip_prefix("\\\\")?;
+    let mut parts = rest.split(['\\', '/']);
+    let server = parts
+        .next()
+        .filter(|s| !s.is_empty() && *s != "?" && *s != ".")?;
+    let share = parts.next().filter(|s| !s.is_empty())?;
+    Some(format!("\\\\{}\\{}", server, share))
+}
+
+/// Lists protected entries that would be removed from `before`, or moved
+/// behind an entry that previously came after them, by changing it to `after`.
+pub fn protected_violations(before: &[String], after: &[String]) -> Vec<String> {
+    let before_keys: Vec<String> = before.iter().map(|p| normalize_for_comparison(p)).collect();
+    let after_keys: Vec<String> = after.iter().map(|p| normalize_for_comparison(p)).collect();
+    let mut violations = Vec::new();
+    for (i, path) in before.iter().enumerate() {
+        if !is_protected_path(path) {
+            continue;
+        }
+        let Some(new_pos) = after_keys.iter().position(|k| *k == before_keys[i]) else {
+            violations.push(format!("Would remove protected system entry: {}", path));
+            continue;
+        };
+        let previously_before = &before_keys[..i];
+        if let Some(overtaking) = after[..new_pos]
+            .iter()
+            .zip(&after_keys[..new_pos])
+            .find(|(_, key)| !previously_before.contains(key))
+        {
+            violations.push(format!(
+                "Would move protected system entry {} behind {}",
+                path, overtaking.0
+            ));
+        }
+    }
+    violations
+}
+
+/// Redacts machine-specific parts of a path for sharing.
+///
+/// The component after `\Users\` is replaced with `<USER>`. With `hash_dirs`,
+/// every other component that is not a drive or a well-known Windows
+/// directory is replaced by the first six hex digits of its SHA-256, so the
+/// same name hashes the same way across builds and machines.
+pub fn redact_path(path: &str, hash_dirs: bool) -> String {
+    let components: Vec<&str> = path.split('\\').collect();
+    let mut redacted = Vec::with_capacity(components.len());
+    for (i, component) in components.iter().enumerate() {
+        let previous = if i > 0 { components[i - 1] } else { "" };
+        if previous
+            .trim_start_matches('"')
+            .eq_ignore_ascii_case("users")
+            && !component.is_empty()
+        {
+            let quote = if component.ends_with('"') { "\"" } else { "" };
+            redacted.push(format!("{}{}", REDACTED_USER, quote));
+        } else if hash_dirs && i > 0 && !is_well_known_dir(component) {
+            redacted.push(hash_component(component));
+        } else {
+            redacted.push(component.to_string());
+        }
+    }
+    redacted.join("\\")
+}
+
+fn is_well_known_dir(component: &str) -> bool {
+    let name = component.trim_matches('"');
+    name.is_empty()
+        || name.starts_with('%')
+        || WELL_KNOWN_DIRS.contains(&name.to_lowercase().as_str())
+}
+
+fn hash_component(component: &str) -> String {
+    let quote = if component.ends_with('"') { "\"" } else { "" };
+    let digest = Sha256::digest(component.trim_matches('"').to_lowercase().as_bytes());
+    format!(
+        "dir-{:02x}{:02x}{:02x}{}",
+        digest[0], digest[1], digest[2], quote
+    )
 }
 
 pub fn is_absolute_path(path: &str) -> bool {
     let trimmed = path.trim();
-    trimmed.contains(':') || trimmed.starts_with('"') || trimmed.contains('%')
+    trimmed.contains(':')
+        || trimmed.starts_with('"')
+        || trimmed.contains('%')
+        || trimmed.starts_with("\\\\")
 }
 
 pub fn quote_if_needed(path: &str) -> String {
@@ -54,3 +440,52 @@ pub fn quote_if_needed(path: &str) -> String {
         path.to_string()
     }
 }
+
+/// Runs `check` on a worker thread and waits at most `timeout` for it.
+///
+/// Returns `None` when the check did not finish in time; the worker is left
+/// to finish in the background.
+pub fn run_with_timeout<T, F>(check: F, timeout: Duration) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(check());
+    });
+    receiver.recv_timeout(timeout).ok()
+}
+
+/// Returns `path` in its on-disk casing, or `None` if it cannot be resolved.
+///
+/// Round-trips through the short (8.3) name so `GetLongPathNameW` rebuilds
+/// every component from the file system; the drive letter is upper-cased.
+pub fn canonical_case(path: &str) -> Option<String> {
+    let wide: Vec<u16> = path.encode_utf16().chain(std::iter::once(0)).collect();
+    // SAFETY: `wide` is NUL-terminated and outlives the call.
+    let short = call_path_api(|buf| unsafe { GetShortPathNameW(PCWSTR(wide.as_ptr()), buf) })?;
+    let short: Vec<u16> = short.into_iter().chain(std::iter::once(0)).collect();
+    // SAFETY: `short` is NUL-terminated and outlives the call.
+    let long = call_path_api(|buf| unsafe { GetLongPathNameW(PCWSTR(short.as_ptr()), buf) })?;
+    let mut canonical = String::from_utf16(&long).ok()?;
+    if canonical.as_bytes().get(1) == Some(&b':') {
+        canonical[..1].make_ascii_uppercase();
+    }
+    Some(canonical)
+}
+
+/// Calls a Win32 path API twice: once for the buffer size, once to fill it.
+fn call_path_api(call: impl Fn(Option<&mut [u16]>) -> u32) -> Option<Vec<u16>> {
+    let required = call(None) as usize;
+    if required == 0 {
+        return None;
+    }
+    let mut buffer = vec![0u16; required];
+    let written = call(Some(&mut buffer)) as usize;
+    if written == 0 || written >= buffer.len() {
+        return None;
+    }
+    buffer.truncate(written);
+    Some(buffer)
+}
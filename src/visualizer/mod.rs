@@ -1,35 +1,57 @@
 //! PATH visualization.
+use crate::glyphs::{glyphs, Glyphs};
 use crate::models::{PathEntry, PathLocation};
 use colored::*;
+use std::fmt::Write;
 
 pub fn visualize_simple(paths: &[String], use_color: bool) {
-    println!("\n{}", format_header("PATH Entries", use_color));
-    println!();
+    print!("{}", render_simple(paths, use_color, glyphs()));
+}
+
+pub fn visualize_tree(paths: &[String], use_color: bool) {
+    print!("{}", render_tree(paths, use_color, glyphs()));
+}
+
+/// Renders the list view that [`visualize_simple`] prints.
+pub fn render_simple(paths: &[String], use_color: bool, glyphs: &Glyphs) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "\n{}", format_header("PATH Entries", use_color));
+    let _ = writeln!(out);
     let entries = build_entries(paths);
     for entry in &entries {
-        print_simple_entry(entry, use_color);
+        write_simple_entry(&mut out, entry, use_color, glyphs);
     }
-    print_summary(&entries, use_color);
+    write_summary(&mut out, &entries, use_color, glyphs);
+    out
 }
 
-pub fn visualize_tree(paths: &[String], use_color: bool) {
-    println!(
+/// Renders the tree view that [`visualize_tree`] prints.
+pub fn render_tree(paths: &[String], use_color: bool, glyphs: &Glyphs) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
         "\n{}",
         format_header("PATH Structure (Tree View)", use_color)
     );
-    println!();
+    let _ = writeln!(out);
     let entries = build_entries(paths);
     for (i, entry) in entries.iter().enumerate() {
-        print_tree_entry(entry, i == entries.len() - 1, use_color);
+        write_tree_entry(&mut out, entry, i == entries.len() - 1, use_color, glyphs);
     }
-    print_summary(&entries, use_color);
+    write_summary(&mut out, &entries, use_color, glyphs);
+    out
 }
 
 fn build_entries(paths: &[String]) -> Vec<PathEntry> {
+    let counts = PathEntry::path_counts(paths);
     paths
         .iter()
         .enumerate()
-        .map(|(i, p)| PathEntry::new(p.clone(), i, PathLocation::User, paths))
+        .map(|(i, p)| {
+            let mut entry = PathEntry::with_path_counts(p.clone(), i, PathLocation::User, &counts);
+            entry.check_acl();
+            entry
+        })
         .collect()
 }
 
@@ -41,23 +63,51 @@ fn format_header(text: &str, use_color: bool) -> ColoredString {
     }
 }
 
-fn print_simple_entry(entry: &PathEntry, use_color: bool) {
+fn write_simple_entry(out: &mut String, entry: &PathEntry, use_color: bool, glyphs: &Glyphs) {
     let index_str = format!("[{}]", entry.index);
-    let status = if entry.exists { "✓" } else { "✗" };
+    let status = entry_status(entry, glyphs);
     let line = format_entry_line(&index_str, status, &entry.path, entry, use_color, "");
-    println!("{}", line);
-    print_warnings(entry, use_color, "    ");
+    let _ = writeln!(out, "{}", line);
+    write_warnings(out, entry, use_color, glyphs, "    ");
 }
 
-fn print_tree_entry(entry: &PathEntry, is_last: bool, use_color: bool) {
-    let branch = if is_last { "└─" } else { "├─" };
+fn write_tree_entry(
+    out: &mut String,
+    entry: &PathEntry,
+    is_last: bool,
+    use_color: bool,
+    glyphs: &Glyphs,
+) {
+    let branch = if is_last {
+        glyphs.last_branch
+    } else {
+        glyphs.branch
+    };
     let index_str = format!("[{}]", entry.index);
-    let status = if entry.exists { "✓" } else { "✗" };
+    let status = entry_status(entry, glyphs);
     let line = format_entry_line(&index_str, status, &entry.path, entry, use_color, branch);
-    println!("{}", line);
-    let continuation = if is_last { "   " } else { "│  " };
-    print_tree_warnings(entry, use_color, continuation);
-    print_user_specific_info(entry, is_last, use_color);
+    let _ = writeln!(out, "{}", line);
+    let continuation = continuation(is_last, glyphs);
+    write_tree_warnings(out, entry, use_color, glyphs, &continuation);
+    write_user_specific_info(out, entry, &continuation, use_color, glyphs);
+}
+
+/// [`Glyphs::warning`] for an entry that is a file, otherwise whether it
+/// exists.
+fn entry_status(entry: &PathEntry, glyphs: &Glyphs) -> &'static str {
+    if entry.is_file() {
+        glyphs.warning
+    } else {
+        glyphs.status(entry.exists)
+    }
+}
+
+/// Indent under a tree entry: blank after the last one, otherwise the
+/// pipe, padded to the width of a branch.
+fn continuation(is_last: bool, glyphs: &Glyphs) -> String {
+    let width = glyphs.branch.chars().count() + 1;
+    let lead = if is_last { "" } else { glyphs.pipe };
+    format!("{:<width$}", lead, width = width)
 }
 
 fn format_entry_line(
@@ -70,7 +120,9 @@ fn format_entry_line(
 ) -> String {
     if use_color {
         let colored_index = index_str.bright_black();
-        let colored_status = if entry.exists {
+        let colored_status = if entry.is_file() {
+            status.yellow()
+        } else if entry.exists {
             status.green()
         } else {
             status.red()
@@ -100,51 +152,81 @@ fn format_entry_line(
     }
 }
 
-fn print_warnings(entry: &PathEntry, use_color: bool, indent: &str) {
+fn write_warnings(
+    out: &mut String,
+    entry: &PathEntry,
+    use_color: bool,
+    glyphs: &Glyphs,
+    indent: &str,
+) {
     for warning in entry.get_warnings() {
         let line = if use_color {
-            format!("{}⚠ {}", indent, warning).yellow()
+            format!("{}{} {}", indent, glyphs.warning, warning).yellow()
         } else {
             format!("{}! {}", indent, warning).normal()
         };
-        println!("{}", line);
+        let _ = writeln!(out, "{}", line);
     }
 }
 
-fn print_tree_warnings(entry: &PathEntry, use_color: bool, continuation: &str) {
+fn write_tree_warnings(
+    out: &mut String,
+    entry: &PathEntry,
+    use_color: bool,
+    glyphs: &Glyphs,
+    continuation: &str,
+) {
     let warnings = entry.get_warnings();
     for (j, warning) in warnings.iter().enumerate() {
         let sub_branch = if j == warnings.len() - 1 {
-            "└─"
+            glyphs.last_branch
         } else {
-            "├─"
+            glyphs.branch
         };
         let line = if use_color {
-            format!("{}  {} ⚠ {}", continuation, sub_branch, warning).yellow()
+            format!(
+                "{}  {} {} {}",
+                continuation, sub_branch, glyphs.warning, warning
+            )
+            .yellow()
         } else {
             format!("{}  {} ! {}", continuation, sub_branch, warning).normal()
         };
-        println!("{}", line);
+        let _ = writeln!(out, "{}", line);
     }
 }
 
-fn print_user_specific_info(entry: &PathEntry, is_last: bool, use_color: bool) {
+fn write_user_specific_info(
+    out: &mut String,
+    entry: &PathEntry,
+    continuation: &str,
+    use_color: bool,
+    glyphs: &Glyphs,
+) {
     if entry.is_user_specific() && entry.get_warnings().is_empty() {
-        let continuation = if is_last { "   " } else { "│  " };
         let line = if use_color {
-            format!("{}  └─ ℹ User-specific path", continuation).cyan()
+            format!(
+                "{}  {} {} User-specific path",
+                continuation, glyphs.last_branch, glyphs.info
+            )
+            .cyan()
         } else {
-            format!("{}  └─ i User-specific path", continuation).normal()
+            format!(
+                "{}  {} i User-specific path",
+                continuation, glyphs.last_branch
+            )
+            .normal()
         };
-        println!("{}", line);
+        let _ = writeln!(out, "{}", line);
     }
 }
 
-fn print_summary(entries: &[PathEntry], use_color: bool) {
-    println!();
-    println!("{}", format_header("Summary", use_color));
+fn write_summary(out: &mut String, entries: &[PathEntry], use_color: bool, glyphs: &Glyphs) {
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{}", format_header("Summary", use_color));
     let total = entries.len();
     let existing = entries.iter().filter(|e: &&PathEntry| e.exists).count();
+    let files = entries.iter().filter(|e: &&PathEntry| e.is_file()).count();
     let with_issues = entries
         .iter()
         .filter(|e: &&PathEntry| e.has_issues())
@@ -157,17 +239,21 @@ fn print_summary(entries: &[PathEntry], use_color: bool) {
         .iter()
         .filter(|e: &&PathEntry| e.is_duplicate)
         .count();
-    println!("  Total paths: {}", total);
-    println!("  Existing: {} / {}", existing, total);
-    println!("  With issues: {}", with_issues);
-    println!("  User-specific: {}", user_specific);
-    println!("  Duplicates: {}", duplicates);
+    let _ = writeln!(out, "  Total paths: {}", total);
+    let _ = writeln!(out, "  Existing: {} / {}", existing, total);
+    if files > 0 {
+        let _ = writeln!(out, "  Files, not directories: {}", files);
+    }
+    let _ = writeln!(out, "  With issues: {}", with_issues);
+    let _ = writeln!(out, "  User-specific: {}", user_specific);
+    let _ = writeln!(out, "  Duplicates: {}", duplicates);
     if use_color {
-        println!();
-        println!("Legend:");
-        println!("  {} Exists", "✓".green());
-        println!("  {} Does not exist", "✗".red());
-        println!("  {} Has issues", "path".yellow());
-        println!("  {} User-specific", "path".cyan());
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Legend:");
+        let _ = writeln!(out, "  {} Exists", glyphs.check.green());
+        let _ = writeln!(out, "  {} Does not exist", glyphs.cross.red());
+        let _ = writeln!(out, "  {} File, not a directory", glyphs.warning.yellow());
+        let _ = writeln!(out, "  {} Has issues", "path".yellow());
+        let _ = writeln!(out, "  {} User-specific", "path".cyan());
     }
 }
@@ -130,3 +130,175 @@ mod analyzer_tests {
         assert!(!has_recommendations);
     }
 }
+
+#[cfg(test)]
+mod runtime_shadowing_tests {
+    use spath_cli::analyzer::detect_runtime_shadowing;
+    use std::fs;
+
+    #[test]
+    fn test_two_python_dirs_report_shadowing() {
+        let dir = tempfile::tempdir().unwrap();
+        let old_python = dir.path().join("Python27");
+        let new_python = dir.path().join("Python312");
+        let node = dir.path().join("nodejs");
+        for d in [&old_python, &new_python, &node] {
+            fs::create_dir(d).unwrap();
+        }
+        fs::write(old_python.join("python.exe"), b"").unwrap();
+        fs::write(new_python.join("python.exe"), b"").unwrap();
+        fs::write(node.join("node.exe"), b"").unwrap();
+        let paths: Vec<String> = [&old_python, &node, &new_python]
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect();
+        let reports = detect_runtime_shadowing(&paths, false);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].executable, "python.exe");
+        assert_eq!(reports[0].providers.len(), 2);
+        assert_eq!(reports[0].winner().directory, paths[0]);
+        assert_eq!(reports[0].providers[1].directory, paths[2]);
+        assert!(reports[0].providers.iter().all(|p| p.version.is_none()));
+    }
+
+    #[test]
+    fn test_single_provider_is_not_shadowing() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("ruby.exe"), b"").unwrap();
+        let paths = vec![dir.path().display().to_string()];
+        assert!(detect_runtime_shadowing(&paths, false).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod architecture_conflict_tests {
+    use spath_cli::analyzer::{detect_architecture_conflicts, Architecture};
+
+    fn paths(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_x86_x64_pair_with_winner() {
+        let paths = paths(&[
+            "C:\\Windows",
+            "C:\\Program Files (x86)\\Tool\\bin",
+            "\"C:\\Program Files\\Tool\\bin\"",
+        ]);
+        let conflicts = detect_architecture_conflicts(&paths);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].suffix, "\\tool\\bin");
+        assert_eq!(conflicts[0].x64_path, paths[2]);
+        assert_eq!(conflicts[0].x86_path, paths[1]);
+        assert_eq!(conflicts[0].winner, Architecture::X86);
+    }
+
+    #[test]
+    fn test_different_tools_are_not_paired() {
+        let paths = paths(&[
+            "C:\\Program Files\\Git\\cmd",
+            "C:\\Program Files (x86)\\Tool\\bin",
+        ]);
+        assert!(detect_architecture_conflicts(&paths).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod redundant_user_entry_tests {
+    use spath_cli::analyzer::{detect_redundant_user_entries, AnalysisResults};
+    use spath_cli::models::{PathEntry, PathLocation};
+
+    fn entry(path: &str, location: PathLocation) -> PathEntry {
+        PathEntry {
+            path: path.to_string(),
+            location,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_user_entry_duplicating_system_is_redundant() {
+        let results = AnalysisResults {
+            entries: vec![
+                entry("C:\\Tools\\bin", PathLocation::System),
+                entry("C:\\Windows", PathLocation::System),
+                entry("c:\\tools\\bin", PathLocation::User),
+                entry("C:\\Users\\dev\\bin", PathLocation::User),
+            ],
+        };
+        let redundant = detect_redundant_user_entries(&results);
+        assert_eq!(redundant.len(), 1);
+        assert_eq!(redundant[0].user_path, "c:\\tools\\bin");
+        assert_eq!(redundant[0].system_path, "C:\\Tools\\bin");
+    }
+
+    #[test]
+    fn test_system_only_duplicates_are_not_user_redundancy() {
+        let results = AnalysisResults {
+            entries: vec![
+                entry("C:\\Tools", PathLocation::System),
+                entry("C:\\Tools", PathLocation::System),
+            ],
+        };
+        assert!(detect_redundant_user_entries(&results).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod analysis_json_tests {
+    use spath_cli::analyzer::AnalysisResults;
+    use spath_cli::formatter::JsonFormatter;
+    use spath_cli::models::{PathCategory, PathEntry, PathLocation};
+
+    fn entry(path: &str, location: PathLocation, category: PathCategory) -> PathEntry {
+        PathEntry {
+            path: path.to_string(),
+            location,
+            category,
+            has_spaces: path.contains(' '),
+            ..Default::default()
+        }
+    }
+
+    fn sample() -> AnalysisResults {
+        AnalysisResults {
+            entries: vec![
+                entry(
+                    "C:\\Program Files\\Tool",
+                    PathLocation::System,
+                    PathCategory::SystemProgram,
+                ),
+                entry(
+                    "C:\\Users\\dev\\.cargo\\bin",
+                    PathLocation::System,
+                    PathCategory::UserProgram,
+                ),
+                entry("C:\\Tools", PathLocation::User, PathCategory::Ambiguous),
+                entry("c:\\tools", PathLocation::User, PathCategory::Ambiguous),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_groups_match_console_report() {
+        let results = sample();
+        let groups = results.groups();
+        assert_eq!(groups.misplaced.len(), 1);
+        assert_eq!(groups.unquoted_system[0].path, "C:\\Program Files\\Tool");
+        assert!(groups.unquoted_user.is_empty());
+        assert_eq!(groups.duplicates.len(), 1);
+        assert_eq!(groups.duplicates[0].path, "c:\\tools");
+    }
+
+    #[test]
+    fn test_json_uses_readable_enum_names() {
+        let json: serde_json::Value =
+            serde_json::from_str(&JsonFormatter::render_analysis(&sample()).unwrap()).unwrap();
+        assert_eq!(json["entries"].as_array().unwrap().len(), 4);
+        assert_eq!(json["entries"][0]["location"], "system");
+        assert_eq!(json["entries"][0]["category"], "system_program");
+        assert_eq!(json["misplaced"][0]["category"], "user_program");
+        assert_eq!(json["duplicates"][0]["path"], "c:\\tools");
+        assert!(json["unquoted_user"].as_array().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,618 @@
+use spath_cli::backup::{BackupFileStatus, BackupManager};
+use std::fs;
+
+#[cfg(test)]
+mod repair_tests {
+    use super::*;
+
+    const VALID_BACKUP: &str =
+        r#"{"timestamp":"20240101_120000","user_path":"C:\\Tools","system_path":null}"#;
+
+    #[test]
+    fn test_repair_classifies_valid_and_corrupt_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let valid = dir.path().join("path_backup_20240101_120000.json");
+        let corrupt = dir.path().join("path_backup_20240102_120000.json");
+        let misnamed = dir.path().join("notes.txt");
+        fs::write(&valid, VALID_BACKUP).unwrap();
+        fs::write(&corrupt, "{invalid").unwrap();
+        fs::write(&misnamed, VALID_BACKUP).unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let report = manager.repair(false).unwrap();
+        assert_eq!(report.files.len(), 3);
+        let status_of = |name: &str| {
+            report
+                .files
+                .iter()
+                .find(|f| f.path.file_name().unwrap() == name)
+                .map(|f| f.status.clone())
+                .unwrap()
+        };
+        assert_eq!(
+            status_of("path_backup_20240101_120000.json"),
+            BackupFileStatus::Valid
+        );
+        assert!(matches!(
+            status_of("path_backup_20240102_120000.json"),
+            BackupFileStatus::Corrupt(_)
+        ));
+        assert!(matches!(
+            status_of("notes.txt"),
+            BackupFileStatus::InvalidName(_)
+        ));
+        assert_eq!(report.valid_count(), 1);
+        assert_eq!(report.broken_count(), 2);
+        assert!(corrupt.exists());
+    }
+
+    #[test]
+    fn test_repair_quarantines_broken_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let valid = dir.path().join("path_backup_20240101_120000.json");
+        let corrupt = dir.path().join("path_backup_20240102_120000.json");
+        fs::write(&valid, VALID_BACKUP).unwrap();
+        fs::write(&corrupt, "{invalid").unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let report = manager.repair(true).unwrap();
+        assert!(valid.exists());
+        assert!(!corrupt.exists());
+        let quarantined: Vec<_> = report
+            .files
+            .iter()
+            .filter_map(|f| f.quarantined_to.as_ref())
+            .collect();
+        assert_eq!(quarantined.len(), 1);
+        assert!(quarantined[0].exists());
+        assert_eq!(manager.list().unwrap(), vec![valid]);
+    }
+
+    #[test]
+    fn test_repair_continues_after_a_quarantine_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let blocked = dir.path().join("path_backup_20240101_120000.json");
+        let corrupt = dir.path().join("path_backup_20240102_120000.json");
+        fs::write(&blocked, "{invalid").unwrap();
+        fs::write(&corrupt, "{invalid").unwrap();
+        let quarantine_dir = dir.path().join("quarantine");
+        fs::create_dir_all(quarantine_dir.join("path_backup_20240101_120000.json")).unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let report = manager.repair(true).unwrap();
+        assert_eq!(report.files.len(), 2);
+        assert!(blocked.exists());
+        assert!(report.files[0].quarantined_to.is_none());
+        assert!(report.files[0].quarantine_error.is_some());
+        assert!(!corrupt.exists());
+        assert!(report.files[1].quarantined_to.is_some());
+        assert!(report.files[1].quarantine_error.is_none());
+    }
+}
+
+#[cfg(test)]
+mod restore_preview_tests {
+    use super::*;
+
+    const BACKUP: &str =
+        r#"{"timestamp":"20240101_120000","user_path":"C:\\Tools;C:\\Go\\bin","system_path":null}"#;
+
+    #[test]
+    fn test_preview_restore_reports_value_and_diff() {
+        let dir = tempfile::tempdir().unwrap();
+        let backup = dir.path().join("path_backup_20240101_120000.json");
+        fs::write(&backup, BACKUP).unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let preview = manager
+            .preview_restore(&backup, "c:\\tools;C:\\Python312")
+            .unwrap();
+        assert_eq!(preview.restored_path, "C:\\Tools;C:\\Go\\bin");
+        assert_eq!(preview.added, vec!["C:\\Go\\bin".to_string()]);
+        assert_eq!(preview.removed, vec!["C:\\Python312".to_string()]);
+        let unchanged = fs::read_to_string(&backup).unwrap();
+        assert_eq!(unchanged, BACKUP);
+    }
+
+    #[test]
+    fn test_preview_restore_rejects_corrupt_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let backup = dir.path().join("path_backup_20240101_120000.json");
+        fs::write(&backup, "{invalid").unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        assert!(manager.preview_restore(&backup, "C:\\Tools").is_err());
+    }
+}
+
+#[cfg(test)]
+mod backup_format_tests {
+    use super::*;
+    use spath_cli::backup::PathBackup;
+    use spath_cli::constants::BACKUP_FORMAT_VERSION;
+
+    #[test]
+    fn test_backup_written_by_manager_parses_as_path_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let created = manager
+            .create_from("C:\\Tools;C:\\Go\\bin", Some("C:\\Windows".to_string()))
+            .unwrap();
+        let json = fs::read_to_string(&created.path).unwrap();
+        let backup: PathBackup = serde_json::from_str(&json).unwrap();
+        assert_eq!(backup.user_path, "C:\\Tools;C:\\Go\\bin");
+        assert_eq!(backup.system_path.as_deref(), Some("C:\\Windows"));
+        let preview = manager
+            .preview_restore(&created.path, "C:\\Tools;C:\\Go\\bin")
+            .unwrap();
+        assert_eq!(preview.restored_path, backup.user_path);
+        assert!(preview.added.is_empty() && preview.removed.is_empty());
+    }
+
+    #[test]
+    fn test_backup_without_system_path_is_accepted() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("path_backup_20240101_120000.json");
+        fs::write(
+            &file,
+            r#"{"timestamp":"20240101_120000","user_path":"C:\\Tools","system_path":null}"#,
+        )
+        .unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let preview = manager.preview_restore(&file, "").unwrap();
+        assert_eq!(preview.restored_path, "C:\\Tools");
+    }
+
+    #[test]
+    fn test_unversioned_backup_is_migrated_on_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("path_backup_20240101_120000.json");
+        fs::write(
+            &file,
+            r#"{"timestamp":"20240101_120000","user_path":"C:\\Tools;C:\\Go\\bin","system_path":"C:\\Windows"}"#,
+        )
+        .unwrap();
+        let raw: PathBackup = serde_json::from_str(&fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(raw.version, 0);
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let backup = manager.load(&file).unwrap();
+        assert_eq!(backup.version, BACKUP_FORMAT_VERSION);
+        assert_eq!(backup.user_path, "C:\\Tools;C:\\Go\\bin");
+        let preview = manager.preview_restore(&file, "C:\\Tools").unwrap();
+        assert_eq!(preview.added, vec!["C:\\Go\\bin".to_string()]);
+    }
+
+    #[test]
+    fn test_newer_backup_version_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("path_backup_20240101_120000.json");
+        let json = format!(
+            r#"{{"version":{},"timestamp":"20240101_120000","user_path":"C:\\Tools","system_path":null}}"#,
+            BACKUP_FORMAT_VERSION + 1
+        );
+        fs::write(&file, json).unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let err = manager.preview_restore(&file, "").unwrap_err();
+        assert!(err.to_string().contains("newer than this spath supports"));
+    }
+
+    #[test]
+    fn test_version_is_checked_before_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("path_backup_20240101_120000.json");
+        let json = format!(
+            r#"{{"version":{},"timestamp":"20240101_120000","user_path":"C:\\Tools","system_path":null,"checksum":"00"}}"#,
+            BACKUP_FORMAT_VERSION + 1
+        );
+        fs::write(&file, json).unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let err = manager.load(&file).unwrap_err();
+        assert!(err.to_string().contains("newer than this spath supports"));
+        let report = manager.repair(false).unwrap();
+        match &report.files[0].status {
+            BackupFileStatus::Corrupt(reason) => {
+                assert!(
+                    reason.contains("newer than this spath supports"),
+                    "{}",
+                    reason
+                )
+            }
+            other => panic!("unexpected status {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_new_backups_record_current_version() {
+        let backup = PathBackup::new("20240101_120000".to_string(), "C:\\Tools".to_string(), None);
+        assert_eq!(backup.version, BACKUP_FORMAT_VERSION);
+        assert!(serde_json::to_string(&backup)
+            .unwrap()
+            .contains("\"version\":"));
+    }
+}
+
+#[cfg(test)]
+mod lazy_backup_dir_tests {
+    use super::*;
+    use spath_cli::scanner::PathScanner;
+
+    #[test]
+    fn test_unwritable_backup_dir_only_fails_on_create() {
+        let dir = tempfile::tempdir().unwrap();
+        let blocker = dir.path().join("not_a_dir");
+        fs::write(&blocker, "file in the way").unwrap();
+        let backup_dir = blocker.join("backups");
+        let manager = BackupManager::with_backup_dir(&backup_dir).unwrap();
+        assert!(!backup_dir.exists());
+        assert!(manager.list().unwrap().is_empty());
+        assert!(PathScanner::from_string("C:\\Windows").scan().is_ok());
+        let err = manager
+            .create_from("C:\\Tools", None)
+            .expect_err("backup dir cannot be created");
+        assert!(err
+            .to_string()
+            .contains("Failed to create backup directory"));
+    }
+
+    #[test]
+    fn test_backup_dir_created_on_first_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let backup_dir = dir.path().join("spath").join("backups");
+        let manager = BackupManager::with_backup_dir(&backup_dir).unwrap();
+        assert!(!backup_dir.exists());
+        manager.create_from("C:\\Tools", None).unwrap();
+        assert!(backup_dir.is_dir());
+    }
+}
+
+#[cfg(test)]
+mod system_scope_tests {
+    use super::*;
+    use spath_cli::backup::PathBackup;
+    use spath_cli::registry::RegistryHelper;
+    use std::path::Path;
+
+    fn read_backup(path: &Path) -> PathBackup {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_user_only_backup_skips_system_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path())
+            .unwrap()
+            .without_system_scope();
+        let result = manager.create().unwrap();
+        assert!(read_backup(&result.path).system_path.is_none());
+    }
+
+    #[test]
+    fn test_scope_selection_decides_whether_system_path_is_read() {
+        let system_path = RegistryHelper::read_system_path_raw().ok();
+        let user_path = RegistryHelper::read_user_path_raw().unwrap();
+        let both_dir = tempfile::tempdir().unwrap();
+        let both = BackupManager::with_backup_dir(both_dir.path())
+            .unwrap()
+            .create()
+            .unwrap();
+        let both = read_backup(&both.path);
+        assert_eq!(both.user_path, user_path);
+        assert_eq!(both.system_path, system_path);
+        let user_dir = tempfile::tempdir().unwrap();
+        let user_only = BackupManager::with_backup_dir(user_dir.path())
+            .unwrap()
+            .without_system_scope()
+            .create()
+            .unwrap();
+        let user_only = read_backup(&user_only.path);
+        assert_eq!(user_only.user_path, user_path);
+        assert!(user_only.system_path.is_none());
+    }
+}
+
+#[cfg(test)]
+mod path_diff_tests {
+    use spath_cli::backup::{BackupManager, MovedEntry, PathDiff};
+
+    #[test]
+    fn test_diff_reports_added_removed_and_modified() {
+        let diff = PathDiff::between(
+            "C:\\Program Files\\App;C:\\Old;C:\\Tools",
+            "\"C:\\Program Files\\App\";C:\\tools;C:\\Go\\bin",
+        );
+        assert_eq!(diff.added, vec!["C:\\Go\\bin".to_string()]);
+        assert_eq!(diff.removed, vec!["C:\\Old".to_string()]);
+        assert_eq!(
+            diff.modified,
+            vec![
+                (
+                    "C:\\Program Files\\App".to_string(),
+                    "\"C:\\Program Files\\App\"".to_string()
+                ),
+                ("C:\\Tools".to_string(), "C:\\tools".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_identical_paths_have_empty_diff() {
+        assert!(PathDiff::between("C:\\Tools;C:\\Go\\bin", "C:\\Tools;C:\\Go\\bin").is_empty());
+    }
+
+    #[test]
+    fn test_diff_against_backup_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let created = manager.create_from("C:\\Tools", None).unwrap();
+        let diff = manager
+            .diff_against(&created.path, "C:\\Tools;C:\\Go\\bin")
+            .unwrap();
+        assert_eq!(diff.added, vec!["C:\\Go\\bin".to_string()]);
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["modified"], serde_json::json!([]));
+        assert_eq!(json["moved"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_diff_reports_moved_entries_separately() {
+        let diff = PathDiff::between("C:\\A;C:\\B;C:\\C;C:\\D", "C:\\D;C:\\A;C:\\B;C:\\C");
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(
+            diff.moved,
+            vec![MovedEntry {
+                path: "C:\\D".to_string(),
+                from: 3,
+                to: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_additions_and_removals_do_not_count_as_moves() {
+        let diff = PathDiff::between("C:\\Old;C:\\A;C:\\B", "C:\\A;C:\\New;C:\\B");
+        assert_eq!(diff.added, vec!["C:\\New".to_string()]);
+        assert_eq!(diff.removed, vec!["C:\\Old".to_string()]);
+        assert!(diff.moved.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod max_backups_tests {
+    use spath_cli::backup::BackupManager;
+    use std::fs;
+
+    fn seed_old_backups(dir: &std::path::Path, count: usize) {
+        for day in 0..count {
+            let name = format!("path_backup_2020010{}_000000.json", day + 1);
+            fs::write(
+                dir.join(name),
+                r#"{"timestamp":"old","user_path":"C:\\Old","system_path":null}"#,
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_custom_limit_removes_oldest_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        seed_old_backups(dir.path(), 4);
+        let manager = BackupManager::with_backup_dir(dir.path())
+            .unwrap()
+            .with_max_backups(2);
+        let result = manager.create_from("C:\\Tools", None).unwrap();
+        assert_eq!(result.cleaned_backups.len(), 3);
+        let remaining = manager.list().unwrap();
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(remaining[0], result.path);
+        assert!(remaining[1].ends_with("path_backup_20200104_000000.json"));
+    }
+
+    #[test]
+    fn test_zero_limit_keeps_the_new_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        seed_old_backups(dir.path(), 1);
+        let manager = BackupManager::with_backup_dir(dir.path())
+            .unwrap()
+            .with_max_backups(0);
+        let result = manager.create_from("C:\\Tools", None).unwrap();
+        assert_eq!(manager.list().unwrap(), vec![result.path]);
+    }
+
+    #[test]
+    fn test_cleanup_to_keeps_the_newest_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        for day in 1..=15 {
+            let name = format!("path_backup_202001{:02}_000000.json", day);
+            fs::write(dir.path().join(name), "{}").unwrap();
+        }
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let removed = manager.cleanup_to(5).unwrap();
+        assert_eq!(removed.len(), 10);
+        let remaining: Vec<String> = manager
+            .list()
+            .unwrap()
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        let expected: Vec<String> = (11..=15)
+            .rev()
+            .map(|day| format!("path_backup_202001{:02}_000000.json", day))
+            .collect();
+        assert_eq!(remaining, expected);
+    }
+
+    #[test]
+    fn test_cleanup_to_zero_keeps_one_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        seed_old_backups(dir.path(), 3);
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        assert_eq!(manager.cleanup_to(0).unwrap().len(), 2);
+        assert_eq!(manager.list().unwrap().len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod checksum_tests {
+    use super::*;
+    use spath_cli::backup::PathBackup;
+
+    fn tampered_backup(dir: &std::path::Path) -> (BackupManager, std::path::PathBuf) {
+        let manager = BackupManager::with_backup_dir(dir).unwrap();
+        let created = manager
+            .create_from("C:\\Tools;C:\\Go\\bin", Some("C:\\Windows".to_string()))
+            .unwrap();
+        let json = fs::read_to_string(&created.path).unwrap();
+        let mut backup: PathBackup = serde_json::from_str(&json).unwrap();
+        assert!(backup.checksum.is_some());
+        backup.user_path = "C:\\Tools".to_string();
+        fs::write(&created.path, serde_json::to_string(&backup).unwrap()).unwrap();
+        (manager, created.path)
+    }
+
+    #[test]
+    fn test_checksum_covers_both_scopes() {
+        let backup = PathBackup::new(
+            "20240101_120000".to_string(),
+            "C:\\Tools".to_string(),
+            Some("C:\\Windows".to_string()),
+        );
+        assert!(backup.verify_checksum().is_ok());
+        assert_ne!(
+            PathBackup::compute_checksum("C:\\Tools", Some("C:\\Windows")),
+            PathBackup::compute_checksum("C:\\Tools", None)
+        );
+    }
+
+    #[test]
+    fn test_tampered_user_path_is_rejected_by_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let (manager, file) = tampered_backup(dir.path());
+        let err = manager.restore(&file, true).unwrap_err();
+        assert!(err.to_string().contains("corrupted"));
+        assert!(manager.preview_restore(&file, "").is_err());
+    }
+
+    #[test]
+    fn test_repair_reports_tampered_backup_as_corrupt() {
+        let dir = tempfile::tempdir().unwrap();
+        let (manager, _) = tampered_backup(dir.path());
+        let report = manager.repair(false).unwrap();
+        assert!(matches!(
+            report.files[0].status,
+            BackupFileStatus::Corrupt(_)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod system_restore_tests {
+    use spath_cli::backup::{BackupManager, RestoreResult, SystemRestoreStatus};
+    use std::path::PathBuf;
+
+    fn result(restored: bool, error: Option<&str>) -> RestoreResult {
+        RestoreResult {
+            restored_from: PathBuf::from("backup.json"),
+            user_path_restored: false,
+            system_path_restored: restored,
+            system_path_error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_system_status_distinguishes_outcomes() {
+        assert_eq!(
+            result(true, None).system_status(),
+            SystemRestoreStatus::Restored
+        );
+        assert_eq!(
+            result(false, None).system_status(),
+            SystemRestoreStatus::Skipped
+        );
+        assert_eq!(
+            result(false, Some("Access is denied.")).system_status(),
+            SystemRestoreStatus::Failed("Access is denied.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_backup_without_system_path_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let created = manager.create_from("C:\\Tools", None).unwrap();
+        let restored = manager.restore_system_path(&created.path).unwrap();
+        assert!(!restored.user_path_restored);
+        assert_eq!(restored.system_status(), SystemRestoreStatus::Skipped);
+        assert!(manager
+            .preview_system_restore(&created.path, "C:\\Windows")
+            .is_err());
+    }
+
+    #[test]
+    fn test_system_preview_diffs_system_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let created = manager
+            .create_from(
+                "C:\\Tools",
+                Some("C:\\Windows;C:\\Windows\\System32".to_string()),
+            )
+            .unwrap();
+        let preview = manager
+            .preview_system_restore(&created.path, "C:\\Windows")
+            .unwrap();
+        assert_eq!(preview.restored_path, "C:\\Windows;C:\\Windows\\System32");
+        assert_eq!(preview.added, vec!["C:\\Windows\\System32".to_string()]);
+    }
+}
+
+#[cfg(test)]
+mod resolve_tests {
+    use spath_cli::backup::BackupManager;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn manager_with_backups(dir: &std::path::Path, count: usize) -> BackupManager {
+        for day in 1..=count {
+            fs::write(
+                dir.join(format!("path_backup_2024010{}_000000.json", day)),
+                "{}",
+            )
+            .unwrap();
+        }
+        BackupManager::with_backup_dir(dir).unwrap()
+    }
+
+    #[test]
+    fn test_latest_and_index_resolve_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = manager_with_backups(dir.path(), 3);
+        let newest = dir.path().join("path_backup_20240103_000000.json");
+        assert_eq!(manager.resolve("latest").unwrap(), newest);
+        assert_eq!(manager.resolve("LATEST").unwrap(), newest);
+        assert_eq!(
+            manager.resolve("2").unwrap(),
+            dir.path().join("path_backup_20240101_000000.json")
+        );
+    }
+
+    #[test]
+    fn test_index_out_of_range_is_a_friendly_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = manager_with_backups(dir.path(), 2);
+        let err = manager.resolve("5").unwrap_err().to_string();
+        assert!(err.contains("out of range"));
+        assert!(err.contains("2 backup(s) available"));
+    }
+
+    #[test]
+    fn test_latest_without_backups_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        assert!(manager.resolve("latest").is_err());
+    }
+
+    #[test]
+    fn test_file_name_is_passed_through() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        assert_eq!(
+            manager.resolve("backup.json").unwrap(),
+            PathBuf::from("backup.json")
+        );
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod config_tests {
+    use spath_cli::config::{SpathConfig, DEFAULT_CONFIG};
+    use std::fs;
+
+    #[test]
+    fn test_default_config_parses_to_defaults() {
+        assert_eq!(
+            SpathConfig::parse(DEFAULT_CONFIG).unwrap(),
+            SpathConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_reads_all_fields() {
+        let config = SpathConfig::parse(
+            "system = true\ndry_run = true\nmax_backups = 20\nexclude_drives = [\"e\"]\nexclude_patterns = [\"C:\\\\Old*\"]\n",
+        )
+        .unwrap();
+        assert!(config.system);
+        assert!(config.dry_run);
+        assert_eq!(config.max_backups, Some(20));
+        assert_eq!(config.exclude_drives, vec!['E']);
+        assert_eq!(config.exclude_patterns, vec!["C:\\Old*".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_reads_disabled_rules() {
+        let config = SpathConfig::parse("disabled_rules = [\"duplicate\"]\n").unwrap();
+        assert_eq!(config.disabled_rules, vec!["duplicate".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_fields_and_bad_drives() {
+        assert!(SpathConfig::parse("max_backup = 3\n").is_err());
+        assert!(SpathConfig::parse("exclude_drives = [\"1\"]\n").is_err());
+    }
+
+    #[test]
+    fn test_write_default_refuses_to_overwrite_without_force() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("spath").join("config.toml");
+        SpathConfig::write_default(&file, false).unwrap();
+        assert_eq!(
+            SpathConfig::load_from(&file).unwrap(),
+            SpathConfig::default()
+        );
+        fs::write(&file, "system = true\n").unwrap();
+        assert!(SpathConfig::write_default(&file, false).is_err());
+        assert!(SpathConfig::load_from(&file).unwrap().system);
+        SpathConfig::write_default(&file, true).unwrap();
+        assert!(!SpathConfig::load_from(&file).unwrap().system);
+    }
+}
+
+#[cfg(test)]
+mod exclude_pattern_tests {
+    use spath_cli::fixer::{FixOptions, PathFixer};
+    use spath_cli::scanner::PathScanner;
+    use spath_cli::utils::matches_exclude_pattern;
+
+    #[test]
+    fn test_wildcards_match_case_insensitively() {
+        assert!(matches_exclude_pattern(
+            "C:\\Tools\\Legacy2",
+            "c:\\tools\\legacy*"
+        ));
+        assert!(matches_exclude_pattern(
+            "\"C:\\Tools\\Legacy\\\"",
+            "C:\\Tools\\Legac?"
+        ));
+        assert!(matches_exclude_pattern(
+            "D:\\src\\app\\node_modules\\.bin",
+            "*\\node_modules\\.bin"
+        ));
+        assert!(!matches_exclude_pattern("C:\\Tools", "C:\\Tools\\*"));
+        assert!(!matches_exclude_pattern("C:\\Tools\\bin", "C:\\Tool"));
+    }
+
+    #[test]
+    fn test_scanner_skips_excluded_entries_silently() {
+        let results =
+            PathScanner::from_string("C:\\Missing Legacy\\bin;C:\\Windows;c:\\missing legacy\\bin")
+                .with_excluded_patterns(["c:\\missing legacy*".to_string()])
+                .scan()
+                .unwrap();
+        assert!(results
+            .issues
+            .iter()
+            .all(|i| !i.path.to_lowercase().contains("legacy")));
+        assert_eq!(results.audit.excluded, 2);
+        assert_eq!(results.audit.total_paths, 1);
+        assert_eq!(results.exclusions, vec!["c:\\missing legacy*".to_string()]);
+    }
+
+    #[test]
+    fn test_fix_leaves_excluded_entries_unchanged() {
+        let options = FixOptions {
+            excluded_patterns: vec!["*\\optional sdk*".to_string()],
+            ..Default::default()
+        };
+        let current = "C:\\Windows;C:\\Optional SDK\\bin;C:\\Windows";
+        let results = PathFixer::plan_fix(current, &options).unwrap();
+        assert_eq!(results.new_path, "C:\\Windows;C:\\Optional SDK\\bin");
+        assert_eq!(
+            results.changes,
+            vec!["Removed duplicate: C:\\Windows".to_string()]
+        );
+    }
+}
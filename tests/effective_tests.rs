@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod effective_tests {
+    use spath_cli::effective::{build_effective_path, compare_with_process};
+
+    fn paths(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_effective_path_is_system_then_user_expanded() {
+        std::env::set_var("SPATH_TEST_EFFECTIVE_ROOT", "C:\\Effective");
+        let effective = build_effective_path("C:\\Windows;", "%SPATH_TEST_EFFECTIVE_ROOT%\\bin");
+        assert_eq!(effective, paths(&["C:\\Windows", "C:\\Effective\\bin"]));
+    }
+
+    #[test]
+    fn test_stale_process_path_differs_from_registry() {
+        let registry = build_effective_path("C:\\Windows", "C:\\Tools;C:\\Go\\bin");
+        let process = paths(&["c:\\windows", "C:\\Tools", "C:\\OldTool"]);
+        let diff = compare_with_process(&process, &registry);
+        assert_eq!(diff.only_in_process, paths(&["C:\\OldTool"]));
+        assert_eq!(diff.only_in_registry, paths(&["C:\\Go\\bin"]));
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_fresh_process_path_matches_registry() {
+        let registry = build_effective_path("C:\\Windows", "C:\\Tools");
+        let diff = compare_with_process(&paths(&["C:\\Windows", "C:\\Tools"]), &registry);
+        assert!(diff.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod resolve_tests {
+    use spath_cli::effective::{default_path_extensions, resolve_executable};
+    use std::fs;
+
+    #[test]
+    fn test_first_directory_wins_and_later_copies_are_shadowed() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first");
+        let second = dir.path().join("second");
+        for tool_dir in [&first, &second] {
+            fs::create_dir(tool_dir).unwrap();
+            fs::write(tool_dir.join("tool.exe"), b"").unwrap();
+        }
+        let path = vec![
+            first.display().to_string(),
+            format!("\"{}\"", second.display()),
+        ];
+        let resolution = resolve_executable("tool", &path, &default_path_extensions());
+        assert_eq!(resolution.matches.len(), 2);
+        assert!(resolution.matches[0].wins);
+        assert_eq!(resolution.matches[0].directory, first.display().to_string());
+        assert!(!resolution.matches[1].wins);
+        assert_eq!(
+            resolution.matches[1].directory,
+            second.display().to_string()
+        );
+    }
+
+    #[test]
+    fn test_extension_order_and_explicit_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("tool.cmd"), b"").unwrap();
+        fs::write(dir.path().join("tool.exe"), b"").unwrap();
+        let path = vec![dir.path().display().to_string()];
+        let resolution = resolve_executable("tool", &path, &default_path_extensions());
+        assert!(resolution.matches[0].file.ends_with("tool.exe"));
+        assert_eq!(resolution.matches.len(), 2);
+        let explicit = resolve_executable("tool.cmd", &path, &default_path_extensions());
+        assert_eq!(explicit.matches.len(), 1);
+        assert!(explicit.matches[0].file.ends_with("tool.cmd"));
+    }
+
+    #[test]
+    fn test_missing_executable_has_no_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = vec![dir.path().display().to_string()];
+        assert!(
+            resolve_executable("nope", &path, &default_path_extensions())
+                .matches
+                .is_empty()
+        );
+    }
+}
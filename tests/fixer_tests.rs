@@ -139,3 +139,542 @@ mod fixer_tests {
         assert!(path.is_empty());
     }
 }
+
+#[cfg(test)]
+mod plan_fix_tests {
+    use spath_cli::fixer::{FixOptions, PathFixer};
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::PathScanner;
+
+    #[test]
+    fn test_plan_fix_rescan_has_no_critical_issues() {
+        let current = "C:\\Program Files\\Common Files;C:\\Windows";
+        let before = PathScanner::from_string(current).scan().unwrap();
+        assert!(before
+            .issues
+            .iter()
+            .any(|i| matches!(i.level, IssueLevel::Critical)));
+        let results = PathFixer::plan_fix(current, &FixOptions::default()).unwrap();
+        assert!(results.changed);
+        assert!(results.dry_run);
+        assert!(results.backup_created.is_none());
+        assert_eq!(
+            results.new_path,
+            "\"C:\\Program Files\\Common Files\";C:\\Windows"
+        );
+        let verification = results.verification.expect("changed PATH is re-scanned");
+        assert!(!verification
+            .issues
+            .iter()
+            .any(|i| matches!(i.level, IssueLevel::Critical)));
+    }
+
+    #[test]
+    fn test_plan_fix_quotes_variable_that_expands_to_spaces() {
+        let dir = tempfile::tempdir().unwrap();
+        let tools = dir.path().join("My Tools");
+        std::fs::create_dir(&tools).unwrap();
+        std::env::set_var("SPATH_TEST_QUOTE_ROOT", &tools);
+        let results = PathFixer::plan_fix(
+            "%SPATH_TEST_QUOTE_ROOT%;C:\\Windows",
+            &FixOptions::default(),
+        );
+        std::env::remove_var("SPATH_TEST_QUOTE_ROOT");
+        let results = results.unwrap();
+        assert_eq!(results.new_path, "\"%SPATH_TEST_QUOTE_ROOT%\";C:\\Windows");
+        assert!(results.changes.contains(
+            &"Added quotes: %SPATH_TEST_QUOTE_ROOT% -> \"%SPATH_TEST_QUOTE_ROOT%\"".to_string()
+        ));
+    }
+
+    #[test]
+    fn test_second_fix_keeps_quoted_variable_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let tools = dir.path().join("App bin");
+        std::fs::create_dir(&tools).unwrap();
+        std::env::set_var("SPATH_TEST_REQUOTE_ROOT", &tools);
+        let first = PathFixer::plan_fix("%SPATH_TEST_REQUOTE_ROOT%", &FixOptions::default());
+        let second = first
+            .as_ref()
+            .ok()
+            .map(|first| PathFixer::plan_fix(&first.new_path, &FixOptions::default()));
+        std::env::remove_var("SPATH_TEST_REQUOTE_ROOT");
+        let first = first.unwrap();
+        assert_eq!(first.new_path, "\"%SPATH_TEST_REQUOTE_ROOT%\"");
+        let second = second.unwrap().unwrap();
+        assert!(!second.changed, "{:?}", second.changes);
+        assert_eq!(second.new_path, first.new_path);
+    }
+
+    #[test]
+    fn test_plan_fix_unchanged_path_skips_rescan() {
+        let results = PathFixer::plan_fix("C:\\Windows", &FixOptions::default()).unwrap();
+        assert!(!results.changed);
+        assert_eq!(results.new_path, "C:\\Windows");
+        assert!(results.verification.is_none());
+    }
+}
+
+#[cfg(test)]
+mod keep_override_tests {
+    use spath_cli::fixer::{FixOptions, KeepOverrides, PathFixer};
+
+    const CURRENT: &str = "C:\\Windows;C:\\Windows\\System32;C:\\WINDOWS";
+
+    fn with_keep(keep: KeepOverrides) -> FixOptions {
+        FixOptions {
+            keep,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_default_keeps_first_occurrence() {
+        let results = PathFixer::plan_fix(CURRENT, &FixOptions::default()).unwrap();
+        assert_eq!(results.new_path, "C:\\Windows;C:\\Windows\\System32");
+    }
+
+    #[test]
+    fn test_keep_index_retains_specified_occurrence() {
+        let keep = KeepOverrides {
+            indices: vec![2],
+            ..Default::default()
+        };
+        let results = PathFixer::plan_fix(CURRENT, &with_keep(keep)).unwrap();
+        assert_eq!(results.new_path, "C:\\Windows\\System32;C:\\WINDOWS");
+        assert!(results
+            .changes
+            .contains(&"Removed duplicate: C:\\Windows".to_string()));
+    }
+
+    #[test]
+    fn test_keep_path_retains_exact_match() {
+        let keep = KeepOverrides {
+            paths: vec!["C:\\WINDOWS".to_string()],
+            ..Default::default()
+        };
+        let results = PathFixer::plan_fix(CURRENT, &with_keep(keep)).unwrap();
+        assert_eq!(results.new_path, "C:\\Windows\\System32;C:\\WINDOWS");
+    }
+
+    #[test]
+    fn test_keep_override_errors() {
+        let out_of_range = KeepOverrides {
+            indices: vec![3],
+            ..Default::default()
+        };
+        assert!(PathFixer::plan_fix(CURRENT, &with_keep(out_of_range)).is_err());
+        let unknown = KeepOverrides {
+            paths: vec!["C:\\Nowhere".to_string()],
+            ..Default::default()
+        };
+        assert!(PathFixer::plan_fix(CURRENT, &with_keep(unknown)).is_err());
+    }
+}
+
+#[cfg(test)]
+mod app_alias_tests {
+    use spath_cli::fixer::{FixOptions, PathFixer};
+
+    #[test]
+    fn test_fix_keeps_alias_dir() {
+        let current = "C:\\Users\\spath_test\\AppData\\Local\\Microsoft\\WindowsApps";
+        let results = PathFixer::plan_fix(current, &FixOptions::default()).unwrap();
+        assert!(!results.changed);
+        assert!(results.changes.is_empty());
+        assert_eq!(results.new_path, current);
+    }
+}
+
+#[cfg(test)]
+mod excluded_drive_tests {
+    use spath_cli::fixer::{FixOptions, PathFixer};
+
+    #[test]
+    fn test_entries_on_excluded_drive_are_not_removed() {
+        let current = "C:\\Windows;Z:\\spath_missing_dir;C:\\spath_missing_dir";
+        let options = FixOptions {
+            excluded_drives: vec!['z'],
+            ..Default::default()
+        };
+        let results = PathFixer::plan_fix(current, &options).unwrap();
+        assert_eq!(results.new_path, "C:\\Windows;Z:\\spath_missing_dir");
+        assert_eq!(
+            results.changes,
+            vec!["Removed non-existent: C:\\spath_missing_dir".to_string()]
+        );
+    }
+}
+
+#[cfg(test)]
+mod existence_timeout_tests {
+    use spath_cli::fixer::{FixOptions, PathFixer};
+    use std::path::Path;
+    use std::thread;
+    use std::time::Duration;
+
+    fn slow_missing(_: &Path) -> bool {
+        thread::sleep(Duration::from_millis(500));
+        false
+    }
+
+    #[test]
+    fn test_timed_out_entry_is_kept() {
+        let options = FixOptions {
+            existence_timeout: Some(Duration::from_millis(20)),
+            existence_check: Some(slow_missing),
+            ..Default::default()
+        };
+        let results = PathFixer::plan_fix("\\\\server\\share\\tools", &options).unwrap();
+        assert_eq!(results.new_path, "\\\\server\\share\\tools");
+        assert!(!results.changed);
+    }
+}
+
+#[cfg(test)]
+mod canonicalize_case_tests {
+    use spath_cli::fixer::{FixOptions, PathFixer};
+    use std::fs;
+
+    fn options() -> FixOptions {
+        FixOptions {
+            canonicalize_case: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_wrong_case_survivor_is_rewritten_to_canonical() {
+        let results = PathFixer::plan_fix("c:\\windows;C:\\Windows", &options()).unwrap();
+        assert_eq!(results.new_path, "C:\\Windows");
+        assert!(results
+            .changes
+            .contains(&"Canonicalized case: c:\\windows -> C:\\Windows".to_string()));
+    }
+
+    #[test]
+    fn test_case_variant_of_created_dir_uses_on_disk_casing() {
+        let dir = tempfile::tempdir().unwrap();
+        let created = dir.path().join("MixedCase");
+        fs::create_dir(&created).unwrap();
+        let lower = created.to_string_lossy().replace("MixedCase", "mixedcase");
+        let current = format!("{};{}", lower, created.display());
+        let results = PathFixer::plan_fix(&current, &options()).unwrap();
+        assert!(results.new_path.ends_with("\\MixedCase"));
+        assert!(!results.new_path.contains(';'));
+    }
+
+    #[test]
+    fn test_casing_is_kept_without_option() {
+        let results =
+            PathFixer::plan_fix("c:\\windows;C:\\Windows", &FixOptions::default()).unwrap();
+        assert_eq!(results.new_path, "c:\\windows");
+    }
+}
+
+#[cfg(test)]
+mod resolution_change_tests {
+    use spath_cli::fixer::{FixOptions, KeepOverrides, PathFixer};
+    use std::fs;
+
+    #[test]
+    fn test_dedup_that_changes_winner_is_called_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first");
+        let second = dir.path().join("second");
+        for tool_dir in [&first, &second] {
+            fs::create_dir(tool_dir).unwrap();
+            fs::write(tool_dir.join("tool.exe"), b"").unwrap();
+        }
+        fs::write(first.join("only-first.cmd"), b"").unwrap();
+        let (first, second) = (first.display().to_string(), second.display().to_string());
+        let current = format!("{};{};{}", first, second, first);
+        let options = FixOptions {
+            keep: KeepOverrides {
+                indices: vec![2],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let results = PathFixer::plan_fix(&current, &options).unwrap();
+        assert_eq!(results.new_path, format!("{};{}", second, first));
+        assert_eq!(results.resolution_changes.len(), 1);
+        let change = &results.resolution_changes[0];
+        assert_eq!(change.executable, "tool.exe");
+        assert_eq!(change.before.as_deref(), Some(first.as_str()));
+        assert_eq!(change.after.as_deref(), Some(second.as_str()));
+    }
+
+    #[test]
+    fn test_plain_dedup_keeps_winners() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("tool.exe"), b"").unwrap();
+        let tools = dir.path().display().to_string();
+        let current = format!("{};C:\\Windows;{}", tools, tools);
+        let results = PathFixer::plan_fix(&current, &FixOptions::default()).unwrap();
+        assert!(results.changed);
+        assert!(results.resolution_changes.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod apply_tests {
+    use anyhow::Result;
+    use spath_cli::backup::BackupManager;
+    use spath_cli::fixer::{validate_path_value, PathFixer};
+    use spath_cli::registry::UserPathSource;
+    use std::cell::RefCell;
+
+    struct FakeSource {
+        value: RefCell<String>,
+        writes: RefCell<Vec<String>>,
+    }
+
+    impl FakeSource {
+        fn new(value: &str) -> Self {
+            Self {
+                value: RefCell::new(value.to_string()),
+                writes: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl UserPathSource for FakeSource {
+        fn read_user_path(&self) -> Result<String> {
+            Ok(self.value.borrow().clone())
+        }
+        fn write_user_path(&self, path: &str) -> Result<()> {
+            self.writes.borrow_mut().push(path.to_string());
+            *self.value.borrow_mut() = path.to_string();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_apply_writes_validated_value_and_backs_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixer =
+            PathFixer::with_backup_manager(BackupManager::with_backup_dir(dir.path()).unwrap());
+        let source = FakeSource::new("C:\\Old");
+        let result = fixer
+            .apply_path_value("C:\\Windows;C:\\Tools;\r\n", &source)
+            .unwrap();
+        assert_eq!(result.previous_path, "C:\\Old");
+        assert_eq!(result.new_path, "C:\\Windows;C:\\Tools");
+        assert_eq!(
+            *source.writes.borrow(),
+            vec!["C:\\Windows;C:\\Tools".to_string()]
+        );
+        let backup = std::fs::read_to_string(&result.backup.path).unwrap();
+        assert!(backup.contains("C:\\\\Old"));
+    }
+
+    #[test]
+    fn test_preview_restore_does_not_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let backup = manager.create_from("C:\\Tools;C:\\Go\\bin", None).unwrap();
+        let fixer = PathFixer::with_backup_manager(manager);
+        let source = FakeSource::new("C:\\Tools;C:\\Python312");
+        let preview = fixer.preview_restore_from(&backup.path, &source).unwrap();
+        assert_eq!(preview.added, vec!["C:\\Go\\bin".to_string()]);
+        assert_eq!(preview.removed, vec!["C:\\Python312".to_string()]);
+        assert!(source.writes.borrow().is_empty());
+        assert_eq!(*source.value.borrow(), "C:\\Tools;C:\\Python312");
+    }
+
+    #[test]
+    fn test_apply_rejects_invalid_value_without_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixer =
+            PathFixer::with_backup_manager(BackupManager::with_backup_dir(dir.path()).unwrap());
+        let source = FakeSource::new("C:\\Old");
+        assert!(fixer.apply_path_value("C:\\A;;C:\\B", &source).is_err());
+        assert!(source.writes.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_validate_path_value() {
+        assert!(validate_path_value("").is_err());
+        assert!(validate_path_value("  \n").is_err());
+        assert!(validate_path_value("C:\\A\nC:\\B").is_err());
+        assert!(validate_path_value("C:\\A\u{0}").is_err());
+        assert!(validate_path_value(&"C:\\A;".repeat(1000)).is_err());
+        assert_eq!(validate_path_value("C:\\A;C:\\B\n").unwrap(), "C:\\A;C:\\B");
+    }
+
+    #[test]
+    fn test_apply_rejects_unbalanced_quotes_without_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixer =
+            PathFixer::with_backup_manager(BackupManager::with_backup_dir(dir.path()).unwrap());
+        let source = FakeSource::new("C:\\Old");
+        let err = fixer
+            .apply_path_value("C:\\Windows;\"C:\\Program Files\\App", &source)
+            .err()
+            .expect("unbalanced quotes are rejected");
+        assert!(err.to_string().contains("unbalanced quotes"));
+        assert!(source.writes.borrow().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod quoting_validation_tests {
+    use spath_cli::fixer::{validate_quoting, FixOptions, PathFixer};
+
+    #[test]
+    fn test_fixed_path_with_unbalanced_quote_fails_pre_write_check() {
+        let results = PathFixer::plan_fix(
+            "\"C:\\Windows;C:\\Windows\\System32",
+            &FixOptions::default(),
+        )
+        .unwrap();
+        assert!(results.new_path.starts_with("\"C:\\Windows"));
+        assert!(validate_quoting(&results.new_path).is_err());
+    }
+
+    #[test]
+    fn test_balanced_quoting_passes() {
+        assert!(validate_quoting("\"C:\\Program Files\\App\";C:\\Windows").is_ok());
+        assert!(validate_quoting("C:\\A\"\"B;C:\\Windows").is_ok());
+        assert!(validate_quoting("C:\\Windows;C:\\Tools\"").is_err());
+    }
+}
+
+#[cfg(test)]
+mod remediation_projection_tests {
+    use spath_cli::fixer::project_remediation;
+    use spath_cli::migrator::{ActionType, MigrationAction, MigrationPlan, ReasonCode};
+    use spath_cli::models::PathLocation;
+    use spath_cli::scanner::PathScanner;
+
+    const USER_PATH: &str =
+        "C:\\Program Files\\Common Files;C:\\spath_missing_dir;C:\\Windows;c:\\windows;.";
+
+    fn clean_plan() -> MigrationPlan {
+        MigrationPlan {
+            actions: vec![MigrationAction {
+                action_type: ActionType::RemoveDuplicate,
+                path: "c:\\windows".to_string(),
+                from_location: PathLocation::User,
+                reason_code: ReasonCode::DuplicateAcrossScopes,
+                reason: "Duplicate - already exists in SYSTEM PATH".to_string(),
+            }],
+            requires_admin: false,
+            protected_violations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_projected_resolution_counts() {
+        let results = PathScanner::from_string(USER_PATH)
+            .with_location(PathLocation::User)
+            .scan()
+            .unwrap();
+        let plan = clean_plan();
+        let projection = project_remediation(&results, Some(&plan)).unwrap();
+        assert_eq!(projection.total, 4);
+        assert_eq!(projection.fix_resolves, Some(3));
+        assert_eq!(projection.clean_resolves, Some(1));
+    }
+
+    #[test]
+    fn test_system_scan_has_no_fix_projection() {
+        let results = PathScanner::from_string(USER_PATH)
+            .with_location(PathLocation::System)
+            .scan()
+            .unwrap();
+        let projection = project_remediation(&results, None).unwrap();
+        assert_eq!(projection.fix_resolves, None);
+        assert_eq!(projection.clean_resolves, None);
+    }
+}
+
+#[cfg(test)]
+mod removal_plan_tests {
+    use spath_cli::fixer::RemovalPlan;
+    use spath_cli::models::PathLocation;
+
+    #[test]
+    fn test_removes_first_case_insensitive_match() {
+        let plan = RemovalPlan::new(
+            "C:\\Tools;\"c:\\tools\";C:\\Go\\bin",
+            "c:\\TOOLS",
+            PathLocation::User,
+            false,
+        )
+        .unwrap();
+        assert_eq!(plan.removed, vec!["C:\\Tools".to_string()]);
+        assert_eq!(plan.new_path, "\"c:\\tools\";C:\\Go\\bin");
+    }
+
+    #[test]
+    fn test_all_removes_every_match() {
+        let plan = RemovalPlan::new(
+            "C:\\Tools;\"c:\\tools\";C:\\Go\\bin",
+            "\"C:\\Tools\"",
+            PathLocation::User,
+            true,
+        )
+        .unwrap();
+        assert_eq!(plan.removed.len(), 2);
+        assert_eq!(plan.new_path, "C:\\Go\\bin");
+    }
+
+    #[test]
+    fn test_missing_entry_is_an_error() {
+        let err = RemovalPlan::new("C:\\Tools", "C:\\Nope", PathLocation::User, false).unwrap_err();
+        assert!(err.to_string().contains("not on the USER PATH"));
+    }
+
+    #[test]
+    fn test_removing_system32_is_a_protected_violation() {
+        let plan = RemovalPlan::new(
+            "C:\\Windows\\System32;C:\\Windows",
+            "C:\\Windows\\System32",
+            PathLocation::System,
+            false,
+        )
+        .unwrap();
+        assert_eq!(plan.protected_violations.len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod system_fix_tests {
+    use spath_cli::fixer::{FixOptions, PathFixer};
+    use spath_cli::models::PathLocation;
+    use spath_cli::registry::RegistryHelper;
+
+    #[test]
+    fn test_plan_fix_defaults_to_user_scope() {
+        let Ok(system_path) = RegistryHelper::read_system_path_raw() else {
+            return;
+        };
+        let Some(first) = system_path.split(';').find(|e| !e.trim().is_empty()) else {
+            return;
+        };
+        let path = format!("{};{}", system_path, first);
+        let results = PathFixer::plan_fix(&path, &FixOptions::default()).unwrap();
+        assert!(results.changed);
+        assert_eq!(results.location, PathLocation::User);
+        assert!(results.system_path_error.is_none());
+        assert_eq!(RegistryHelper::read_system_path_raw().unwrap(), system_path);
+    }
+
+    #[test]
+    fn test_system_dry_run_plans_against_system_path() {
+        let Ok(system_path) = RegistryHelper::read_system_path_raw() else {
+            return;
+        };
+        let results = PathFixer::new()
+            .unwrap()
+            .fix_system_path(true, false)
+            .unwrap();
+        let planned = PathFixer::plan_fix(&system_path, &FixOptions::default()).unwrap();
+        assert_eq!(results.location, PathLocation::System);
+        assert!(results.dry_run);
+        assert!(results.backup_created.is_none());
+        assert_eq!(results.new_path, planned.new_path);
+    }
+}
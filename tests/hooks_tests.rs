@@ -0,0 +1,17 @@
+use spath_cli::hooks;
+
+#[test]
+fn test_broadcast_environment_change_returns_ok() {
+    assert!(hooks::broadcast_environment_change().is_ok());
+}
+
+#[test]
+fn test_post_hook_runs_harmless_command() {
+    assert!(hooks::run_post_hook("echo spath post-hook").is_ok());
+}
+
+#[test]
+fn test_post_hook_reports_failure_exit_code() {
+    let err = hooks::run_post_hook("exit 3").unwrap_err();
+    assert!(err.to_string().contains("exit 3"));
+}
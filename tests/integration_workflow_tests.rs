@@ -329,3 +329,40 @@ mod integration_workflow_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod scanner_backed_workflow_tests {
+    use spath_cli::constants::{PROGRAM_FILES, WINDOWS_PATH};
+    use spath_cli::fixer::{FixOptions, PathFixer};
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::{PathScanner, DUPLICATE_PATH_MESSAGE};
+
+    #[test]
+    fn test_scan_of_captured_path_detects_issues() {
+        let captured = format!(
+            "{}\\Git\\cmd;{};{}",
+            PROGRAM_FILES, WINDOWS_PATH, WINDOWS_PATH
+        );
+        let results = PathScanner::from_string(captured).scan().unwrap();
+        assert_eq!(results.paths.len(), 3);
+        assert_eq!(results.audit.unquoted_with_spaces, 1);
+        assert!(results
+            .issues
+            .iter()
+            .any(|i| i.message == DUPLICATE_PATH_MESSAGE));
+    }
+
+    #[test]
+    fn test_fix_then_rescan_clears_critical_issues() {
+        let captured = format!("{};{}\\Common Files", WINDOWS_PATH, PROGRAM_FILES);
+        let before = PathScanner::from_string(captured.clone()).scan().unwrap();
+        assert!(before
+            .issues
+            .iter()
+            .any(|i| i.level == IssueLevel::Critical));
+        let fixed = PathFixer::plan_fix(&captured, &FixOptions::default()).unwrap();
+        assert!(fixed.changed);
+        let after = PathScanner::from_string(fixed.new_path).scan().unwrap();
+        assert!(after.issues.iter().all(|i| i.level != IssueLevel::Critical));
+    }
+}
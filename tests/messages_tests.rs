@@ -0,0 +1,21 @@
+#[cfg(test)]
+mod messages_tests {
+    use spath_cli::messages::{message, Locale, MessageKey};
+
+    #[test]
+    fn test_message_resolves_per_locale() {
+        let english = message(MessageKey::NoIssuesFound, Locale::En);
+        let russian = message(MessageKey::NoIssuesFound, Locale::Ru);
+        assert_eq!(english, "No security issues found.");
+        assert_ne!(english, russian);
+    }
+
+    #[test]
+    fn test_parse_locale_tags() {
+        assert_eq!(Locale::parse("ru"), Some(Locale::Ru));
+        assert_eq!(Locale::parse("ru_RU.UTF-8"), Some(Locale::Ru));
+        assert_eq!(Locale::parse("en-US"), Some(Locale::En));
+        assert_eq!(Locale::parse("C"), Some(Locale::En));
+        assert_eq!(Locale::parse("de_DE"), None);
+    }
+}
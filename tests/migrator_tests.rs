@@ -1,4 +1,19 @@
 ﻿use spath_cli::constants::{PROGRAM_FILES, WINDOWS_PATH};
+use spath_cli::models::{PathCategory, PathEntry, PathLocation};
+
+/// Existing entry at `path`, with the space and quote flags the scanner
+/// would set.
+fn entry(path: &str, location: PathLocation, category: PathCategory) -> PathEntry {
+    PathEntry {
+        path: path.to_string(),
+        location,
+        category,
+        exists: true,
+        has_spaces: path.contains(' '),
+        is_quoted: path.starts_with('"') && path.ends_with('"'),
+        ..Default::default()
+    }
+}
 
 #[cfg(test)]
 mod migrator_tests {
@@ -133,3 +148,364 @@ mod migrator_tests {
         assert!(path.starts_with('"') && path.ends_with('"'));
     }
 }
+
+#[cfg(test)]
+mod protected_entry_tests {
+    use spath_cli::analyzer::AnalysisResults;
+    use spath_cli::migrator::PathMigrator;
+    use spath_cli::models::{PathCategory, PathEntry, PathLocation};
+    use spath_cli::utils::{is_protected_path, protected_violations};
+
+    fn paths(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_builtin_protected_paths() {
+        assert!(is_protected_path("C:\\Windows\\System32"));
+        assert!(is_protected_path("\"c:\\windows\\system32\\\""));
+        assert!(is_protected_path("%SystemRoot%\\System32"));
+        assert!(!is_protected_path("C:\\Tools"));
+    }
+
+    #[test]
+    fn test_dropping_system32_is_a_violation() {
+        let before = paths(&["C:\\Windows\\System32", "C:\\Tools"]);
+        let after = paths(&["C:\\Tools"]);
+        let violations = protected_violations(&before, &after);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("C:\\Windows\\System32"));
+    }
+
+    #[test]
+    fn test_demoting_system32_is_a_violation() {
+        let before = paths(&["C:\\Windows\\System32", "C:\\Tools"]);
+        let after = paths(&["C:\\Tools", "C:\\Windows\\System32"]);
+        assert_eq!(protected_violations(&before, &after).len(), 1);
+    }
+
+    #[test]
+    fn test_removing_later_entries_is_allowed() {
+        let before = paths(&[
+            "C:\\Windows\\System32",
+            "C:\\Tools",
+            "C:\\Windows\\System32",
+        ]);
+        let after = paths(&["C:\\Windows\\System32"]);
+        assert!(protected_violations(&before, &after).is_empty());
+    }
+
+    #[test]
+    fn test_plan_dropping_system32_is_blocked_by_default() {
+        let entry = PathEntry {
+            path: "C:\\Windows\\System32".to_string(),
+            location: PathLocation::System,
+            category: PathCategory::UserProgram,
+            exists: true,
+            ..Default::default()
+        };
+        let analysis = AnalysisResults {
+            entries: vec![entry],
+        };
+        let migrator = PathMigrator::new().unwrap();
+        let plan = migrator.plan_migration(&analysis, true, true).unwrap();
+        assert_eq!(plan.protected_violations.len(), 1);
+        let err = migrator
+            .execute_migration(&plan, false, false)
+            .expect_err("protected entry removal must be blocked");
+        assert!(err.to_string().contains("--force"));
+    }
+}
+
+#[cfg(test)]
+mod reason_code_tests {
+    use super::entry;
+    use spath_cli::analyzer::AnalysisResults;
+    use spath_cli::migrator::{ActionType, PathMigrator, ReasonCode};
+    use spath_cli::models::{PathCategory, PathLocation};
+
+    #[test]
+    fn test_duplicate_across_scopes_reason_code() {
+        let analysis = AnalysisResults {
+            entries: vec![
+                entry("C:\\Tools", PathLocation::System, PathCategory::Ambiguous),
+                entry("c:\\tools", PathLocation::User, PathCategory::Ambiguous),
+            ],
+        };
+        let plan = PathMigrator::new()
+            .unwrap()
+            .plan_migration(&analysis, true, false)
+            .unwrap();
+        assert_eq!(plan.actions.len(), 1);
+        assert!(matches!(
+            plan.actions[0].action_type,
+            ActionType::RemoveDuplicate
+        ));
+        assert_eq!(
+            plan.actions[0].reason_code,
+            ReasonCode::DuplicateAcrossScopes
+        );
+    }
+
+    #[test]
+    fn test_user_path_in_system_and_unquoted_reason_codes() {
+        let analysis = AnalysisResults {
+            entries: vec![
+                entry(
+                    "C:\\Users\\dev\\bin",
+                    PathLocation::System,
+                    PathCategory::UserProgram,
+                ),
+                entry(
+                    "C:\\Program Files\\Tool",
+                    PathLocation::User,
+                    PathCategory::SystemProgram,
+                ),
+            ],
+        };
+        let plan = PathMigrator::new()
+            .unwrap()
+            .plan_migration(&analysis, false, true)
+            .unwrap();
+        let codes: Vec<ReasonCode> = plan.actions.iter().map(|a| a.reason_code).collect();
+        assert_eq!(
+            codes,
+            vec![ReasonCode::UserPathInSystem, ReasonCode::UnquotedSpaces]
+        );
+    }
+
+    #[test]
+    fn test_reason_code_in_json_output() {
+        let analysis = AnalysisResults {
+            entries: vec![entry(
+                "C:\\Users\\dev\\bin",
+                PathLocation::System,
+                PathCategory::UserProgram,
+            )],
+        };
+        let plan = PathMigrator::new()
+            .unwrap()
+            .plan_migration(&analysis, false, true)
+            .unwrap();
+        let json = serde_json::to_value(&plan).unwrap();
+        assert_eq!(json["actions"][0]["reason_code"], "user_path_in_system");
+        assert_eq!(json["actions"][0]["action_type"], "move_to_user");
+        assert_eq!(json["actions"][0]["from_location"], "system");
+    }
+}
+
+#[cfg(test)]
+mod quote_fix_tests {
+    use super::entry;
+    use spath_cli::analyzer::AnalysisResults;
+    use spath_cli::migrator::{ActionType, PathMigrator};
+    use spath_cli::models::{PathCategory, PathLocation};
+    use spath_cli::prompt::{AutoConfirmPrompter, Prompter};
+
+    fn quoted_paths(analysis: &AnalysisResults, move_user_paths: bool) -> Vec<String> {
+        PathMigrator::new()
+            .unwrap()
+            .plan_migration(analysis, true, move_user_paths)
+            .unwrap()
+            .actions
+            .iter()
+            .filter(|a| matches!(a.action_type, ActionType::AddQuotes))
+            .map(|a| a.path.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_quote_fixes_cover_both_scopes() {
+        let entries = vec![
+            entry(
+                "C:\\Program Files\\Git\\cmd",
+                PathLocation::System,
+                PathCategory::SystemProgram,
+            ),
+            entry("D:\\My Tools", PathLocation::User, PathCategory::Ambiguous),
+            entry(
+                "\"C:\\Quoted Dir\"",
+                PathLocation::User,
+                PathCategory::Ambiguous,
+            ),
+            entry("C:\\Tools", PathLocation::User, PathCategory::Ambiguous),
+        ];
+        let actions = PathMigrator::new().unwrap().plan_quote_fixes(&entries);
+        let planned: Vec<(&str, PathLocation)> = actions
+            .iter()
+            .map(|a| (a.path.as_str(), a.from_location))
+            .collect();
+        assert_eq!(
+            planned,
+            vec![
+                ("C:\\Program Files\\Git\\cmd", PathLocation::System),
+                ("D:\\My Tools", PathLocation::User),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clean_plan_includes_quote_fixes_without_moving() {
+        let analysis = AnalysisResults {
+            entries: vec![entry(
+                "D:\\My Tools",
+                PathLocation::User,
+                PathCategory::Ambiguous,
+            )],
+        };
+        assert_eq!(
+            quoted_paths(&analysis, false),
+            vec!["D:\\My Tools".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_moved_entry_is_not_quoted_twice() {
+        let analysis = AnalysisResults {
+            entries: vec![entry(
+                "C:\\Users\\dev\\My Tools",
+                PathLocation::System,
+                PathCategory::UserProgram,
+            )],
+        };
+        assert!(quoted_paths(&analysis, true).is_empty());
+        assert_eq!(quoted_paths(&analysis, false).len(), 1);
+    }
+
+    #[test]
+    fn test_select_actions_recomputes_admin_requirement() {
+        let analysis = AnalysisResults {
+            entries: vec![
+                entry(
+                    "C:\\Program Files\\App",
+                    PathLocation::System,
+                    PathCategory::SystemProgram,
+                ),
+                entry("D:\\My Tools", PathLocation::User, PathCategory::Ambiguous),
+            ],
+        };
+        let migrator = PathMigrator::new().unwrap();
+        let plan = migrator.plan_migration(&analysis, true, false).unwrap();
+        assert!(plan.requires_admin);
+        let user_only = migrator.select_actions(&plan, |a| a.from_location == PathLocation::User);
+        assert_eq!(user_only.actions.len(), 1);
+        assert!(!user_only.requires_admin);
+    }
+
+    /// Answers no to every prompt mentioning SYSTEM.
+    struct DeclineSystem;
+
+    impl Prompter for DeclineSystem {
+        fn confirm(&self, message: &str) -> bool {
+            !message.contains("SYSTEM")
+        }
+    }
+
+    #[test]
+    fn test_confirm_actions_keeps_only_confirmed_actions() {
+        let analysis = AnalysisResults {
+            entries: vec![
+                entry(
+                    "C:\\Program Files\\App",
+                    PathLocation::System,
+                    PathCategory::SystemProgram,
+                ),
+                entry("D:\\My Tools", PathLocation::User, PathCategory::Ambiguous),
+            ],
+        };
+        let migrator = PathMigrator::new()
+            .unwrap()
+            .with_prompter(Box::new(DeclineSystem));
+        let plan = migrator.plan_migration(&analysis, true, false).unwrap();
+        let confirmed =
+            migrator.confirm_actions(&plan, |a| format!("{} {}?", a.from_location, a.path));
+        assert_eq!(confirmed.actions.len(), 1);
+        assert_eq!(confirmed.actions[0].from_location, PathLocation::User);
+        assert!(!confirmed.requires_admin);
+    }
+
+    #[test]
+    fn test_auto_confirm_prompter_accepts_everything() {
+        let migrator = PathMigrator::new()
+            .unwrap()
+            .with_prompter(Box::new(AutoConfirmPrompter));
+        assert!(migrator.confirm("Write the reordered USER PATH?"));
+    }
+}
+
+#[cfg(test)]
+mod reorder_tests {
+    use spath_cli::migrator::{ActionType, PathMigrator, ReorderOp};
+
+    fn paths() -> Vec<String> {
+        ["C:\\A", "C:\\B", "C:\\C", "C:\\D"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_move_and_swap_commands() {
+        let ops = ReorderOp::parse_all(&["move", "3", "0", "SWAP", "1", "2"]).unwrap();
+        assert_eq!(
+            ops,
+            vec![
+                ReorderOp::Move { from: 3, to: 0 },
+                ReorderOp::Swap {
+                    first: 1,
+                    second: 2
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_commands() {
+        assert!(ReorderOp::parse_all(&["move", "1"]).is_err());
+        assert!(ReorderOp::parse_all(&["shift", "1", "2"]).is_err());
+        assert!(ReorderOp::parse_all(&["move", "x", "2"]).is_err());
+    }
+
+    #[test]
+    fn test_move_and_swap_reorder_entries() {
+        let Ok(migrator) = PathMigrator::new() else {
+            return;
+        };
+        let ops = [
+            ReorderOp::Move { from: 3, to: 0 },
+            ReorderOp::Swap {
+                first: 1,
+                second: 3,
+            },
+        ];
+        let (plan, reordered) = migrator.plan_reorder(&paths(), &ops).unwrap();
+        assert_eq!(reordered, vec!["C:\\D", "C:\\C", "C:\\B", "C:\\A"]);
+        assert_eq!(plan.actions.len(), 3);
+        assert!(matches!(
+            plan.actions[0].action_type,
+            ActionType::ReorderAction { from: 3, to: 0 }
+        ));
+        assert_eq!(plan.actions[0].path, "C:\\D");
+        assert!(!plan.requires_admin);
+    }
+
+    #[test]
+    fn test_out_of_range_index_is_rejected() {
+        let Ok(migrator) = PathMigrator::new() else {
+            return;
+        };
+        let ops = [ReorderOp::Move { from: 4, to: 0 }];
+        assert!(migrator.plan_reorder(&paths(), &ops).is_err());
+    }
+
+    #[test]
+    fn test_demoting_protected_entry_is_reported() {
+        let Ok(migrator) = PathMigrator::new() else {
+            return;
+        };
+        let paths = vec!["C:\\Windows\\System32".to_string(), "C:\\Tools".to_string()];
+        let ops = [ReorderOp::Move { from: 1, to: 0 }];
+        let (plan, _) = migrator.plan_reorder(&paths, &ops).unwrap();
+        assert!(!plan.protected_violations.is_empty());
+    }
+}
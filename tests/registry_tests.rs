@@ -1,5 +1,88 @@
 ﻿use spath_cli::constants::WINDOWS_PATH;
+use spath_cli::registry::{PathValueStore, RegistryHelper};
+use std::cell::RefCell;
+use std::io;
 use std::path::PathBuf;
+use winreg::enums::*;
+use winreg::types::FromRegValue;
+use winreg::RegValue;
+
+/// In-memory `Path` value whose writes can be truncated to simulate a
+/// partial write, or fail with `write_error`.
+struct FakeStore {
+    value: RefCell<Option<RegValue>>,
+    truncate_to: Option<usize>,
+    write_error: Option<io::ErrorKind>,
+    writes: RefCell<usize>,
+}
+
+impl FakeStore {
+    fn new(value: Option<&str>, truncate_to: Option<usize>) -> Self {
+        Self::with_type(value, REG_SZ, truncate_to)
+    }
+    fn with_type(value: Option<&str>, vtype: RegType, truncate_to: Option<usize>) -> Self {
+        let value = value.map(|path| {
+            let mut value = RegistryHelper::path_reg_value(path, None);
+            value.vtype = vtype;
+            value
+        });
+        Self {
+            value: RefCell::new(value),
+            truncate_to,
+            write_error: None,
+            writes: RefCell::new(0),
+        }
+    }
+    fn failing(value: Option<&str>, write_error: io::ErrorKind) -> Self {
+        Self {
+            write_error: Some(write_error),
+            ..Self::new(value, None)
+        }
+    }
+}
+
+impl PathValueStore for FakeStore {
+    fn read_path(&self) -> io::Result<String> {
+        String::from_reg_value(&self.read_raw()?)
+    }
+    fn write_path(&self, path: &str) -> io::Result<()> {
+        let stored = match self.truncate_to {
+            Some(len) if *self.writes.borrow() == 0 => &path[..len],
+            _ => path,
+        };
+        let existing = self
+            .value
+            .borrow()
+            .as_ref()
+            .map(|value| value.vtype.clone());
+        self.write_raw(&RegistryHelper::path_reg_value(stored, existing))
+    }
+    fn read_raw(&self) -> io::Result<RegValue> {
+        self.value
+            .borrow()
+            .as_ref()
+            .map(|value| RegValue {
+                bytes: value.bytes.clone(),
+                vtype: value.vtype.clone(),
+            })
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+    fn write_raw(&self, value: &RegValue) -> io::Result<()> {
+        *self.writes.borrow_mut() += 1;
+        if let Some(kind) = self.write_error {
+            return Err(io::Error::from(kind));
+        }
+        *self.value.borrow_mut() = Some(RegValue {
+            bytes: value.bytes.clone(),
+            vtype: value.vtype.clone(),
+        });
+        Ok(())
+    }
+    fn delete_path(&self) -> io::Result<()> {
+        *self.value.borrow_mut() = None;
+        Ok(())
+    }
+}
 
 mod parse_tests {
     use super::*;
@@ -250,3 +333,288 @@ mod file_lock_tests {
         let _ = fs::remove_file(&lock_path);
     }
 }
+
+mod separator_tests {
+    use spath_cli::constants::PATH_SEPARATOR;
+    use spath_cli::registry::RegistryHelper;
+
+    #[test]
+    fn test_default_separator_is_semicolon() {
+        assert_eq!(PATH_SEPARATOR, ';');
+        let paths = RegistryHelper::parse_path_string("C:\\Windows;;C:\\Tools;");
+        assert_eq!(paths, vec!["C:\\Windows", "C:\\Tools"]);
+        assert_eq!(RegistryHelper::join_paths(&paths), "C:\\Windows;C:\\Tools");
+    }
+
+    #[test]
+    fn test_parse_and_join_with_injected_separator() {
+        let paths = RegistryHelper::parse_path_string_with("/usr/bin:/opt/tools::/bin", ':');
+        assert_eq!(paths, vec!["/usr/bin", "/opt/tools", "/bin"]);
+        assert_eq!(
+            RegistryHelper::join_paths_with(&paths, ':'),
+            "/usr/bin:/opt/tools:/bin"
+        );
+        assert_eq!(
+            RegistryHelper::parse_path_string_with("tools;bin", ':'),
+            vec!["tools;bin"]
+        );
+    }
+}
+
+mod value_type_tests {
+    use spath_cli::registry::RegistryHelper;
+    use winreg::enums::*;
+    use winreg::types::ToRegValue;
+    use winreg::RegKey;
+
+    const EXPANDABLE_PATH: &str = "%SystemRoot%\\System32;C:\\Tools";
+
+    #[test]
+    fn test_expandable_path_is_written_as_expand_sz() {
+        let value = RegistryHelper::path_reg_value(EXPANDABLE_PATH, Some(REG_SZ));
+        assert_eq!(value.vtype, REG_EXPAND_SZ);
+        assert_eq!(value.bytes, EXPANDABLE_PATH.to_reg_value().bytes);
+    }
+
+    #[test]
+    fn test_existing_expand_sz_type_is_preserved() {
+        let value = RegistryHelper::path_reg_value("C:\\Tools", Some(REG_EXPAND_SZ));
+        assert_eq!(value.vtype, REG_EXPAND_SZ);
+        let value = RegistryHelper::path_reg_value("C:\\Tools", Some(REG_SZ));
+        assert_eq!(value.vtype, REG_SZ);
+    }
+
+    #[test]
+    fn test_expandable_path_round_trips_through_registry() {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let key_name = format!("Software\\spath_cli_test_{}", std::process::id());
+        let (key, _) = hkcu.create_subkey(&key_name).unwrap();
+        let mut initial = "C:\\Old".to_reg_value();
+        initial.vtype = REG_EXPAND_SZ;
+        key.set_raw_value("Path", &initial).unwrap();
+        RegistryHelper::write_path_value(&key, EXPANDABLE_PATH).unwrap();
+        let raw = key.get_raw_value("Path").unwrap();
+        let read: String = key.get_value("Path").unwrap();
+        hkcu.delete_subkey_all(&key_name).unwrap();
+        assert_eq!(raw.vtype, REG_EXPAND_SZ);
+        assert_eq!(read, EXPANDABLE_PATH);
+    }
+}
+
+mod quote_aware_parse_tests {
+    use spath_cli::fixer::validate_path_value;
+    use spath_cli::registry::RegistryHelper;
+
+    #[test]
+    fn test_quoted_entry_keeps_semicolon() {
+        let parsed = RegistryHelper::parse_path_string("\"C:\\A;B\";C:\\Windows");
+        assert_eq!(parsed, vec!["\"C:\\A;B\"", "C:\\Windows"]);
+    }
+
+    #[test]
+    fn test_quoted_entry_round_trips_through_join() {
+        let original = "C:\\Tools;\"C:\\Weird;Dir\";;C:\\Windows";
+        let parsed = RegistryHelper::parse_path_string(original);
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(
+            RegistryHelper::join_paths(&parsed),
+            "C:\\Tools;\"C:\\Weird;Dir\";C:\\Windows"
+        );
+    }
+
+    #[test]
+    fn test_split_entries_keeps_empty_entries() {
+        assert_eq!(
+            RegistryHelper::split_entries("C:\\A;;\"C:\\B;C\""),
+            vec!["C:\\A", "", "\"C:\\B;C\""]
+        );
+    }
+
+    #[test]
+    fn test_validation_accepts_quoted_semicolon() {
+        assert_eq!(
+            validate_path_value("\"C:\\A;B\";C:\\Windows").unwrap(),
+            "\"C:\\A;B\";C:\\Windows"
+        );
+    }
+}
+
+mod read_fallback_tests {
+    use spath_cli::registry::{PathReadError, RegistryHelper};
+    use std::io;
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    #[test]
+    fn test_io_errors_are_classified() {
+        let not_found = PathReadError::from(io::Error::from(io::ErrorKind::NotFound));
+        assert!(matches!(not_found, PathReadError::NotFound));
+        let denied = PathReadError::from(io::Error::from(io::ErrorKind::PermissionDenied));
+        assert!(matches!(denied, PathReadError::PermissionDenied));
+        let other = PathReadError::from(io::Error::from(io::ErrorKind::InvalidData));
+        assert!(matches!(other, PathReadError::IoError(_)));
+    }
+
+    #[test]
+    fn test_missing_value_reads_as_empty_path() {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let key_name = format!("Software\\spath_cli_fallback_test_{}", std::process::id());
+        hkcu.create_subkey(&key_name).unwrap();
+        let strict = RegistryHelper::read_path_value(&hkcu, &key_name);
+        let fallback = RegistryHelper::read_path_with_fallback(&hkcu, &key_name);
+        hkcu.delete_subkey_all(&key_name).unwrap();
+        assert!(matches!(strict, Err(PathReadError::NotFound)));
+        assert_eq!(fallback.unwrap(), "");
+    }
+
+    #[test]
+    fn test_missing_key_reads_as_empty_path() {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let fallback =
+            RegistryHelper::read_path_with_fallback(&hkcu, "Software\\spath_cli_no_such_key");
+        assert_eq!(fallback.unwrap(), "");
+    }
+}
+
+#[cfg(test)]
+mod watch_tests {
+    use spath_cli::models::PathLocation;
+    use spath_cli::registry::RegistryHelper;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{mpsc, Arc};
+    use std::time::Duration;
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    #[test]
+    fn test_value_change_fires_callback() {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let key_name = format!("Software\\spath_cli_watch_test_{}", std::process::id());
+        let (key, _) = hkcu.create_subkey(&key_name).unwrap();
+        let (notify, changes) = mpsc::channel();
+        let handle = RegistryHelper::watch_key_change(&hkcu, &key_name, move || {
+            let _ = notify.send(());
+        })
+        .unwrap();
+        // The watcher arms its notification on its own thread, so keep
+        // changing the value until one change lands after it is armed.
+        let fired = (0..50).any(|i| {
+            key.set_value("Path", &format!("C:\\Tools{}", i)).unwrap();
+            changes.recv_timeout(Duration::from_millis(100)).is_ok()
+        });
+        drop(handle);
+        hkcu.delete_subkey_all(&key_name).unwrap();
+        assert!(fired);
+    }
+
+    #[test]
+    fn test_dropping_watch_handle_stops_watcher() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let handle = RegistryHelper::watch_path_change(PathLocation::User, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        drop(handle);
+        let after_drop = calls.load(Ordering::SeqCst);
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert_eq!(calls.load(Ordering::SeqCst), after_drop);
+    }
+}
+
+#[cfg(test)]
+mod write_verify_tests {
+    use super::FakeStore;
+    use spath_cli::registry::{PathValueStore, RegistryHelper};
+    use std::io;
+    use winreg::enums::*;
+    use winreg::types::FromRegValue;
+
+    #[test]
+    fn test_matching_write_succeeds() {
+        let store = FakeStore::new(Some("C:\\Old"), None);
+        RegistryHelper::write_verified(&store, "C:\\New;C:\\Tools").unwrap();
+        assert_eq!(store.read_path().unwrap(), "C:\\New;C:\\Tools");
+    }
+
+    #[test]
+    fn test_mismatch_restores_previous_value() {
+        let store = FakeStore::new(Some("C:\\Old;C:\\Windows"), Some(4));
+        let err = RegistryHelper::write_verified(&store, "C:\\New;C:\\Tools").unwrap_err();
+        assert!(err.to_string().contains("previous PATH was restored"));
+        assert_eq!(store.read_path().unwrap(), "C:\\Old;C:\\Windows");
+        assert_eq!(*store.writes.borrow(), 2);
+    }
+
+    #[test]
+    fn test_mismatch_restores_raw_value_and_type() {
+        let store = FakeStore::with_type(Some("C:\\Old"), REG_EXPAND_SZ, Some(4));
+        RegistryHelper::write_verified(&store, "C:\\New;C:\\Tools").unwrap_err();
+        let restored = store.read_raw().unwrap();
+        assert_eq!(restored.vtype, REG_EXPAND_SZ);
+        assert_eq!(String::from_reg_value(&restored).unwrap(), "C:\\Old");
+    }
+
+    #[test]
+    fn test_mismatch_without_previous_value_deletes_it() {
+        let store = FakeStore::new(None, Some(2));
+        let err = RegistryHelper::write_verified(&store, "C:\\New").unwrap_err();
+        assert!(err.to_string().contains("previous PATH was restored"));
+        assert_eq!(
+            store.read_path().unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
+
+#[cfg(test)]
+mod registry_error_tests {
+    use super::FakeStore;
+    use spath_cli::constants::MAX_PATH_LENGTH;
+    use spath_cli::registry::{RegistryError, RegistryHelper};
+    use std::error::Error;
+    use std::io;
+
+    #[test]
+    fn test_too_long_path_is_typed() {
+        let path = "X".repeat(MAX_PATH_LENGTH + 5);
+        match RegistryHelper::validate_path_length(&path) {
+            Err(RegistryError::PathTooLong { len, max }) => {
+                assert_eq!(len, MAX_PATH_LENGTH + 5);
+                assert_eq!(max, MAX_PATH_LENGTH);
+            }
+            other => panic!("expected PathTooLong, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_failed_write_is_write_failed() {
+        let store = FakeStore::failing(Some("C:\\Old"), io::ErrorKind::PermissionDenied);
+        let err = RegistryHelper::write_verified(&store, "C:\\New").unwrap_err();
+        assert!(matches!(err, RegistryError::WriteFailed(_)));
+        assert_eq!(*store.writes.borrow(), 1);
+        let cause = err.source().unwrap().to_string();
+        assert!(format!("{:#}", anyhow::Error::from(err)).ends_with(&cause));
+    }
+
+    #[test]
+    fn test_mismatch_reports_restore() {
+        let store = FakeStore::new(Some("C:\\Old"), Some(2));
+        let err = RegistryHelper::write_verified(&store, "C:\\New").unwrap_err();
+        assert!(matches!(
+            err,
+            RegistryError::VerifyFailed { restored: true, .. }
+        ));
+        assert_eq!(*store.writes.borrow(), 2);
+    }
+
+    #[test]
+    fn test_converts_into_anyhow() {
+        let result: anyhow::Result<()> =
+            RegistryHelper::validate_path_length(&"X".repeat(MAX_PATH_LENGTH + 1))
+                .map_err(Into::into);
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("exceeds maximum length"));
+        assert!(err.downcast_ref::<RegistryError>().is_some());
+    }
+}
@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod report_tests {
+    use spath_cli::models::{IssueLevel, PathLocation};
+    use spath_cli::report::{build_report, from_scans};
+    use spath_cli::scanner::{CombinedScanResults, PathScanner, DUPLICATE_PATH_MESSAGE};
+
+    const SYSTEM: &str = "C:\\Windows;C:\\Program Files\\Common Files;C:\\spath_missing_dir";
+    const USER: &str = "C:\\spath_missing_dir;c:\\windows;C:\\Windows";
+
+    #[test]
+    fn test_report_counts_match_individual_scans() {
+        let report = build_report(SYSTEM, USER).unwrap();
+        let system = PathScanner::from_string(SYSTEM).scan().unwrap();
+        let user = PathScanner::from_string(USER).scan().unwrap();
+        assert_eq!(report.entries.len(), system.paths.len() + user.paths.len());
+        assert_eq!(
+            report.issues().count(),
+            system.issues.len() + user.issues.len()
+        );
+        assert_eq!(report.system.valid_paths, system.audit.valid_paths);
+        assert_eq!(report.user.non_existent, user.audit.non_existent);
+        assert_eq!(
+            report.summary.total_paths,
+            system.audit.total_paths + user.audit.total_paths
+        );
+        assert_eq!(
+            report.health_score(),
+            system.audit.merged(&user.audit).health_score()
+        );
+    }
+
+    #[test]
+    fn test_report_entries_carry_location_and_issues() {
+        let report = build_report(SYSTEM, USER).unwrap();
+        assert!(report.entries[..3]
+            .iter()
+            .all(|e| e.entry.location == PathLocation::System));
+        assert!(report.entries[3..]
+            .iter()
+            .all(|e| e.entry.location == PathLocation::User));
+        assert!(report.entries[1]
+            .issues
+            .iter()
+            .any(|i| i.level == IssueLevel::Critical));
+        assert!(report.entries[0].issues.is_empty());
+        assert!(report.entries[0].entry.is_duplicate);
+    }
+
+    #[test]
+    fn test_report_attaches_duplicate_issues_to_the_repeated_entry() {
+        let report = build_report(
+            "C:\\Windows",
+            "C:\\spath_missing_dir;C:\\Windows\\System32;C:\\spath_missing_dir",
+        )
+        .unwrap();
+        let duplicate = |index: usize| {
+            report.entries[index]
+                .issues
+                .iter()
+                .any(|i| i.message == DUPLICATE_PATH_MESSAGE)
+        };
+        assert!(!duplicate(1));
+        assert!(duplicate(3));
+        assert!(!report.entries[3].issues.is_empty());
+        assert_eq!(
+            report.entries[1].issues.len() + 1,
+            report.entries[3].issues.len()
+        );
+    }
+
+    #[test]
+    fn test_report_lists_cross_scope_duplicates() {
+        let report = build_report(SYSTEM, USER).unwrap();
+        assert_eq!(
+            report.cross_scope_duplicates,
+            vec![
+                "C:\\Windows".to_string(),
+                "C:\\spath_missing_dir".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report_takes_existence_from_scan_issues() {
+        let report = build_report(SYSTEM, USER).unwrap();
+        let windows = &report.entries[0].entry;
+        assert!(windows.existence_checked && windows.exists && windows.is_directory);
+        let missing = &report.entries[2].entry;
+        assert!(missing.existence_checked && !missing.exists);
+    }
+
+    #[test]
+    fn test_offline_report_leaves_existence_unchecked() {
+        let scan = |path: &str, location| {
+            PathScanner::from_string(path)
+                .with_location(location)
+                .with_offline(true)
+                .scan()
+                .unwrap()
+        };
+        let combined = CombinedScanResults::new(
+            scan(USER, PathLocation::User),
+            scan(SYSTEM, PathLocation::System),
+        );
+        let report = from_scans(&combined);
+        assert!(report.entries.iter().all(|e| !e.entry.existence_checked));
+    }
+}
+
+#[cfg(test)]
+mod markdown_tests {
+    use spath_cli::formatter::MarkdownFormatter;
+    use spath_cli::models::{IssueLevel, PathLocation};
+    use spath_cli::report::{build_report, from_scans};
+    use spath_cli::scanner::{CombinedScanResults, PathScanner};
+
+    #[test]
+    fn test_markdown_has_table_header_and_row_per_issue() {
+        let report = build_report(
+            "C:\\Windows;C:\\Program Files\\Common Files",
+            "C:\\spath_missing_dir;c:\\windows",
+        )
+        .unwrap();
+        let markdown = MarkdownFormatter::render(&report);
+        assert!(markdown.contains("| # | Scope | Path | Level | Message |"));
+        assert!(markdown.contains("## Summary"));
+        let issue_rows = markdown
+            .lines()
+            .skip_while(|l| !l.starts_with("| # |"))
+            .skip(2)
+            .filter(|l| l.starts_with('|'))
+            .count();
+        assert_eq!(issue_rows, report.issues().count());
+        assert!(issue_rows > 0);
+    }
+
+    #[test]
+    fn test_markdown_from_filtered_scans_keeps_level_and_redaction() {
+        let scan = |path: &str, location| {
+            PathScanner::from_string(path)
+                .with_location(location)
+                .scan()
+                .unwrap()
+                .with_min_level(IssueLevel::Warning)
+                .redacted(false)
+        };
+        let combined = CombinedScanResults::new(
+            scan("C:\\Users\\alice\\spath_missing", PathLocation::User),
+            scan(
+                "C:\\Windows;C:\\Program Files\\Common Files",
+                PathLocation::System,
+            ),
+        );
+        let markdown = MarkdownFormatter::render(&from_scans(&combined));
+        assert!(!markdown.contains("alice"));
+        assert!(markdown.contains("<USER>"));
+        assert!(!markdown.contains("| Info |"));
+        assert!(markdown.contains("| Critical |"));
+    }
+}
+
+#[cfg(test)]
+mod check_exit_code_tests {
+    use spath_cli::constants::{EXIT_WORST_CRITICAL, EXIT_WORST_WARNING};
+    use spath_cli::models::IssueLevel;
+    use spath_cli::report::{build_report, exit_code};
+    use spath_cli::scanner::PathScanner;
+
+    #[test]
+    fn test_clean_report_exits_zero() {
+        let report = build_report("C:\\Windows", "C:\\Windows\\System32").unwrap();
+        assert_eq!(report.worst_level(), None);
+        assert_eq!(report.check_exit_code(), 0);
+    }
+
+    #[test]
+    fn test_warning_only_report_exits_with_warning_code() {
+        let report = build_report("C:\\Windows", "C:\\spath_missing_dir").unwrap();
+        assert_eq!(report.worst_level(), Some(IssueLevel::Warning));
+        assert_eq!(report.check_exit_code(), EXIT_WORST_WARNING);
+    }
+
+    #[test]
+    fn test_critical_report_exits_with_critical_code() {
+        let report = build_report(
+            "C:\\Windows;C:\\Program Files\\Common Files",
+            "C:\\spath_missing_dir",
+        )
+        .unwrap();
+        assert_eq!(report.worst_level(), Some(IssueLevel::Critical));
+        assert_eq!(report.check_exit_code(), EXIT_WORST_CRITICAL);
+    }
+
+    #[test]
+    fn test_scan_exit_code_follows_worst_level() {
+        let warnings = PathScanner::from_string("C:\\spath_missing_dir")
+            .scan()
+            .unwrap();
+        assert_eq!(warnings.worst_level(), Some(IssueLevel::Warning));
+        assert_eq!(exit_code(warnings.worst_level()), EXIT_WORST_WARNING);
+        let critical = PathScanner::from_string("C:\\Program Files\\Common Files")
+            .scan()
+            .unwrap();
+        assert_eq!(exit_code(critical.worst_level()), EXIT_WORST_CRITICAL);
+        assert_eq!(exit_code(None), 0);
+    }
+}
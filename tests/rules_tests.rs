@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod lint_rule_tests {
+    use spath_cli::models::{IssueLevel, PathEntry, PathLocation};
+    use spath_cli::rules::Linter;
+
+    fn entries(paths: &[&str]) -> Vec<PathEntry> {
+        let paths: Vec<String> = paths.iter().map(|p| p.to_string()).collect();
+        paths
+            .iter()
+            .enumerate()
+            .map(|(index, path)| PathEntry::new(path.clone(), index, PathLocation::User, &paths))
+            .collect()
+    }
+
+    fn rules_for(findings: &[spath_cli::rules::LintFinding], path: &str) -> Vec<&'static str> {
+        findings
+            .iter()
+            .filter(|f| f.issue.path == path)
+            .map(|f| f.rule)
+            .collect()
+    }
+
+    #[test]
+    fn test_builtin_rules_report_their_names() {
+        let long = format!("C:\\{}", "a".repeat(300));
+        let findings = Linter::new().lint(&entries(&[
+            "C:\\Windows",
+            "C:\\Program Files\\spath_missing",
+            "tools\\bin",
+            &long,
+            "C:\\Windows",
+        ]));
+        assert_eq!(
+            rules_for(&findings, "C:\\Windows"),
+            vec!["duplicate", "duplicate"]
+        );
+        assert_eq!(
+            rules_for(&findings, "C:\\Program Files\\spath_missing"),
+            vec!["unquoted-spaces", "non-existent"]
+        );
+        assert_eq!(
+            rules_for(&findings, "tools\\bin"),
+            vec!["non-existent", "relative"]
+        );
+        assert_eq!(
+            rules_for(&findings, &long),
+            vec!["non-existent", "too-long"]
+        );
+    }
+
+    #[test]
+    fn test_writable_by_others_is_critical() {
+        let mut entry = entries(&["C:\\Windows"]).remove(0);
+        entry.writable_by_others = true;
+        let findings = Linter::new().lint(&[entry]);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, "writable-by-others");
+        assert_eq!(findings[0].issue.level, IssueLevel::Critical);
+    }
+
+    #[test]
+    fn test_disabled_rules_are_skipped() {
+        let linter = Linter::new()
+            .with_disabled(&["duplicate".to_string(), "non-existent".to_string()])
+            .unwrap();
+        assert!(!linter.rule_names().contains(&"duplicate"));
+        let findings = linter.lint(&entries(&["C:\\spath_missing", "C:\\spath_missing"]));
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_rule_name_is_an_error() {
+        let err = Linter::new()
+            .with_disabled(&["duplicates".to_string()])
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("unknown rule 'duplicates'"));
+    }
+
+    #[test]
+    fn test_offline_entries_are_not_reported_missing() {
+        let paths = vec!["C:\\spath_missing_dir".to_string()];
+        let counts = PathEntry::path_counts(&paths);
+        let entry = PathEntry::offline(paths[0].clone(), 0, PathLocation::User, &counts);
+        assert!(!entry.existence_checked);
+        assert!(!entry.is_missing());
+        assert!(Linter::new().lint(&[entry]).is_empty());
+    }
+}
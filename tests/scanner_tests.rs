@@ -220,3 +220,1154 @@ mod scan_results_tests {
         assert_eq!(score, 0);
     }
 }
+
+#[cfg(test)]
+mod scan_input_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::PathScanner;
+
+    #[test]
+    fn test_scan_input_one_entry_per_line() {
+        let input = "C:\\Windows\r\n\r\nC:\\Windows\n  C:\\spath_missing_dir  \n";
+        let results = PathScanner::from_input(input, false).scan().unwrap();
+        assert_eq!(
+            results.paths,
+            vec!["C:\\Windows", "C:\\Windows", "C:\\spath_missing_dir"]
+        );
+        assert!(results
+            .issues
+            .iter()
+            .any(|i| i.path == "C:\\Windows" && i.message == "Duplicate path entry"));
+        assert!(results
+            .issues
+            .iter()
+            .any(|i| i.path == "C:\\spath_missing_dir"
+                && matches!(i.level, IssueLevel::Warning)
+                && i.message == "Path does not exist"));
+    }
+
+    #[test]
+    fn test_scan_input_raw_value() {
+        let input = "C:\\Windows;C:\\spath_missing_dir;\n";
+        let results = PathScanner::from_input(input, true).scan().unwrap();
+        assert_eq!(results.paths, vec!["C:\\Windows", "C:\\spath_missing_dir"]);
+        assert_eq!(results.audit.total_paths, 2);
+        assert_eq!(results.audit.non_existent, 1);
+    }
+}
+
+#[cfg(test)]
+mod scan_custom_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::PathScanner;
+    use std::fs;
+
+    fn levels(path_string: &str) -> Vec<IssueLevel> {
+        PathScanner::scan_custom(path_string)
+            .unwrap()
+            .issues
+            .iter()
+            .map(|issue| issue.level)
+            .collect()
+    }
+
+    #[test]
+    fn test_current_directory_is_critical() {
+        assert_eq!(levels("."), vec![IssueLevel::Critical]);
+    }
+
+    #[test]
+    fn test_missing_directory_is_warning() {
+        assert_eq!(levels("C:\\spath_missing_dir"), vec![IssueLevel::Warning]);
+    }
+
+    #[test]
+    fn test_quoted_directory_with_spaces_is_info() {
+        let dir = tempfile::tempdir().unwrap();
+        let spaced = dir.path().join("My Tools");
+        fs::create_dir(&spaced).unwrap();
+        assert_eq!(
+            levels(&format!("\"{}\"", spaced.display())),
+            vec![IssueLevel::Info]
+        );
+    }
+
+    #[test]
+    fn test_clean_path_has_no_issues() {
+        let results = PathScanner::scan_custom("C:\\Windows").unwrap();
+        assert!(results.issues.is_empty());
+        assert_eq!(results.paths, vec!["C:\\Windows"]);
+        assert!(results.location.is_none());
+    }
+}
+
+#[cfg(test)]
+mod current_directory_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::PathScanner;
+
+    fn critical_paths(path_var: &str) -> Vec<String> {
+        PathScanner::from_string(path_var)
+            .scan()
+            .unwrap()
+            .issues
+            .into_iter()
+            .filter(|i| {
+                matches!(i.level, IssueLevel::Critical)
+                    && i.message == "Current directory on PATH is a security risk"
+            })
+            .map(|i| i.path)
+            .collect()
+    }
+
+    #[test]
+    fn test_dot_entry_is_critical() {
+        assert_eq!(critical_paths("C:\\Windows;."), vec!["."]);
+    }
+
+    #[test]
+    fn test_parent_and_blank_entries_are_critical() {
+        assert_eq!(critical_paths("..;  ;\"\""), vec!["..", "  ", "\"\""]);
+    }
+
+    #[test]
+    fn test_dot_entry_is_not_reported_as_relative() {
+        let results = PathScanner::from_string(".").scan().unwrap();
+        assert_eq!(results.issues.len(), 1);
+    }
+
+    #[test]
+    fn test_relative_subdirectory_is_not_current_directory() {
+        assert!(critical_paths(".\\bin").is_empty());
+    }
+}
+
+#[cfg(test)]
+mod duplicate_group_tests {
+    use spath_cli::scanner::PathScanner;
+
+    #[test]
+    fn test_triple_duplicate_is_one_group() {
+        let results = PathScanner::from_string("C:\\Windows;C:\\Tools;c:\\windows;\"C:\\Windows\"")
+            .scan()
+            .unwrap();
+        let groups = results.duplicate_groups();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].normalized, "c:\\windows");
+        assert_eq!(groups[0].indices, vec![0, 2, 3]);
+        assert_eq!(groups[0].kept_index, 0);
+    }
+
+    #[test]
+    fn test_no_groups_without_duplicates() {
+        let results = PathScanner::from_string("C:\\Windows;C:\\Tools")
+            .scan()
+            .unwrap();
+        assert!(results.duplicate_groups().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod scope_health_tests {
+    use spath_cli::models::PathLocation;
+    use spath_cli::scanner::PathScanner;
+
+    #[test]
+    fn test_distinct_scores_per_scope() {
+        let user = PathScanner::from_string("C:\\Windows;C:\\spath_missing_dir")
+            .with_location(PathLocation::User)
+            .scan()
+            .unwrap();
+        let system = PathScanner::from_string("C:\\Windows;C:\\Windows\\System32")
+            .with_location(PathLocation::System)
+            .scan()
+            .unwrap();
+        assert_eq!(user.location, Some(PathLocation::User));
+        assert_eq!(system.location, Some(PathLocation::System));
+        assert_eq!(user.audit.health_score(), 50);
+        assert_eq!(system.audit.health_score(), 100);
+        let combined = user.audit.merged(&system.audit);
+        assert_eq!(combined.total_paths, 4);
+        assert_eq!(combined.health_score(), 75);
+    }
+
+    #[test]
+    fn test_empty_path_scores_zero() {
+        let results = PathScanner::from_string("").scan().unwrap();
+        assert_eq!(results.location, None);
+        assert_eq!(results.audit.health_score(), 0);
+    }
+}
+
+#[cfg(test)]
+mod timeout_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::{unreachable_message, PathScanner, UNREACHABLE_PATH_MESSAGE};
+    use spath_cli::utils::{run_with_timeout, unc_share};
+    use std::path::Path;
+    use std::thread;
+    use std::time::Duration;
+
+    fn slow_exists(_: &Path) -> bool {
+        thread::sleep(Duration::from_millis(500));
+        true
+    }
+
+    fn fast_missing(_: &Path) -> bool {
+        false
+    }
+
+    #[test]
+    fn test_run_with_timeout() {
+        assert_eq!(run_with_timeout(|| 7, Duration::from_secs(5)), Some(7));
+        let slow = run_with_timeout(
+            || thread::sleep(Duration::from_millis(500)),
+            Duration::from_millis(10),
+        );
+        assert!(slow.is_none());
+    }
+
+    #[test]
+    fn test_slow_path_is_classified_unreachable() {
+        let results = PathScanner::from_string("\\\\server\\share\\tools")
+            .with_existence_check(slow_exists)
+            .with_timeout(Duration::from_millis(20))
+            .scan()
+            .unwrap();
+        assert_eq!(results.issues.len(), 1);
+        assert_eq!(results.issues[0].level, IssueLevel::Warning);
+        assert_eq!(
+            results.issues[0].message,
+            format!(
+                "{} - network share \\\\server\\share",
+                UNREACHABLE_PATH_MESSAGE
+            )
+        );
+        assert_eq!(results.audit.unreachable, 1);
+        assert_eq!(results.audit.non_existent, 0);
+    }
+
+    #[test]
+    fn test_fast_check_within_timeout_is_not_unreachable() {
+        let results = PathScanner::from_string("C:\\spath_missing_dir")
+            .with_existence_check(fast_missing)
+            .with_timeout(Duration::from_secs(5))
+            .scan()
+            .unwrap();
+        assert_eq!(results.audit.unreachable, 0);
+        assert_eq!(results.audit.non_existent, 1);
+        assert!(results
+            .issues
+            .iter()
+            .all(|i| i.message != UNREACHABLE_PATH_MESSAGE));
+    }
+
+    #[test]
+    fn test_unc_share() {
+        assert_eq!(
+            unc_share("\\\\server\\share\\tools").as_deref(),
+            Some("\\\\server\\share")
+        );
+        assert_eq!(
+            unc_share("\"\\\\nas\\bin\"").as_deref(),
+            Some("\\\\nas\\bin")
+        );
+        assert_eq!(unc_share("\\\\server"), None);
+        assert_eq!(unc_share("\\\\?\\C:\\Tools"), None);
+        assert_eq!(unc_share("C:\\Tools"), None);
+    }
+
+    #[test]
+    fn test_unreachable_message_for_local_path() {
+        assert_eq!(unreachable_message("Z:\\Tools"), UNREACHABLE_PATH_MESSAGE);
+    }
+
+    #[test]
+    fn test_timed_out_entries_keep_path_order() {
+        let current = "\\\\b\\share;\\\\a\\share;\\\\c\\share";
+        let results = PathScanner::from_string(current)
+            .with_existence_check(slow_exists)
+            .with_timeout(Duration::from_millis(20))
+            .scan()
+            .unwrap();
+        let paths: Vec<&str> = results.issues.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(paths, vec!["\\\\b\\share", "\\\\a\\share", "\\\\c\\share"]);
+        assert_eq!(results.audit.unreachable, 3);
+    }
+}
+
+#[cfg(test)]
+mod app_alias_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::{PathScanner, APP_ALIAS_DIR_MESSAGE};
+    use spath_cli::utils::is_app_execution_alias_dir;
+
+    const ALIAS_DIR: &str = "C:\\Users\\spath_test\\AppData\\Local\\Microsoft\\WindowsApps";
+
+    #[test]
+    fn test_detects_alias_dir() {
+        assert!(is_app_execution_alias_dir(ALIAS_DIR));
+        assert!(is_app_execution_alias_dir(
+            "%USERPROFILE%\\AppData\\Local\\Microsoft\\WindowsApps\\"
+        ));
+        assert!(!is_app_execution_alias_dir("C:\\Tools\\WindowsApps"));
+    }
+
+    #[test]
+    fn test_alias_dir_reported_as_info_not_missing() {
+        let results = PathScanner::from_string(ALIAS_DIR).scan().unwrap();
+        assert_eq!(results.issues.len(), 1);
+        assert_eq!(results.issues[0].level, IssueLevel::Info);
+        assert_eq!(results.issues[0].message, APP_ALIAS_DIR_MESSAGE);
+        assert_eq!(results.audit.non_existent, 0);
+    }
+}
+
+#[cfg(test)]
+mod excluded_drive_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::{PathScanner, SKIPPED_PATH_MESSAGE};
+    use spath_cli::utils::{drive_letter, is_on_excluded_drive};
+
+    #[test]
+    fn test_drive_letter_detection() {
+        assert_eq!(drive_letter("e:\\tools"), Some('E'));
+        assert_eq!(drive_letter("\"Z:\\My Tools\""), Some('Z'));
+        assert_eq!(drive_letter("\\\\server\\share"), None);
+        assert!(is_on_excluded_drive("z:\\bin", &['Z']));
+        assert!(!is_on_excluded_drive("C:\\bin", &['Z']));
+    }
+
+    #[test]
+    fn test_entries_on_excluded_drive_are_skipped() {
+        let results = PathScanner::from_string("Z:\\spath_missing_dir;C:\\spath_missing_dir")
+            .with_excluded_drives(['Z'])
+            .scan()
+            .unwrap();
+        assert_eq!(results.audit.skipped, 1);
+        assert_eq!(results.audit.non_existent, 1);
+        let skipped: Vec<_> = results
+            .issues
+            .iter()
+            .filter(|i| i.path == "Z:\\spath_missing_dir")
+            .collect();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].level, IssueLevel::Info);
+        assert_eq!(skipped[0].message, SKIPPED_PATH_MESSAGE);
+    }
+}
+
+#[cfg(test)]
+mod validity_policy_tests {
+    use spath_cli::models::ValidityPolicy;
+    use spath_cli::scanner::PathScanner;
+
+    const INPUT: &str = "C:\\Windows;\"C:\\spath missing dir\";C:\\spath_missing_dir;tools";
+
+    #[test]
+    fn test_default_policy() {
+        let results = PathScanner::from_string(INPUT).scan().unwrap();
+        assert_eq!(results.audit.valid_paths, 1);
+    }
+
+    #[test]
+    fn test_policy_without_existence_requirement() {
+        let policy = ValidityPolicy {
+            require_exists: false,
+            ..Default::default()
+        };
+        let results = PathScanner::from_string(INPUT)
+            .with_validity_policy(policy)
+            .scan()
+            .unwrap();
+        assert_eq!(results.audit.valid_paths, 3);
+        assert_eq!(results.audit.health_score(), 75);
+    }
+
+    #[test]
+    fn test_policy_predicate() {
+        let strict = ValidityPolicy::default();
+        assert!(strict.is_valid(true, true, false, false));
+        assert!(!strict.is_valid(true, true, true, false));
+        let lenient = ValidityPolicy {
+            require_quotes_for_spaces: false,
+            ..Default::default()
+        };
+        assert!(lenient.is_valid(true, true, true, false));
+    }
+}
+
+#[cfg(test)]
+mod json_output_tests {
+    use spath_cli::scanner::PathScanner;
+
+    #[test]
+    fn test_scan_results_serialize_with_lowercase_levels() {
+        let results =
+            PathScanner::from_string("C:\\Program Files\\Common Files;C:\\spath_missing_dir")
+                .scan()
+                .unwrap();
+        let json = serde_json::to_value(&results).unwrap();
+        assert_eq!(json["paths"].as_array().unwrap().len(), 2);
+        let levels: Vec<&str> = json["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["level"].as_str().unwrap())
+            .collect();
+        assert!(levels.contains(&"critical"));
+        assert!(levels.contains(&"warning"));
+        assert_eq!(json["issues"][0]["path"], "C:\\Program Files\\Common Files");
+        assert_eq!(json["audit"]["total_paths"], 2);
+    }
+
+    #[test]
+    fn test_clean_scan_serializes_empty_issue_array() {
+        let results = PathScanner::from_string("C:\\Windows").scan().unwrap();
+        let json = serde_json::to_value(&results).unwrap();
+        assert_eq!(json["issues"], serde_json::json!([]));
+        assert!(json["location"].is_null());
+    }
+}
+
+#[cfg(test)]
+mod json_formatter_tests {
+    use spath_cli::formatter::JsonFormatter;
+    use spath_cli::models::PathLocation;
+    use spath_cli::scanner::PathScanner;
+
+    #[test]
+    fn test_single_scope_renders_object_and_both_render_array() {
+        let user = PathScanner::from_string("C:\\Windows")
+            .with_location(PathLocation::User)
+            .scan()
+            .unwrap();
+        let system = PathScanner::from_string("C:\\Windows\\System32")
+            .with_location(PathLocation::System)
+            .scan()
+            .unwrap();
+        let single: serde_json::Value =
+            serde_json::from_str(&JsonFormatter::render(std::slice::from_ref(&user)).unwrap())
+                .unwrap();
+        assert!(single.is_object());
+        assert_eq!(single["location"], "user");
+        let both: serde_json::Value =
+            serde_json::from_str(&JsonFormatter::render(&[user, system]).unwrap()).unwrap();
+        assert_eq!(both.as_array().unwrap().len(), 2);
+        assert_eq!(both[1]["location"], "system");
+    }
+}
+
+#[cfg(test)]
+mod component_length_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::PathScanner;
+    use spath_cli::utils::has_overlong_component;
+
+    #[test]
+    fn test_overlong_component_detection() {
+        assert!(has_overlong_component(&format!("C:\\{}", "a".repeat(256))));
+        assert!(!has_overlong_component(&format!("C:\\{}", "a".repeat(255))));
+    }
+
+    #[test]
+    fn test_300_char_component_gets_specific_warning() {
+        let entry = format!("C:\\Tools\\{}\\bin", "x".repeat(300));
+        let results = PathScanner::from_string(entry.as_str()).scan().unwrap();
+        let component_warnings: Vec<_> = results
+            .issues
+            .iter()
+            .filter(|i| i.message.contains("255-character NTFS limit"))
+            .collect();
+        assert_eq!(component_warnings.len(), 1);
+        assert_eq!(component_warnings[0].level, IssueLevel::Warning);
+        assert_eq!(component_warnings[0].path, entry);
+    }
+}
+
+#[cfg(test)]
+mod sarif_tests {
+    use spath_cli::formatter::sarif::{rule_id, to_sarif, to_sarif_all, RULES};
+    use spath_cli::models::PathLocation;
+    use spath_cli::scanner::{
+        PathScanner, APP_ALIAS_DIR_MESSAGE, CURRENT_DIRECTORY_MESSAGE, DUPLICATE_PATH_MESSAGE,
+        EXPLOITABLE_PATH_MESSAGE, FILE_ENTRY_MESSAGE, MISSING_PATH_MESSAGE,
+        MISSING_UNQUOTED_PATH_MESSAGE, OVERLONG_COMPONENT_MESSAGE, QUOTED_PATH_MESSAGE,
+        RELATIVE_PATH_MESSAGE, RESERVED_CHARS_MESSAGE, SKIPPED_PATH_MESSAGE, UNQUOTED_PATH_MESSAGE,
+        UNREACHABLE_PATH_MESSAGE, UNRESOLVED_VARS_MESSAGE, WRITABLE_BY_OTHERS_MESSAGE,
+    };
+    use std::path::Path;
+
+    fn always_exists(_: &Path) -> bool {
+        true
+    }
+
+    #[test]
+    fn test_every_rule_prefix_matches_a_scanner_message() {
+        let messages = [
+            APP_ALIAS_DIR_MESSAGE,
+            CURRENT_DIRECTORY_MESSAGE,
+            DUPLICATE_PATH_MESSAGE,
+            EXPLOITABLE_PATH_MESSAGE,
+            FILE_ENTRY_MESSAGE,
+            MISSING_PATH_MESSAGE,
+            MISSING_UNQUOTED_PATH_MESSAGE,
+            OVERLONG_COMPONENT_MESSAGE,
+            QUOTED_PATH_MESSAGE,
+            RELATIVE_PATH_MESSAGE,
+            RESERVED_CHARS_MESSAGE,
+            SKIPPED_PATH_MESSAGE,
+            UNQUOTED_PATH_MESSAGE,
+            UNREACHABLE_PATH_MESSAGE,
+            UNRESOLVED_VARS_MESSAGE,
+            WRITABLE_BY_OTHERS_MESSAGE,
+        ];
+        for (prefix, id) in RULES {
+            assert!(
+                messages.iter().any(|message| message.starts_with(prefix)),
+                "rule {} matches no scanner message",
+                id
+            );
+        }
+        let long_component = format!("C:\\{}", "a".repeat(256));
+        let path = format!(
+            "C:\\Program Files\\My App;.;relative\\bin;\"C:\\Quoted Dir\";{};C:\\Windows;C:\\Windows",
+            long_component
+        );
+        let results = PathScanner::from_string(&path)
+            .with_existence_check(always_exists)
+            .scan()
+            .unwrap();
+        assert!(results.issues.len() >= 6);
+        for issue in &results.issues {
+            assert_ne!(rule_id(issue), "path-issue", "{}", issue.message);
+        }
+    }
+
+    #[test]
+    fn test_sarif_maps_levels_and_rule_ids() {
+        let results = PathScanner::from_string("C:\\Program Files\\My App;C:\\Windows;C:\\Windows")
+            .with_existence_check(always_exists)
+            .with_location(PathLocation::User)
+            .scan()
+            .unwrap();
+        let log = to_sarif(&results);
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "spath");
+        assert_eq!(run["tool"]["driver"]["version"], env!("CARGO_PKG_VERSION"));
+        let sarif_results = run["results"].as_array().unwrap();
+        let exploitable = sarif_results
+            .iter()
+            .find(|r| r["ruleId"] == "unquoted-spaces-exploitable")
+            .unwrap();
+        assert_eq!(exploitable["level"], "error");
+        let duplicate = sarif_results
+            .iter()
+            .find(|r| r["ruleId"] == "duplicate-entry")
+            .unwrap();
+        assert_eq!(duplicate["level"], "warning");
+        assert!(duplicate["message"]["text"]
+            .as_str()
+            .unwrap()
+            .ends_with("C:\\Windows"));
+    }
+
+    #[test]
+    fn test_sarif_escapes_backslashes() {
+        let results = PathScanner::from_string("C:\\Program Files\\My App")
+            .with_existence_check(always_exists)
+            .scan()
+            .unwrap();
+        let json = serde_json::to_string(&to_sarif(&results)).unwrap();
+        assert!(json.contains("C:\\\\Program Files\\\\My App"));
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            parsed["runs"][0]["results"][0]["locations"][0]["logicalLocations"][0]["name"],
+            "C:\\Program Files\\My App"
+        );
+    }
+
+    #[test]
+    fn test_sarif_emits_one_run_per_scope() {
+        let user = PathScanner::from_string("relative\\bin").scan().unwrap();
+        let system = PathScanner::from_string("C:\\Windows").scan().unwrap();
+        let log = to_sarif_all(&[user, system]);
+        assert_eq!(log["runs"].as_array().unwrap().len(), 2);
+        assert!(log["runs"][0]["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|r| r["ruleId"] == "relative-path"));
+    }
+}
+
+#[cfg(test)]
+mod csv_formatter_tests {
+    use spath_cli::formatter::CsvFormatter;
+    use spath_cli::scanner::PathScanner;
+    use std::path::Path;
+
+    fn never_exists(_: &Path) -> bool {
+        false
+    }
+
+    #[test]
+    fn test_csv_has_header_and_row_per_issue() {
+        let results = PathScanner::from_string("C:\\Tools,Old;C:\\Tools,Old")
+            .with_existence_check(never_exists)
+            .scan()
+            .unwrap();
+        let csv = CsvFormatter::render(std::slice::from_ref(&results));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "path,level,message,exists,has_spaces,is_quoted");
+        assert_eq!(lines.len(), results.issues.len() + 1);
+        assert!(lines[1].starts_with("\"C:\\Tools,Old\",warning,"));
+        assert!(lines[1].ends_with(",false,false,false"));
+    }
+
+    #[test]
+    fn test_csv_doubles_quotes_in_quoted_paths() {
+        let results = PathScanner::from_string("\"C:\\Program Files\\My App\"")
+            .with_existence_check(never_exists)
+            .scan()
+            .unwrap();
+        let csv = CsvFormatter::render(&[results]);
+        assert!(csv.contains("\"\"\"C:\\Program Files\\My App\"\"\",warning,"));
+        assert!(csv.contains(",false,true,true"));
+    }
+}
+
+#[cfg(test)]
+mod combined_scan_tests {
+    use spath_cli::models::PathLocation;
+    use spath_cli::scanner::{CombinedScanResults, CrossDuplicate, PathScanner};
+
+    #[test]
+    fn test_cross_duplicates_are_reported_once_per_path() {
+        let user = PathScanner::from_string("C:\\Tools;c:\\windows;C:\\Windows")
+            .with_location(PathLocation::User)
+            .scan()
+            .unwrap();
+        let system = PathScanner::from_string("C:\\Windows\\System32;C:\\Windows;C:\\Windows")
+            .with_location(PathLocation::System)
+            .scan()
+            .unwrap();
+        let combined = CombinedScanResults::new(user, system);
+        assert_eq!(
+            combined.cross_duplicates,
+            vec![CrossDuplicate {
+                system_path: "C:\\Windows".to_string(),
+                user_path: "c:\\windows".to_string(),
+                system_index: 1,
+                user_index: 1,
+            }]
+        );
+        assert_eq!(combined.user.location, Some(PathLocation::User));
+        assert_eq!(combined.system.paths.len(), 3);
+    }
+
+    #[test]
+    fn test_disjoint_scopes_have_no_cross_duplicates() {
+        let user = PathScanner::from_string("C:\\Tools").scan().unwrap();
+        let system = PathScanner::from_string("C:\\Windows").scan().unwrap();
+        assert!(CombinedScanResults::new(user, system)
+            .cross_duplicates
+            .is_empty());
+    }
+
+    #[test]
+    fn test_redacted_cross_duplicate_hides_both_paths() {
+        let duplicate = CrossDuplicate {
+            system_path: "C:\\Users\\alice\\bin".to_string(),
+            user_path: "c:\\users\\alice\\bin".to_string(),
+            system_index: 0,
+            user_index: 2,
+        };
+        let redacted = duplicate.redacted(false);
+        assert!(!redacted.system_path.contains("alice"));
+        assert!(!redacted.user_path.contains("alice"));
+        assert_eq!(redacted.user_index, 2);
+    }
+}
+
+#[cfg(test)]
+mod issue_diff_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::{IssueDiff, PathScanner};
+    use std::path::Path;
+
+    fn always_exists(_: &Path) -> bool {
+        true
+    }
+
+    #[test]
+    fn test_diff_reports_added_and_resolved_issues() {
+        let before = PathScanner::from_string("C:\\Windows;C:\\Windows;C:\\Program Files\\Old App")
+            .with_existence_check(always_exists)
+            .scan()
+            .unwrap();
+        let after = PathScanner::from_string(
+            "C:\\Windows;C:\\Program Files\\Old App;C:\\Program Files\\New App",
+        )
+        .with_existence_check(always_exists)
+        .scan()
+        .unwrap();
+        let diff = IssueDiff::between(&[before], &[after]);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].path, "C:\\Program Files\\New App");
+        assert_eq!(diff.added[0].level, IssueLevel::Critical);
+        assert_eq!(diff.resolved.len(), 1);
+        assert_eq!(diff.resolved[0].level, IssueLevel::Warning);
+        assert_eq!(diff.remaining_critical, 1);
+    }
+
+    #[test]
+    fn test_identical_scans_have_empty_diff() {
+        let scan = || {
+            PathScanner::from_string("C:\\Windows;C:\\Windows")
+                .scan()
+                .unwrap()
+        };
+        assert!(IssueDiff::between(&[scan()], &[scan()]).is_empty());
+    }
+
+    #[test]
+    fn test_new_since_keeps_only_issues_missing_from_baseline() {
+        let baseline = PathScanner::from_string("C:\\Windows;C:\\Program Files\\Old App")
+            .with_existence_check(always_exists)
+            .scan()
+            .unwrap();
+        let current = PathScanner::from_string(
+            "C:\\Windows;C:\\Program Files\\Old App;C:\\Program Files\\New App",
+        )
+        .with_existence_check(always_exists)
+        .scan()
+        .unwrap();
+        let regressions = current.new_since(&baseline);
+        assert_eq!(regressions.issues.len(), 1);
+        assert_eq!(regressions.issues[0].path, "C:\\Program Files\\New App");
+        assert_eq!(regressions.paths, current.paths);
+        assert_eq!(regressions.audit.total_paths, 3);
+    }
+
+    #[test]
+    fn test_new_since_matches_repeated_issues_one_to_one() {
+        let scan = |path: &str| {
+            PathScanner::from_string(path)
+                .with_existence_check(always_exists)
+                .scan()
+                .unwrap()
+        };
+        let baseline = scan("C:\\Windows;C:\\Windows");
+        let current = scan("C:\\Windows;C:\\Windows;C:\\Windows");
+        let regressions = current.new_since(&baseline);
+        assert_eq!(
+            regressions.issues.len(),
+            current.issues.len() - baseline.issues.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod parallel_scan_tests {
+    use spath_cli::scanner::{PathScanner, DUPLICATE_PATH_MESSAGE, MISSING_PATH_MESSAGE};
+    use std::path::Path;
+
+    fn exists_if_even(path: &Path) -> bool {
+        path.to_string_lossy()
+            .trim_end_matches(|c: char| !c.is_ascii_digit())
+            .ends_with(['0', '2', '4', '6', '8'])
+    }
+
+    fn synthetic_path() -> String {
+        (0..500)
+            .map(|i| match i % 5 {
+                0 => format!("C:\\Program Files\\Tool{}", i),
+                1 => format!("C:\\Tools\\bin{}", i),
+                2 => format!("\"C:\\Quoted Dir\\bin{}\"", i),
+                3 => format!("C:\\Tools\\bin{}", i - 2),
+                _ => format!("relative\\dir{}", i),
+            })
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    #[test]
+    fn test_issues_follow_entry_order_on_large_path() {
+        let results = PathScanner::from_string(synthetic_path())
+            .with_existence_check(exists_if_even)
+            .scan()
+            .unwrap();
+        assert_eq!(results.paths.len(), 500);
+        let mut cursor = 0;
+        for issue in &results.issues {
+            let offset = results.paths[cursor..]
+                .iter()
+                .position(|p| *p == issue.path)
+                .expect("issue order must follow entry order");
+            cursor += offset;
+        }
+        let duplicates = results
+            .issues
+            .iter()
+            .filter(|i| i.message == DUPLICATE_PATH_MESSAGE)
+            .count();
+        assert_eq!(duplicates, 100);
+        let missing = results
+            .issues
+            .iter()
+            .filter(|i| i.message == MISSING_PATH_MESSAGE)
+            .count();
+        assert!(missing > 0);
+        assert_eq!(results.audit.total_paths, 500);
+        assert_eq!(results.audit.relative_paths, 100);
+        assert_eq!(results.audit.properly_quoted, 100);
+    }
+
+    #[test]
+    fn test_repeated_scans_are_identical() {
+        let scan = || {
+            PathScanner::from_string(synthetic_path())
+                .with_existence_check(exists_if_even)
+                .scan()
+                .unwrap()
+        };
+        let (first, second) = (scan(), scan());
+        assert_eq!(first.issues, second.issues);
+        assert_eq!(first.audit.non_existent, second.audit.non_existent);
+        assert_eq!(first.audit.valid_paths, second.audit.valid_paths);
+    }
+
+    #[test]
+    fn test_parallel_scan_matches_serial_order() {
+        let scan = |parallel: bool| {
+            PathScanner::from_string(synthetic_path())
+                .with_existence_check(exists_if_even)
+                .with_parallel(parallel)
+                .scan()
+                .unwrap()
+        };
+        let (serial, parallel) = (scan(false), scan(true));
+        assert_eq!(parallel.paths, serial.paths);
+        assert_eq!(parallel.issues, serial.issues);
+        assert_eq!(parallel.audit.non_existent, serial.audit.non_existent);
+    }
+}
+
+#[cfg(test)]
+mod level_filter_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::PathScanner;
+    use std::path::Path;
+
+    fn always_exists(_: &Path) -> bool {
+        true
+    }
+
+    #[test]
+    fn test_min_level_keeps_only_severe_issues() {
+        let results = PathScanner::from_string("C:\\Program Files\\App;C:\\Tools;C:\\Tools")
+            .with_existence_check(always_exists)
+            .scan()
+            .unwrap();
+        let critical = results.with_min_level(IssueLevel::Critical);
+        assert!(!critical.issues.is_empty());
+        assert!(critical
+            .issues
+            .iter()
+            .all(|i| i.level == IssueLevel::Critical));
+        assert!(results.issues.len() > critical.issues.len());
+        assert_eq!(
+            results.with_min_level(IssueLevel::Info).issues,
+            results.issues
+        );
+    }
+}
+
+#[cfg(test)]
+mod file_entry_tests {
+    use spath_cli::models::{IssueLevel, PathEntry, PathLocation};
+    use spath_cli::scanner::{PathScanner, FILE_ENTRY_MESSAGE};
+    use std::fs;
+
+    #[test]
+    fn test_file_entry_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("git.exe");
+        fs::write(&file, "").unwrap();
+        let file = file.to_string_lossy().to_string();
+        let results = PathScanner::from_string(file.clone()).scan().unwrap();
+        let issue = results
+            .issues
+            .iter()
+            .find(|i| i.message == FILE_ENTRY_MESSAGE)
+            .expect("file entry should be flagged");
+        assert_eq!(issue.path, file);
+        assert_eq!(issue.level, IssueLevel::Warning);
+    }
+
+    #[test]
+    fn test_directory_entry_is_not_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let results = PathScanner::from_string(dir.path().to_string_lossy().to_string())
+            .scan()
+            .unwrap();
+        assert!(results
+            .issues
+            .iter()
+            .all(|i| i.message != FILE_ENTRY_MESSAGE));
+    }
+
+    #[test]
+    fn test_path_entry_records_whether_it_is_a_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("tool.exe");
+        fs::write(&file, "").unwrap();
+        let paths = vec![
+            dir.path().to_string_lossy().to_string(),
+            file.to_string_lossy().to_string(),
+        ];
+        let dir_entry = PathEntry::new(paths[0].clone(), 0, PathLocation::User, &paths);
+        let file_entry = PathEntry::new(paths[1].clone(), 1, PathLocation::User, &paths);
+        assert!(dir_entry.is_directory && !dir_entry.is_file());
+        assert!(file_entry.exists && !file_entry.is_directory);
+        assert!(file_entry.has_issues());
+        assert!(file_entry
+            .get_warnings()
+            .contains(&"PATH entry is a file, not a directory".to_string()));
+    }
+}
+
+#[cfg(test)]
+mod issue_sort_tests {
+    use spath_cli::models::{IssueLevel, PathIssue};
+    use spath_cli::scanner::{IssueSort, PathScanner, ScanResults};
+
+    fn results() -> ScanResults {
+        let mut results = PathScanner::from_string("").scan().unwrap();
+        results.issues = vec![
+            PathIssue::info("\"C:\\Zed\"", "info"),
+            PathIssue::warning("c:\\b", "first warning"),
+            PathIssue::critical("C:\\Program Files\\App", "critical"),
+            PathIssue::warning("C:\\a\\longer", "second warning"),
+        ];
+        results
+    }
+
+    fn paths(issues: &[&PathIssue]) -> Vec<String> {
+        issues.iter().map(|i| i.path.clone()).collect()
+    }
+
+    #[test]
+    fn test_name_sort_ignores_case_and_quotes() {
+        let results = results();
+        assert_eq!(
+            paths(&results.sorted_issues(Some(IssueSort::Name))),
+            vec![
+                "C:\\a\\longer",
+                "c:\\b",
+                "C:\\Program Files\\App",
+                "\"C:\\Zed\""
+            ]
+        );
+    }
+
+    #[test]
+    fn test_length_sort_is_longest_first() {
+        let results = results();
+        let sorted = results.sorted_issues(Some(IssueSort::Length));
+        assert_eq!(sorted[0].path, "C:\\Program Files\\App");
+        assert_eq!(sorted[3].path, "c:\\b");
+    }
+
+    #[test]
+    fn test_status_sort_is_stable_and_leaves_results_unchanged() {
+        let results = results();
+        let levels: Vec<IssueLevel> = results
+            .sorted_issues(Some(IssueSort::Status))
+            .iter()
+            .map(|i| i.level)
+            .collect();
+        assert_eq!(
+            levels,
+            vec![
+                IssueLevel::Critical,
+                IssueLevel::Warning,
+                IssueLevel::Warning,
+                IssueLevel::Info
+            ]
+        );
+        let sorted = results.sorted_issues(Some(IssueSort::Status));
+        assert_eq!(sorted[1].message, "first warning");
+        assert_eq!(results.issues[0].level, IssueLevel::Info);
+        assert_eq!(
+            paths(&results.sorted_issues(None)),
+            paths(&results.issues.iter().collect::<Vec<_>>())
+        );
+    }
+}
+
+#[cfg(test)]
+mod reserved_chars_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::{PathScanner, RESERVED_CHARS_MESSAGE};
+
+    fn reserved_issues(path: &str) -> Vec<String> {
+        PathScanner::from_string(path)
+            .scan()
+            .unwrap()
+            .issues
+            .into_iter()
+            .filter(|i| i.message.starts_with(RESERVED_CHARS_MESSAGE))
+            .inspect(|i| assert_eq!(i.level, IssueLevel::Warning))
+            .map(|i| i.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_reserved_character_is_named() {
+        let issues = reserved_issues("C:\\Bad<Dir");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].ends_with(": '<'"));
+    }
+
+    #[test]
+    fn test_quoted_path_is_not_flagged() {
+        assert!(reserved_issues("\"C:\\Program Files\\App\"").is_empty());
+    }
+
+    #[test]
+    fn test_interior_quote_and_pipe_are_flagged() {
+        let issues = reserved_issues("C:\\Bad\"Dir|x");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].ends_with(": '\"', '|'"));
+    }
+
+    #[test]
+    fn test_extended_length_prefix_is_allowed() {
+        assert!(reserved_issues("\\\\?\\C:\\Tools").is_empty());
+    }
+}
+
+#[cfg(test)]
+mod path_table_tests {
+    use spath_cli::formatter::ConsoleFormatter;
+    use spath_cli::glyphs::ASCII_GLYPHS;
+    use spath_cli::scanner::PathScanner;
+    use spath_cli::utils::truncate_with_ellipsis;
+
+    #[test]
+    fn test_truncate_with_ellipsis() {
+        assert_eq!(truncate_with_ellipsis("C:\\Tools", 20, "…"), "C:\\Tools");
+        assert_eq!(
+            truncate_with_ellipsis("C:\\Program Files", 8, "…"),
+            "C:\\Prog…"
+        );
+        assert_eq!(
+            truncate_with_ellipsis("C:\\Program Files", 8, "..."),
+            "C:\\Pr..."
+        );
+    }
+
+    #[test]
+    fn test_table_has_one_row_per_entry_within_width() {
+        colored::control::set_override(false);
+        let long = format!("C:\\{}", "deep\\".repeat(30));
+        let path = format!("C:\\Windows;C:\\spath_missing_dir;{};C:\\Windows", long);
+        let results = PathScanner::from_string(path).scan().unwrap();
+        let table = ConsoleFormatter::render_path_table(&results, 80, &ASCII_GLYPHS);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 2 + 3);
+        assert!(
+            lines.iter().all(|line| line.chars().count() <= 80),
+            "{}",
+            table
+        );
+        assert!(lines[2].starts_with("0 | !"));
+        assert!(lines[2].contains("Duplicate path entry"));
+        assert!(lines[3].starts_with("1 | !"));
+        assert!(lines[3].contains("Path does not exist"));
+        assert!(lines[4].starts_with("2 |"));
+        assert!(lines[4].contains("..."));
+    }
+}
+
+#[cfg(test)]
+mod offline_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::{PathScanner, DUPLICATE_PATH_MESSAGE, UNQUOTED_PATH_MESSAGE};
+    use std::path::Path;
+
+    fn no_filesystem(_: &Path) -> bool {
+        panic!("offline scan touched the filesystem")
+    }
+
+    fn scan_offline(path_string: &str) -> spath_cli::scanner::ScanResults {
+        PathScanner::from_string(path_string)
+            .with_existence_check(no_filesystem)
+            .with_writable_check(no_filesystem)
+            .with_offline(true)
+            .scan()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_missing_entry_is_not_reported_offline() {
+        let results = scan_offline("C:\\spath_missing_dir");
+        assert!(results.offline);
+        assert!(results.issues.is_empty());
+        assert_eq!(results.audit.non_existent, 0);
+        assert_eq!(results.audit.valid_paths, 1);
+    }
+
+    #[test]
+    fn test_structure_is_still_checked_offline() {
+        let results = scan_offline("C:\\Program Files\\App;tools;C:\\Program Files\\App");
+        let messages: Vec<(&str, &str)> = results
+            .issues
+            .iter()
+            .map(|i| (i.path.as_str(), i.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                ("C:\\Program Files\\App", UNQUOTED_PATH_MESSAGE),
+                (
+                    "tools",
+                    "Relative path detected - should use absolute paths"
+                ),
+                ("C:\\Program Files\\App", DUPLICATE_PATH_MESSAGE),
+                ("C:\\Program Files\\App", UNQUOTED_PATH_MESSAGE),
+            ]
+        );
+        assert!(results
+            .issues
+            .iter()
+            .all(|i| i.level != IssueLevel::Critical));
+    }
+
+    #[test]
+    fn test_current_directory_is_critical_offline() {
+        let results = scan_offline(".");
+        assert_eq!(results.issues.len(), 1);
+        assert_eq!(results.issues[0].level, IssueLevel::Critical);
+    }
+}
+
+#[cfg(test)]
+mod progress_tests {
+    use spath_cli::scanner::PathScanner;
+
+    #[test]
+    fn test_progress_bar_does_not_change_results() {
+        let current = "C:\\Windows;C:\\spath_missing_dir;tools;C:\\Windows";
+        let plain = PathScanner::from_string(current).scan().unwrap();
+        let with_progress = PathScanner::from_string(current)
+            .with_progress(true)
+            .scan()
+            .unwrap();
+        assert_eq!(with_progress.issues, plain.issues);
+        assert_eq!(with_progress.paths, plain.paths);
+    }
+}
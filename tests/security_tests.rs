@@ -191,3 +191,298 @@ fn test_acl_on_ini_file() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_primary_exploit_candidate_for_program_files() {
+    use spath_cli::security::exploits::primary_exploit_candidate;
+    assert_eq!(
+        primary_exploit_candidate("C:\\Program Files\\Git\\cmd").as_deref(),
+        Some("C:\\Program.exe")
+    );
+    assert_eq!(
+        primary_exploit_candidate("\"C:\\Program Files\\Git\\cmd\"").as_deref(),
+        Some("C:\\Program.exe")
+    );
+    assert_eq!(primary_exploit_candidate("C:\\Windows\\System32"), None);
+}
+
+#[test]
+fn test_scanner_critical_message_names_exploit_candidate() {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::PathScanner;
+    let results = PathScanner::from_string("C:\\Program Files\\Common Files")
+        .scan()
+        .unwrap();
+    let critical = results
+        .issues
+        .iter()
+        .find(|i| matches!(i.level, IssueLevel::Critical))
+        .expect("unquoted Program Files path should be critical");
+    assert!(critical
+        .message
+        .contains("attacker could place C:\\Program.exe"));
+}
+
+#[cfg(test)]
+mod expanded_exploit_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::PathScanner;
+    use std::path::Path;
+
+    fn always_exists(_: &Path) -> bool {
+        true
+    }
+
+    fn has_critical(path_var: &str) -> bool {
+        PathScanner::from_string(path_var)
+            .with_existence_check(always_exists)
+            .scan()
+            .unwrap()
+            .issues
+            .iter()
+            .any(|i| i.level == IssueLevel::Critical)
+    }
+
+    #[test]
+    fn test_unquoted_program_files_var_is_critical_after_expansion() {
+        assert!(has_critical("%ProgramFiles%\\App bin"));
+        assert!(!has_critical("\"%ProgramFiles%\\App bin\""));
+    }
+
+    #[test]
+    fn test_var_expanding_to_spaces_is_critical_without_literal_space() {
+        std::env::set_var("SPATH_TEST_TOOLS_ROOT", "C:\\Program Files\\Tools");
+        assert!(has_critical("%SPATH_TEST_TOOLS_ROOT%\\bin"));
+    }
+}
+
+#[cfg(test)]
+mod writable_dir_tests {
+    use spath_cli::models::{PathEntry, PathLocation};
+    use spath_cli::security::acl::is_writable_by_non_admin;
+    use std::process::Command;
+
+    /// Grants BUILTIN\Users (S-1-5-32-545) write access through icacls.
+    fn grant_users_write(dir: &std::path::Path) {
+        let status = Command::new("icacls")
+            .arg(dir)
+            .args(["/grant", "*S-1-5-32-545:(W)"])
+            .output()
+            .expect("icacls must be available");
+        assert!(status.status.success());
+    }
+
+    #[test]
+    fn test_missing_directory_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(is_writable_by_non_admin(&dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_windows_directory_is_not_writable_by_users() {
+        assert!(!is_writable_by_non_admin(std::path::Path::new("C:\\Windows")).unwrap());
+    }
+
+    #[test]
+    fn test_users_write_grant_is_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        grant_users_write(dir.path());
+        assert!(is_writable_by_non_admin(dir.path()).unwrap());
+        let path = dir.path().to_string_lossy().to_string();
+        let mut entry = PathEntry::new(path.clone(), 0, PathLocation::User, &[path]);
+        assert!(entry.check_acl());
+        assert!(entry
+            .get_warnings()
+            .contains(&"Writable by non-admin users".to_string()));
+    }
+}
+
+#[cfg(test)]
+mod scanner_writable_dir_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::{PathScanner, WRITABLE_BY_OTHERS_MESSAGE};
+    use std::process::Command;
+
+    fn writable_issues(path: &str) -> Vec<IssueLevel> {
+        PathScanner::from_string(path)
+            .scan()
+            .unwrap()
+            .issues
+            .into_iter()
+            .filter(|i| i.message == WRITABLE_BY_OTHERS_MESSAGE)
+            .map(|i| i.level)
+            .collect()
+    }
+
+    #[test]
+    fn test_everyone_write_grant_is_critical() {
+        let dir = tempfile::tempdir().unwrap();
+        // S-1-1-0 is Everyone.
+        let output = Command::new("icacls")
+            .arg(dir.path())
+            .args(["/grant", "*S-1-1-0:(W)"])
+            .output()
+            .expect("icacls must be available");
+        assert!(output.status.success());
+        let levels = writable_issues(&dir.path().to_string_lossy());
+        assert_eq!(levels, vec![IssueLevel::Critical]);
+    }
+
+    #[test]
+    fn test_private_directory_is_not_flagged() {
+        let dir = tempfile::tempdir().unwrap();
+        spath_cli::security::acl::set_user_only_acl(dir.path()).unwrap();
+        assert!(writable_issues(&dir.path().to_string_lossy()).is_empty());
+    }
+
+    #[test]
+    fn test_missing_directory_is_not_checked() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing");
+        assert!(writable_issues(&missing.to_string_lossy()).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod verify_issues_tests {
+    use spath_cli::models::PathIssue;
+    use spath_cli::security::exploits::verify_issues;
+    use std::fs;
+    use std::path::Path;
+
+    fn always_writable(_: &Path) -> bool {
+        true
+    }
+
+    fn never_writable(_: &Path) -> bool {
+        false
+    }
+
+    #[test]
+    fn test_exploitable_warning_is_severity_adjusted() {
+        let dir = tempfile::tempdir().unwrap();
+        let entry = dir.path().join("My Tools").join("bin");
+        fs::write(dir.path().join("My Tools.exe"), "").unwrap();
+        let entry = entry.to_string_lossy().to_string();
+        let warning = PathIssue::warning(entry.clone(), "Path contains spaces but is not quoted");
+        let duplicate = PathIssue::warning(entry.clone(), "Duplicate path entry");
+        let plain = PathIssue::warning("C:\\Tools", "Path does not exist");
+        let (results, summary) = verify_issues(&[&warning, &duplicate, &plain], always_writable);
+        assert_eq!(summary.total_checked, 1);
+        assert!(results[0].is_exploitable);
+        assert!(results[0].severity_adjusted);
+        let critical = PathIssue::critical(entry, "Path contains spaces without quotes");
+        let (results, _) = verify_issues(&[&warning, &critical], always_writable);
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].severity_adjusted);
+    }
+
+    #[test]
+    fn test_paths_without_writable_target_are_not_verified() {
+        let issue = PathIssue::warning(
+            "D:\\My Tools\\bin",
+            "Path contains spaces but is not quoted",
+        );
+        let (results, summary) = verify_issues(&[&issue], never_writable);
+        assert!(results.is_empty());
+        assert_eq!(summary.total_checked, 0);
+        let (results, _) = verify_issues(&[&issue], always_writable);
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_variable_entry_is_verified_expanded() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("My Tools.exe"), "").unwrap();
+        std::env::set_var("SPATH_TEST_VERIFY_ROOT", dir.path());
+        let issue = PathIssue::critical(
+            "%SPATH_TEST_VERIFY_ROOT%\\My Tools\\bin",
+            "Path contains spaces without quotes",
+        );
+        let (results, summary) = verify_issues(&[&issue], always_writable);
+        std::env::remove_var("SPATH_TEST_VERIFY_ROOT");
+        assert_eq!(summary.total_checked, 1);
+        assert_eq!(
+            results[0].path,
+            format!("{}\\My Tools\\bin", dir.path().display())
+        );
+        assert!(results[0].is_exploitable);
+    }
+}
+
+#[cfg(test)]
+mod exploit_target_tests {
+    use spath_cli::models::IssueLevel;
+    use spath_cli::scanner::PathScanner;
+    use spath_cli::security::exploits::{exploit_target_dirs, is_path_exploitable};
+    use std::path::{Path, PathBuf};
+
+    fn always_exists(_: &Path) -> bool {
+        true
+    }
+
+    fn always_writable(_: &Path) -> bool {
+        true
+    }
+
+    fn never_writable(_: &Path) -> bool {
+        false
+    }
+
+    fn levels(path_var: &str, writable: fn(&Path) -> bool) -> Vec<IssueLevel> {
+        PathScanner::from_string(path_var)
+            .with_existence_check(always_exists)
+            .with_writable_check(writable)
+            .scan()
+            .unwrap()
+            .issues
+            .iter()
+            .map(|i| i.level)
+            .collect()
+    }
+
+    #[test]
+    fn test_target_dir_is_directory_preceding_the_space() {
+        assert_eq!(
+            exploit_target_dirs("D:\\My Tools\\bin"),
+            vec![PathBuf::from("D:\\")]
+        );
+        assert_eq!(
+            exploit_target_dirs("\"E:\\Apps\\Dev Kit\\bin\""),
+            vec![PathBuf::from("E:\\Apps")]
+        );
+        assert!(exploit_target_dirs("C:\\Windows\\System32").is_empty());
+    }
+
+    #[test]
+    fn test_exploitability_follows_writable_check() {
+        assert!(is_path_exploitable("D:\\My Tools\\bin", always_writable));
+        assert!(!is_path_exploitable("D:\\My Tools\\bin", never_writable));
+    }
+
+    #[test]
+    fn test_unquoted_path_on_other_drive_is_flagged() {
+        assert!(levels("D:\\My Tools\\bin", always_writable).contains(&IssueLevel::Critical));
+        assert!(!levels("D:\\My Tools\\bin", never_writable).contains(&IssueLevel::Critical));
+    }
+
+    #[test]
+    fn test_writable_temp_prefix_is_flagged() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("My Tools").join("bin")).unwrap();
+        let status = std::process::Command::new("icacls")
+            .arg(dir.path())
+            .args(["/grant", "*S-1-5-32-545:(W)"])
+            .output()
+            .expect("icacls must be available");
+        assert!(status.status.success());
+        let entry = dir.path().join("My Tools").join("bin");
+        let results = PathScanner::from_string(entry.to_string_lossy().to_string())
+            .scan()
+            .unwrap();
+        assert!(results
+            .issues
+            .iter()
+            .any(|i| i.level == IssueLevel::Critical && i.message.contains("could be exploited")));
+    }
+}
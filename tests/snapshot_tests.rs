@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod export_tests {
+    use spath_cli::snapshot::PathSnapshot;
+    use std::fs;
+
+    #[test]
+    fn test_export_round_trips_both_scopes() {
+        let snapshot =
+            PathSnapshot::from_values(Some("C:\\Windows;C:\\Windows\\System32"), Some("C:\\Tools"));
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("path.txt");
+        snapshot.write_to(&file, false).unwrap();
+        let text = fs::read_to_string(&file).unwrap();
+        assert!(text.contains("C:\\Windows\\System32\n"));
+        assert_eq!(PathSnapshot::read_from(&file).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn test_export_refuses_to_overwrite_without_force() {
+        let snapshot = PathSnapshot::from_values(None, Some("C:\\Tools"));
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("path.txt");
+        fs::write(&file, "keep me").unwrap();
+        assert!(snapshot.write_to(&file, false).is_err());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "keep me");
+        snapshot.write_to(&file, true).unwrap();
+        assert!(fs::read_to_string(&file).unwrap().contains("C:\\Tools"));
+    }
+
+    #[test]
+    fn test_plain_list_without_headers_is_user_path() {
+        let snapshot = PathSnapshot::parse("C:\\Tools\r\n\r\n# my tools\nC:\\Go\\bin\n").unwrap();
+        assert_eq!(
+            snapshot.user,
+            Some(vec!["C:\\Tools".to_string(), "C:\\Go\\bin".to_string()])
+        );
+        assert!(snapshot.system.is_none());
+    }
+
+    #[test]
+    fn test_parse_rejects_separator_inside_entry() {
+        let err = PathSnapshot::parse("C:\\Tools;C:\\Go\\bin\n").unwrap_err();
+        assert!(err.to_string().contains("Line 1"));
+    }
+}
+
+#[cfg(test)]
+mod import_tests {
+    use spath_cli::snapshot::{ImportMode, PathSnapshot};
+
+    #[test]
+    fn test_replace_reports_added_and_removed() {
+        let snapshot = PathSnapshot::parse("C:\\Tools\nC:\\Go\\bin\n").unwrap();
+        let plan = snapshot
+            .plan_import("C:\\tools;C:\\Old", ImportMode::Replace)
+            .unwrap();
+        assert_eq!(plan.new_path, "C:\\Tools;C:\\Go\\bin");
+        assert_eq!(plan.added, vec!["C:\\Go\\bin".to_string()]);
+        assert_eq!(plan.removed, vec!["C:\\Old".to_string()]);
+    }
+
+    #[test]
+    fn test_merge_appends_only_missing_entries() {
+        let snapshot = PathSnapshot::parse("C:\\TOOLS\nC:\\Go\\bin\n").unwrap();
+        let plan = snapshot
+            .plan_import("C:\\Tools;C:\\Old", ImportMode::Merge)
+            .unwrap();
+        assert_eq!(plan.new_path, "C:\\Tools;C:\\Old;C:\\Go\\bin");
+        assert!(plan.removed.is_empty());
+    }
+
+    #[test]
+    fn test_import_requires_user_section() {
+        let snapshot = PathSnapshot::from_values(Some("C:\\Windows"), None);
+        assert!(snapshot
+            .plan_import("C:\\Tools", ImportMode::Replace)
+            .is_err());
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod health_history_tests {
+    use spath_cli::backup::{BackupManager, PathBackup};
+    use spath_cli::stats::{health_history, HealthSnapshot};
+    use std::fs;
+    use std::path::Path;
+
+    fn write_backup(dir: &Path, timestamp: &str, user_path: &str, system_path: Option<&str>) {
+        let backup = PathBackup::new(
+            timestamp.to_string(),
+            user_path.to_string(),
+            system_path.map(str::to_string),
+        );
+        fs::write(
+            dir.join(format!("path_backup_{}.json", timestamp)),
+            serde_json::to_string(&backup).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_history_is_oldest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().to_string_lossy().to_string();
+        write_backup(dir.path(), "20240101_000000", "C:\\NoSuchDir_spath", None);
+        write_backup(dir.path(), "20240102_000000", &existing, None);
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let history = health_history(&manager, None).unwrap();
+        let timestamps: Vec<&str> = history.iter().map(|s| s.timestamp.as_str()).collect();
+        assert_eq!(timestamps, vec!["20240101_000000", "20240102_000000"]);
+        assert_eq!(history[0].user_health_score, 0);
+        assert_eq!(history[1].user_health_score, 100);
+    }
+
+    #[test]
+    fn test_last_keeps_most_recent_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        for day in 1..=3 {
+            write_backup(
+                dir.path(),
+                &format!("2024010{}_000000", day),
+                "C:\\Tools",
+                None,
+            );
+        }
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let history = health_history(&manager, Some(2)).unwrap();
+        let timestamps: Vec<&str> = history.iter().map(|s| s.timestamp.as_str()).collect();
+        assert_eq!(timestamps, vec!["20240102_000000", "20240103_000000"]);
+    }
+
+    #[test]
+    fn test_corrupted_backup_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        write_backup(dir.path(), "20240101_000000", "C:\\Tools", None);
+        write_backup(dir.path(), "20240102_000000", "C:\\Tools", None);
+        let tampered = dir.path().join("path_backup_20240102_000000.json");
+        let json = fs::read_to_string(&tampered).unwrap();
+        fs::write(&tampered, json.replace("C:\\\\Tools", "C:\\\\Evil")).unwrap();
+        let manager = BackupManager::with_backup_dir(dir.path()).unwrap();
+        let history = health_history(&manager, None).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].timestamp, "20240101_000000");
+    }
+
+    #[test]
+    fn test_system_scope_is_scored_when_captured() {
+        let snapshot = HealthSnapshot::from_values(
+            "20240101_000000",
+            "b.json",
+            "C:\\Tools",
+            Some("C:\\Windows"),
+        )
+        .unwrap();
+        assert!(snapshot.system.is_some());
+        assert!(snapshot.system_health_score.is_some());
+        let user_only =
+            HealthSnapshot::from_values("20240101_000000", "b.json", "C:\\Tools", None).unwrap();
+        assert!(user_only.system_health_score.is_none());
+    }
+}
@@ -161,3 +161,385 @@ mod error_handling_tests {
         assert!(error.starts_with("Error:"));
     }
 }
+
+#[cfg(test)]
+mod normalization_tests {
+    use spath_cli::models::{PathEntry, PathLocation};
+    use spath_cli::scanner::PathScanner;
+    use spath_cli::utils::{normalize_for_comparison, strip_trailing_dots_and_spaces};
+
+    #[test]
+    fn test_trailing_dot_is_folded() {
+        assert_eq!(
+            normalize_for_comparison("C:\\Tools."),
+            normalize_for_comparison("C:\\Tools")
+        );
+        assert_eq!(
+            normalize_for_comparison("\"C:\\My Tools . \\bin\""),
+            normalize_for_comparison("C:\\my tools\\BIN")
+        );
+    }
+
+    #[test]
+    fn test_relative_components_are_kept() {
+        assert_eq!(strip_trailing_dots_and_spaces("."), ".");
+        assert_eq!(strip_trailing_dots_and_spaces("..\\bin."), "..\\bin");
+    }
+
+    #[test]
+    fn test_trailing_dot_entries_are_duplicates() {
+        let paths = vec!["C:\\Tools.".to_string(), "C:\\Tools".to_string()];
+        let entry = PathEntry::new(paths[0].clone(), 0, PathLocation::User, &paths);
+        assert!(entry.is_duplicate);
+        let results = PathScanner::from_string("C:\\Tools.;C:\\Tools")
+            .scan()
+            .unwrap();
+        let duplicates: Vec<_> = results
+            .issues
+            .iter()
+            .filter(|i| i.message == "Duplicate path entry")
+            .collect();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].path, "C:\\Tools");
+    }
+}
+
+#[cfg(test)]
+mod redaction_tests {
+    use spath_cli::formatter::JsonFormatter;
+    use spath_cli::scanner::PathScanner;
+    use spath_cli::utils::redact_path;
+    use std::path::Path;
+
+    fn always_exists(_: &Path) -> bool {
+        true
+    }
+
+    fn always_writable(_: &Path) -> bool {
+        true
+    }
+
+    #[test]
+    fn test_redact_username() {
+        assert_eq!(
+            redact_path("C:\\Users\\alice\\.cargo\\bin", false),
+            "C:\\Users\\<USER>\\.cargo\\bin"
+        );
+        assert_eq!(
+            redact_path("\"C:\\Users\\alice\"", false),
+            "\"C:\\Users\\<USER>\""
+        );
+        assert_eq!(redact_path("C:\\Windows", false), "C:\\Windows");
+    }
+
+    #[test]
+    fn test_hash_custom_directories() {
+        let redacted = redact_path("D:\\AcmeCorp\\Tools\\bin", true);
+        assert!(redacted.starts_with("D:\\dir-24992d\\"));
+        assert!(redacted.ends_with("\\bin"));
+        assert!(!redacted.contains("AcmeCorp"));
+        assert_eq!(redacted, redact_path("D:\\acmecorp\\tools\\bin", true));
+    }
+
+    #[test]
+    fn test_redacted_scan_keeps_classification() {
+        let path_var =
+            "C:\\Users\\alice\\spath_missing;C:\\Users\\alice\\spath_missing;C:\\Windows";
+        let results = PathScanner::from_string(path_var).scan().unwrap();
+        let redacted = results.redacted(false);
+        assert!(redacted.paths.iter().all(|p| !p.contains("alice")));
+        assert!(redacted.issues.iter().all(|i| !i.path.contains("alice")));
+        assert_eq!(redacted.paths[0], "C:\\Users\\<USER>\\spath_missing");
+        assert_eq!(redacted.issues.len(), results.issues.len());
+        for (before, after) in results.issues.iter().zip(&redacted.issues) {
+            assert_eq!(before.level, after.level);
+        }
+        assert_eq!(redacted.audit.non_existent, results.audit.non_existent);
+        assert_eq!(redacted.audit.valid_paths, results.audit.valid_paths);
+    }
+
+    #[test]
+    fn test_hashed_scan_hides_custom_directories_everywhere() {
+        let results = PathScanner::from_string("D:\\Secret Tools\\bin;\\\\acmehost\\acmeshare")
+            .with_existence_check(always_exists)
+            .with_writable_check(always_writable)
+            .scan()
+            .unwrap();
+        assert!(results
+            .issues
+            .iter()
+            .any(|i| i.message.contains("D:\\Secret.exe")));
+        let output = JsonFormatter::render(&[results.redacted(true)]).unwrap();
+        for name in ["Secret", "Tools", "acmehost", "acmeshare"] {
+            assert!(!output.contains(name), "{} in {}", name, output);
+        }
+        assert!(output.contains("(attacker could place D:\\\\dir-"));
+    }
+}
+
+#[cfg(test)]
+mod expansion_tests {
+    use spath_cli::scanner::{PathScanner, MISSING_PATH_MESSAGE, UNRESOLVED_VARS_MESSAGE};
+    use spath_cli::utils::expand_vars;
+
+    #[test]
+    fn test_percent_inside_value_is_not_reexpanded() {
+        std::env::set_var("SPATH_TEST_PCT_HOME", "C:\\Users\\100%SPATH_TEST_PCT_DEV");
+        std::env::set_var("SPATH_TEST_PCT_DEV", "WRONG");
+        let result = expand_vars("%SPATH_TEST_PCT_HOME%\\.cargo\\bin");
+        std::env::remove_var("SPATH_TEST_PCT_HOME");
+        std::env::remove_var("SPATH_TEST_PCT_DEV");
+        assert_eq!(
+            result.expanded,
+            "C:\\Users\\100%SPATH_TEST_PCT_DEV\\.cargo\\bin"
+        );
+        assert!(result.is_complete());
+    }
+
+    #[test]
+    fn test_expansion_continues_past_unset_variable() {
+        std::env::set_var("SPATH_TEST_SET_ROOT", "C:\\Tools");
+        let result = expand_vars("%SPATH_TEST_UNSET_VAR%;%SPATH_TEST_SET_ROOT%\\bin");
+        assert_eq!(result.expanded, "%SPATH_TEST_UNSET_VAR%;C:\\Tools\\bin");
+        assert_eq!(result.unresolved, vec!["SPATH_TEST_UNSET_VAR".to_string()]);
+    }
+
+    #[test]
+    fn test_literal_percent_signs_are_kept() {
+        let result = expand_vars("C:\\50% off\\100%");
+        assert_eq!(result.expanded, "C:\\50% off\\100%");
+        assert!(result.is_complete());
+    }
+
+    #[test]
+    fn test_scanner_reports_unset_variable_instead_of_missing() {
+        let results = PathScanner::from_string("%SPATH_TEST_NEVER_SET%\\bin")
+            .scan()
+            .unwrap();
+        assert!(results
+            .issues
+            .iter()
+            .any(|i| i.message.starts_with(UNRESOLVED_VARS_MESSAGE)
+                && i.message.contains("%SPATH_TEST_NEVER_SET%")));
+        assert!(results
+            .issues
+            .iter()
+            .all(|i| i.message != MISSING_PATH_MESSAGE));
+    }
+}
+
+#[cfg(test)]
+mod nested_expansion_tests {
+    use spath_cli::models::{PathEntry, PathLocation};
+    use spath_cli::utils::{expand_env_vars, expand_vars};
+
+    #[test]
+    fn test_nested_reference_is_expanded() {
+        std::env::set_var("SPATH_TEST_NEST_INNER", "C:\\Tools");
+        std::env::set_var("SPATH_TEST_NEST_OUTER", "%SPATH_TEST_NEST_INNER%\\bin");
+        let result = expand_vars("%SPATH_TEST_NEST_OUTER%");
+        assert_eq!(result.expanded, "C:\\Tools\\bin");
+        assert!(result.is_complete());
+    }
+
+    #[test]
+    fn test_cyclic_references_terminate() {
+        std::env::set_var("SPATH_TEST_CYCLE_A", "%SPATH_TEST_CYCLE_B%");
+        std::env::set_var("SPATH_TEST_CYCLE_B", "%SPATH_TEST_CYCLE_A%");
+        let result = expand_vars("%SPATH_TEST_CYCLE_A%\\bin");
+        assert_eq!(result.expanded, "%SPATH_TEST_CYCLE_A%\\bin");
+        assert_eq!(result.unresolved, vec!["SPATH_TEST_CYCLE_A".to_string()]);
+    }
+
+    #[test]
+    fn test_variable_reused_inside_another_value_is_expanded() {
+        let profile = std::env::var("USERPROFILE").unwrap();
+        std::env::set_var("SPATH_TEST_PROFILE_BIN", "%USERPROFILE%\\bin");
+        let result = expand_vars("%USERPROFILE%\\%SPATH_TEST_PROFILE_BIN%");
+        assert_eq!(result.expanded, format!("{}\\{}\\bin", profile, profile));
+        assert!(result.is_complete());
+    }
+
+    #[test]
+    fn test_quoted_entry_with_nested_reference_resolves() {
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var("SPATH_TEST_ENTRY_INNER", dir.path());
+        std::env::set_var("SPATH_TEST_ENTRY_OUTER", "%SPATH_TEST_ENTRY_INNER%");
+        let paths = vec!["\"%SPATH_TEST_ENTRY_OUTER%\"".to_string()];
+        let entry = PathEntry::new(paths[0].clone(), 0, PathLocation::User, &paths);
+        assert_eq!(entry.path, "\"%SPATH_TEST_ENTRY_OUTER%\"");
+        assert_eq!(entry.resolve_env_vars(), dir.path().to_string_lossy());
+        assert!(entry.exists);
+    }
+
+    #[test]
+    fn test_unset_reference_does_not_exist() {
+        let paths = vec!["\"%SPATH_TEST_ENTRY_UNSET%\\bin\"".to_string()];
+        let entry = PathEntry::new(paths[0].clone(), 0, PathLocation::User, &paths);
+        assert_eq!(entry.resolve_env_vars(), "%SPATH_TEST_ENTRY_UNSET%\\bin");
+        assert!(!entry.exists);
+    }
+
+    #[test]
+    fn test_self_reference_terminates() {
+        std::env::set_var("SPATH_TEST_CYCLE_SELF", "x%SPATH_TEST_CYCLE_SELF%");
+        assert_eq!(
+            expand_env_vars("%SPATH_TEST_CYCLE_SELF%"),
+            "x%SPATH_TEST_CYCLE_SELF%"
+        );
+    }
+}
+
+#[cfg(test)]
+mod separator_normalization_tests {
+    use spath_cli::fixer::{FixOptions, PathFixer};
+    use spath_cli::utils::normalize_for_comparison;
+
+    #[test]
+    fn test_trailing_backslash_and_quotes_share_a_key() {
+        let key = normalize_for_comparison("C:\\Tools");
+        assert_eq!(normalize_for_comparison("C:\\Tools\\"), key);
+        assert_eq!(normalize_for_comparison("\"C:\\Tools\""), key);
+        assert_eq!(normalize_for_comparison("C:\\\\Tools/"), key);
+        assert_eq!(normalize_for_comparison("C:\\Tools\\..\\Tools\\."), key);
+    }
+
+    #[test]
+    fn test_drive_root_and_unc_prefix_are_kept() {
+        assert_eq!(normalize_for_comparison("C:\\"), "c:\\");
+        assert_eq!(normalize_for_comparison("\"C:\\\""), "c:\\");
+        assert_eq!(normalize_for_comparison("C:"), "c:");
+        assert_eq!(
+            normalize_for_comparison("\\\\Server\\Share\\"),
+            "\\\\server\\share"
+        );
+        assert_eq!(normalize_for_comparison("..\\bin\\"), "..\\bin");
+    }
+
+    #[test]
+    fn test_fix_collapses_variants_into_one_entry() {
+        let results = PathFixer::plan_fix(
+            "C:\\Windows;C:\\Windows\\;\"C:\\Windows\";C:\\",
+            &FixOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(results.new_path, "C:\\Windows;C:\\");
+    }
+}
+
+#[cfg(test)]
+mod user_tool_category_tests {
+    use spath_cli::models::PathCategory;
+    use spath_cli::utils::{categorize_path, user_tool_name};
+
+    const CASES: &[(&str, PathCategory, Option<&str>)] = &[
+        (
+            "%USERPROFILE%\\.pyenv\\pyenv-win\\shims",
+            PathCategory::UserProgram,
+            Some("pyenv shims"),
+        ),
+        (
+            "D:\\dev\\.pyenv\\shims",
+            PathCategory::UserProgram,
+            Some("pyenv shims"),
+        ),
+        (
+            "%USERPROFILE%\\go\\bin",
+            PathCategory::UserProgram,
+            Some("Go workspace"),
+        ),
+        (
+            "%USERPROFILE%\\.rbenv\\shims",
+            PathCategory::UserProgram,
+            Some("rbenv shims"),
+        ),
+        (
+            "%USERPROFILE%\\.nvm",
+            PathCategory::UserProgram,
+            Some("nvm"),
+        ),
+        ("C:\\Go\\bin", PathCategory::Ambiguous, None),
+        ("C:\\Tools", PathCategory::Ambiguous, None),
+    ];
+
+    #[test]
+    fn test_user_tool_directories_are_categorized_and_named() {
+        for (path, category, tool) in CASES {
+            assert_eq!(categorize_path(path), *category, "{}", path);
+            assert_eq!(user_tool_name(path), *tool, "{}", path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod status_mark_tests {
+    use spath_cli::formatter::SYSTEM_PATH_FAILED_MESSAGE;
+    use spath_cli::glyphs::UNICODE_GLYPHS;
+
+    #[test]
+    fn test_status_marks_are_single_characters() {
+        assert_eq!(UNICODE_GLYPHS.status(true), "\u{2713}");
+        assert_eq!(UNICODE_GLYPHS.status(false), "\u{2717}");
+    }
+
+    #[test]
+    fn test_status_output_has_no_mojibake() {
+        for text in [
+            UNICODE_GLYPHS.check,
+            UNICODE_GLYPHS.cross,
+            SYSTEM_PATH_FAILED_MESSAGE,
+        ] {
+            assert!(!text.contains('\u{c3}'), "{}", text);
+            assert!(!text.contains('\u{e2}'), "{}", text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod color_choice_tests {
+    use spath_cli::utils::should_colorize;
+
+    #[test]
+    fn test_explicit_choice_wins() {
+        assert!(should_colorize(Some(true), Some("1"), false));
+        assert!(!should_colorize(Some(false), None, true));
+    }
+
+    #[test]
+    fn test_no_color_disables_only_when_non_empty() {
+        assert!(!should_colorize(None, Some("1"), true));
+        assert!(should_colorize(None, Some(""), true));
+        assert!(should_colorize(None, None, true));
+    }
+
+    #[test]
+    fn test_auto_needs_a_terminal() {
+        assert!(!should_colorize(None, None, false));
+    }
+}
+
+#[cfg(test)]
+mod mojibake_tests {
+    // A UTF-8 source re-saved as Windows-1252 turns `✓` into `âœ“`; these
+    // lead characters never appear in the English output otherwise.
+    const SOURCES: [(&str, &str); 3] = [
+        (
+            "src/formatter/mod.rs",
+            include_str!("../src/formatter/mod.rs"),
+        ),
+        ("src/main.rs", include_str!("../src/main.rs")),
+        (
+            "src/visualizer/mod.rs",
+            include_str!("../src/visualizer/mod.rs"),
+        ),
+    ];
+
+    #[test]
+    fn test_status_output_sources_have_no_mojibake() {
+        for (name, source) in SOURCES {
+            for lead in ['\u{c2}', '\u{c3}', '\u{e2}'] {
+                assert!(!source.contains(lead), "{} contains {:?}", name, lead);
+            }
+        }
+    }
+}
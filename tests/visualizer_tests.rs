@@ -238,3 +238,93 @@ mod visualizer_tests {
         spath_cli::visualizer::visualize_tree(&paths, false);
     }
 }
+
+#[cfg(test)]
+mod path_counts_tests {
+    use super::*;
+    use spath_cli::utils::normalize_for_comparison;
+
+    #[test]
+    fn test_precomputed_counts_match_pairwise_duplicate_check() {
+        let paths: Vec<String> = (0..1000)
+            .map(|i| match i % 4 {
+                0 => format!("C:\\Tools\\bin{}", i / 8),
+                1 => format!("\"C:\\Program Files\\App{}\"", i),
+                2 => format!("c:\\tools\\BIN{}.", i / 8),
+                _ => format!("D:\\Unique{}", i),
+            })
+            .collect();
+        let counts = PathEntry::path_counts(&paths);
+        let mut duplicates = 0;
+        for (i, path) in paths.iter().enumerate() {
+            let key = normalize_for_comparison(path);
+            let pairwise = paths
+                .iter()
+                .enumerate()
+                .any(|(j, other)| j != i && normalize_for_comparison(other) == key);
+            let counted = PathEntry::with_path_counts(path.clone(), i, PathLocation::User, &counts);
+            let delegated = PathEntry::new(path.clone(), i, PathLocation::User, &paths);
+            assert_eq!(counted.is_duplicate, pairwise, "entry {}", path);
+            assert_eq!(delegated.is_duplicate, pairwise, "entry {}", path);
+            duplicates += usize::from(pairwise);
+        }
+        assert_eq!(duplicates, 500);
+    }
+}
+
+#[cfg(test)]
+mod ascii_mode_tests {
+    use spath_cli::glyphs::{ASCII_GLYPHS, UNICODE_GLYPHS};
+    use spath_cli::scanner::FILE_ENTRY_MESSAGE;
+    use spath_cli::visualizer::{render_simple, render_tree};
+
+    fn sample_paths() -> Vec<String> {
+        vec![
+            "C:\\Windows".to_string(),
+            "C:\\Program Files".to_string(),
+            "C:\\NonExistent123".to_string(),
+            "C:\\Users\\test\\.cargo\\bin".to_string(),
+            "c:\\windows".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_ascii_output_is_pure_ascii() {
+        let paths = sample_paths();
+        for use_color in [false, true] {
+            let simple = render_simple(&paths, use_color, &ASCII_GLYPHS);
+            let tree = render_tree(&paths, use_color, &ASCII_GLYPHS);
+            assert!(simple.is_ascii(), "{}", simple);
+            assert!(tree.is_ascii(), "{}", tree);
+        }
+    }
+
+    #[test]
+    fn test_ascii_tree_uses_ascii_branches() {
+        let tree = render_tree(&sample_paths(), false, &ASCII_GLYPHS);
+        assert!(tree.contains("+-- [0] [OK] C:\\Windows"));
+        assert!(tree.contains("\\-- [4]"));
+        assert!(tree.contains("[X] C:\\NonExistent123"));
+    }
+
+    #[test]
+    fn test_file_entry_is_marked_as_file() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let path = file.path().display().to_string();
+        let simple = render_simple(std::slice::from_ref(&path), false, &UNICODE_GLYPHS);
+        assert!(
+            simple.contains(&format!("[0] \u{26a0} {}", path)),
+            "{}",
+            simple
+        );
+        assert!(simple.contains(FILE_ENTRY_MESSAGE));
+        assert!(simple.contains("Files, not directories: 1"));
+    }
+
+    #[test]
+    fn test_unicode_tree_keeps_box_drawing() {
+        let tree = render_tree(&sample_paths(), false, &UNICODE_GLYPHS);
+        assert!(tree.contains("├─ [0] ✓ C:\\Windows"));
+        assert!(!tree.is_ascii());
+    }
+}